@@ -1,6 +1,6 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec,
+    contract, contractimpl, contracttype, symbol_short, Address, Env, String, Symbol, Vec,
 };
 
 #[contracttype]
@@ -12,6 +12,15 @@ pub struct Achievement {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct BurnRecord {
+    pub token_id: u32,
+    pub owner: Address,
+    pub reason: Option<Symbol>,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 pub enum DataKey {
     Achievement(u32),          // Persistent: Individual NFT data
@@ -20,38 +29,126 @@ pub enum DataKey {
     TotalSupply,               // Instance: Current count of NFTs
     Admin,                     // Instance: Contract administrator
     PuzzleCompleted(Address, u32), // Tracks if a user has completed a puzzle
+    ModerationLog,             // Persistent: Recent admin burns (Vec<BurnRecord>)
+    Approved(u32),             // Persistent: Address allowed to transfer a single token
+    Operator(Address, Address), // Persistent: (owner, operator) allowed to transfer all owner's tokens
+    TokenByIndex(u32),         // Persistent: Token ID at a position in the global index
+    TokenIndex(u32),           // Persistent: Position of a token in the global index
+    TokenCount,                // Instance: Number of tokens in the global index
+    Minted(Address, u32),      // Persistent: User already minted the achievement for a puzzle
+    Paused,                    // Instance: Transfers frozen by the admin
+    Name,                      // Instance: Collection name
+    Symbol,                    // Instance: Collection symbol
+    BaseUri,                   // Instance: Prefix for token URIs
+    Verifier,                  // Instance: Contract that marks puzzle completions
 }
 
+const MAX_MODERATION_LOG: u32 = 50;
+const MAX_URI_LEN: usize = 256;
+
 #[contract]
 pub struct AchievementNFT;
 
 #[contractimpl]
 impl AchievementNFT {
-    /// Initialize the contract and set the administrator.
-    pub fn initialize(env: Env, admin: Address) {
+    /// Initialize the contract and set the administrator and collection details.
+    pub fn initialize(env: Env, admin: Address, name: String, symbol: String) {
         if env.storage().instance().has(&DataKey::Admin) {
             panic!("Already initialized");
         }
         env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Name, &name);
+        env.storage().instance().set(&DataKey::Symbol, &symbol);
         env.storage().instance().set(&DataKey::NextTokenId, &1u32);
         env.storage().instance().set(&DataKey::TotalSupply, &0u32);
     }
 
-    /// Admin function to mark a puzzle as completed for a user.
+    /// Mark a puzzle as completed for a user. Called by the verifier if one
+    /// is set, otherwise by the admin.
     pub fn mark_puzzle_completed(env: Env, user: Address, puzzle_id: u32) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
+        let marker: Address = match Self::get_verifier(env.clone()) {
+            Some(verifier) => verifier,
+            None => env.storage().instance().get(&DataKey::Admin).unwrap(),
+        };
+        marker.require_auth();
         env.storage()
             .persistent()
             .set(&DataKey::PuzzleCompleted(user, puzzle_id), &true);
     }
 
+    /// Admin sets the address allowed to mark completions, e.g. the puzzle
+    /// verification contract. The admin keeps every other privilege.
+    pub fn set_verifier(env: Env, admin: Address, verifier: Address) {
+        Self::assert_admin(&env, &admin);
+        env.storage().instance().set(&DataKey::Verifier, &verifier);
+    }
+
+    /// Returns the address allowed to mark completions, if set.
+    pub fn get_verifier(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Verifier)
+    }
+
+    /// Mint an achievement for a completed puzzle. Consumes the completion flag.
+    pub fn mint(env: Env, to: Address, puzzle_id: u32, metadata: String) -> u32 {
+        to.require_auth();
+        Self::claim_completion(&env, &to, puzzle_id);
+        Self::mint_token(&env, to, puzzle_id, metadata)
+    }
+
+    /// Admin mints the same achievement to every recipient, consuming each
+    /// recipient's completion flag. Panics if any recipient is not eligible.
+    pub fn batch_mint(
+        env: Env,
+        admin: Address,
+        recipients: Vec<Address>,
+        puzzle_id: u32,
+        metadata: String,
+    ) -> Vec<u32> {
+        Self::assert_admin(&env, &admin);
+
+        let mut token_ids = Vec::new(&env);
+        for to in recipients.iter() {
+            Self::claim_completion(&env, &to, puzzle_id);
+            token_ids.push_back(Self::mint_token(&env, to, puzzle_id, metadata.clone()));
+        }
+        token_ids
+    }
+
+    /// Returns whether the user has already minted the achievement for a puzzle.
+    pub fn has_minted(env: Env, user: Address, puzzle_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Minted(user, puzzle_id))
+            .unwrap_or(false)
+    }
+
+    /// Consume a completion flag, allowing only one achievement per user and puzzle.
+    fn claim_completion(env: &Env, to: &Address, puzzle_id: u32) {
+        if Self::has_minted(env.clone(), to.clone(), puzzle_id) {
+            panic!("Achievement already minted");
+        }
+
+        let completed_key = DataKey::PuzzleCompleted(to.clone(), puzzle_id);
+        if !env.storage().persistent().get(&completed_key).unwrap_or(false) {
+            panic!("Puzzle not completed");
+        }
+        env.storage().persistent().remove(&completed_key);
+
+        let minted_key = DataKey::Minted(to.clone(), puzzle_id);
+        env.storage().persistent().set(&minted_key, &true);
+        env.storage().persistent().extend_ttl(&minted_key, 100_000, 500_000);
+    }
+
     /// Mint a new NFT for crafting purposes (testnet: no auth required).
     pub fn craftmint(env: Env, to: Address, puzzle_id: u32, metadata: String) -> u32 {
         // For testnet deployment, remove admin auth requirement
         // let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         // admin.require_auth();
 
+        Self::mint_token(&env, to, puzzle_id, metadata)
+    }
+
+    fn mint_token(env: &Env, to: Address, puzzle_id: u32, metadata: String) -> u32 {
         let token_id: u32 = env.storage().instance().get(&DataKey::NextTokenId).unwrap();
 
         let achievement = Achievement {
@@ -73,6 +170,11 @@ impl AchievementNFT {
         env.storage().persistent().set(&collection_key, &collection);
         env.storage().persistent().extend_ttl(&collection_key, 100_000, 500_000);
 
+        // Update global index
+        let count: u32 = env.storage().instance().get(&DataKey::TokenCount).unwrap_or(0);
+        Self::set_token_index(env, count, token_id);
+        env.storage().instance().set(&DataKey::TokenCount, &(count + 1));
+
         // Update Counters
         env.storage().instance().set(&DataKey::NextTokenId, &(token_id + 1));
         let total: u32 = env.storage().instance().get(&DataKey::TotalSupply).unwrap_or(0);
@@ -84,9 +186,94 @@ impl AchievementNFT {
         token_id
     }
 
+    /// Admin freezes or unfreezes all transfers.
+    pub fn set_paused(env: Env, admin: Address, paused: bool) {
+        Self::assert_admin(&env, &admin);
+        env.storage().instance().set(&DataKey::Paused, &paused);
+        env.events().publish((symbol_short!("paused"),), paused);
+    }
+
+    /// Returns whether transfers are frozen.
+    pub fn is_paused(env: Env) -> bool {
+        env.storage().instance().get(&DataKey::Paused).unwrap_or(false)
+    }
+
+    fn assert_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if *admin != stored_admin {
+            panic!("Unauthorized");
+        }
+    }
+
     /// Transfers a token safely
     pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) {
         from.require_auth();
+        Self::transfer_token(&env, from, to, token_id);
+    }
+
+    /// Transfers a token on the owner's behalf. The spender must be the owner,
+    /// the token's approved address, or an operator for the owner.
+    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32) {
+        spender.require_auth();
+
+        if spender != from
+            && Self::get_approved(env.clone(), token_id) != Some(spender.clone())
+            && !Self::is_approved_for_all(env.clone(), from.clone(), spender.clone())
+        {
+            panic!("Not approved");
+        }
+
+        Self::transfer_token(&env, from, to, token_id);
+    }
+
+    /// Owner approves an address to transfer a single token. Cleared on transfer.
+    pub fn approve(env: Env, owner: Address, operator: Address, token_id: u32) {
+        owner.require_auth();
+
+        if Self::owner_of(env.clone(), token_id) != owner {
+            panic!("Not the owner");
+        }
+
+        let key = DataKey::Approved(token_id);
+        env.storage().persistent().set(&key, &operator);
+        env.storage().persistent().extend_ttl(&key, 100_000, 500_000);
+
+        env.events().publish((symbol_short!("approve"), owner, operator), token_id);
+    }
+
+    /// Owner grants or revokes an operator's right to transfer all their tokens.
+    pub fn set_approval_for_all(env: Env, owner: Address, operator: Address, approved: bool) {
+        owner.require_auth();
+
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        if approved {
+            env.storage().persistent().set(&key, &true);
+            env.storage().persistent().extend_ttl(&key, 100_000, 500_000);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        env.events().publish((symbol_short!("appr_all"), owner, operator), approved);
+    }
+
+    /// Returns the address approved for a single token, if any.
+    pub fn get_approved(env: Env, token_id: u32) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Approved(token_id))
+    }
+
+    /// Whether the operator may transfer all of the owner's tokens.
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Operator(owner, operator))
+            .unwrap_or(false)
+    }
+
+    fn transfer_token(env: &Env, from: Address, to: Address, token_id: u32) {
+        if Self::is_paused(env.clone()) {
+            panic!("Transfers are paused");
+        }
 
         if from == to {
             panic!("Cannot transfer to self");
@@ -120,6 +307,9 @@ impl AchievementNFT {
         env.storage().persistent().set(&DataKey::Achievement(token_id), &achievement);
         env.storage().persistent().extend_ttl(&DataKey::Achievement(token_id), 100_000, 500_000);
 
+        // A single-token approval does not survive a change of owner
+        env.storage().persistent().remove(&DataKey::Approved(token_id));
+
         env.events().publish((symbol_short!("transfer"), from, to), token_id);
     }
 
@@ -131,6 +321,29 @@ impl AchievementNFT {
             .unwrap_or(Vec::new(&env))
     }
 
+    /// Returns the number of tokens owned by an address.
+    pub fn balance_of(env: Env, owner: Address) -> u32 {
+        Self::get_collection(env, owner).len()
+    }
+
+    /// Returns the ID of the token at `index` among all existing tokens.
+    /// Burning moves the last token into the burned token's position.
+    pub fn token_by_index(env: Env, index: u32) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TokenByIndex(index))
+            .expect("Index out of bounds")
+    }
+
+    fn set_token_index(env: &Env, index: u32, token_id: u32) {
+        let by_index = DataKey::TokenByIndex(index);
+        let index_of = DataKey::TokenIndex(token_id);
+        env.storage().persistent().set(&by_index, &token_id);
+        env.storage().persistent().set(&index_of, &index);
+        env.storage().persistent().extend_ttl(&by_index, 100_000, 500_000);
+        env.storage().persistent().extend_ttl(&index_of, 100_000, 500_000);
+    }
+
     /// Get owner of a specific token.
     pub fn owner_of(env: Env, token_id: u32) -> Address {
         let achievement: Achievement = env
@@ -147,7 +360,7 @@ impl AchievementNFT {
     }
 
     /// Destroys a token (testnet: no auth required for crafting).
-    pub fn burn(env: Env, token_id: u32) {
+    pub fn burn(env: Env, token_id: u32, reason: Option<Symbol>) {
         let achievement: Achievement = env
             .storage()
             .persistent()
@@ -157,6 +370,62 @@ impl AchievementNFT {
         // For testnet deployment, remove owner auth requirement
         // achievement.owner.require_auth();
 
+        Self::burn_token(&env, token_id, &achievement, reason);
+    }
+
+    /// Admin burns any token, recording the reason in the moderation log.
+    pub fn admin_burn(env: Env, admin: Address, token_id: u32, reason: Option<Symbol>) {
+        Self::assert_admin(&env, &admin);
+
+        let achievement: Achievement = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Achievement(token_id))
+            .expect("Token does not exist");
+
+        // Keep only the most recent admin burns
+        let mut log = Self::get_moderation_log(env.clone());
+        if log.len() >= MAX_MODERATION_LOG {
+            log.pop_front();
+        }
+        log.push_back(BurnRecord {
+            token_id,
+            owner: achievement.owner.clone(),
+            reason: reason.clone(),
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&DataKey::ModerationLog, &log);
+        env.storage().persistent().extend_ttl(&DataKey::ModerationLog, 100_000, 500_000);
+
+        Self::burn_token(&env, token_id, &achievement, reason);
+    }
+
+    /// Admin replaces a token's metadata, e.g. to fix a URI or update traits.
+    pub fn update_metadata(env: Env, admin: Address, token_id: u32, new_metadata: String) {
+        Self::assert_admin(&env, &admin);
+
+        let key = DataKey::Achievement(token_id);
+        let mut achievement: Achievement = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Token does not exist");
+        achievement.metadata = new_metadata.clone();
+        env.storage().persistent().set(&key, &achievement);
+        env.storage().persistent().extend_ttl(&key, 100_000, 500_000);
+
+        env.events().publish((symbol_short!("meta_upd"), token_id), new_metadata);
+    }
+
+    /// Returns recent admin burns, oldest first.
+    pub fn get_moderation_log(env: Env) -> Vec<BurnRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ModerationLog)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    fn burn_token(env: &Env, token_id: u32, achievement: &Achievement, reason: Option<Symbol>) {
         let mut collection = Self::get_collection(env.clone(), achievement.owner.clone());
         if let Some(index) = collection.first_index_of(token_id) {
             collection.remove(index);
@@ -164,10 +433,87 @@ impl AchievementNFT {
         }
 
         env.storage().persistent().remove(&DataKey::Achievement(token_id));
+        env.storage().persistent().remove(&DataKey::Approved(token_id));
+
+        // Swap-remove from the global index: the last token takes this slot
+        if let Some(index) = env.storage().persistent().get::<_, u32>(&DataKey::TokenIndex(token_id)) {
+            let count: u32 = env.storage().instance().get(&DataKey::TokenCount).unwrap();
+            let last = count - 1;
+            if index != last {
+                let last_token: u32 = env.storage().persistent().get(&DataKey::TokenByIndex(last)).unwrap();
+                Self::set_token_index(env, index, last_token);
+            }
+            env.storage().persistent().remove(&DataKey::TokenByIndex(last));
+            env.storage().persistent().remove(&DataKey::TokenIndex(token_id));
+            env.storage().instance().set(&DataKey::TokenCount, &last);
+        }
+
         let total: u32 = env.storage().instance().get(&DataKey::TotalSupply).unwrap();
         env.storage().instance().set(&DataKey::TotalSupply, &(total - 1));
 
-        env.events().publish((symbol_short!("burn"), achievement.owner), token_id);
+        env.events().publish((symbol_short!("burn"), achievement.owner.clone()), (token_id, reason));
+    }
+
+    /// Returns the collection name.
+    pub fn name(env: Env) -> String {
+        env.storage().instance().get(&DataKey::Name).unwrap()
+    }
+
+    /// Returns the collection symbol.
+    pub fn symbol(env: Env) -> String {
+        env.storage().instance().get(&DataKey::Symbol).unwrap()
+    }
+
+    /// Admin sets the prefix token URIs are built from. An empty URI falls
+    /// back to each token's own metadata.
+    pub fn set_base_uri(env: Env, admin: Address, base_uri: String) {
+        Self::assert_admin(&env, &admin);
+        // Leave room for the longest u32 token id
+        if base_uri.len() as usize > MAX_URI_LEN - 10 {
+            panic!("Base URI too long");
+        }
+        env.storage().instance().set(&DataKey::BaseUri, &base_uri);
+    }
+
+    /// Returns the token's URI: the base URI followed by the token id, or the
+    /// token's metadata when no base URI is set.
+    pub fn token_uri(env: Env, token_id: u32) -> String {
+        let achievement: Achievement = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Achievement(token_id))
+            .expect("Token does not exist");
+
+        let base_uri: String = env
+            .storage()
+            .instance()
+            .get(&DataKey::BaseUri)
+            .unwrap_or(String::from_str(&env, ""));
+        if base_uri.is_empty() {
+            return achievement.metadata;
+        }
+
+        let mut buf = [0u8; MAX_URI_LEN];
+        let base_len = base_uri.len() as usize;
+        base_uri.copy_into_slice(&mut buf[..base_len]);
+
+        // Append the token id in decimal
+        let mut digits = [0u8; 10];
+        let mut n = token_id;
+        let mut count = 0;
+        loop {
+            digits[count] = b'0' + (n % 10) as u8;
+            count += 1;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        for i in 0..count {
+            buf[base_len + i] = digits[count - 1 - i];
+        }
+
+        String::from_bytes(&env, &buf[..base_len + count])
     }
 
     /// Returns full achievement details.
@@ -1,7 +1,11 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    Address, Env, String, Symbol, TryFromVal, Val, Vec,
+};
 
 #[test]
 fn test_nft_lifecycle() {
@@ -15,7 +19,7 @@ fn test_nft_lifecycle() {
     let user_a = Address::generate(&env);
     let user_b = Address::generate(&env);
 
-    client.initialize(&admin);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
 
     // Mark puzzle completed (admin calls it → mocked auth allows it)
     let puzzle_id = 101u32;
@@ -46,7 +50,7 @@ fn test_nft_lifecycle() {
     assert_eq!(user_b_collection.get(0).unwrap(), token_id);
 
     // Burn from current owner (user_b)
-    client.burn(&token_id);
+    client.burn(&token_id, &None);
 
     assert_eq!(client.total_supply(), 0u32);
     assert!(client.get_achievement(&token_id).is_none());
@@ -62,8 +66,8 @@ fn test_already_initialized() {
     let client = AchievementNFTClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
-    client.initialize(&admin);
-    client.initialize(&admin); // should panic
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH")); // should panic
 }
 
 #[test]
@@ -79,7 +83,7 @@ fn test_transfer_non_existent_token() {
     let user_a = Address::generate(&env);
     let user_b = Address::generate(&env);
 
-    client.initialize(&admin);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
 
     client.transfer(&user_a, &user_b, &999u32); // non-existent
 }
@@ -96,7 +100,7 @@ fn test_mint_without_puzzle_completion() {
     let admin = Address::generate(&env);
     let user = Address::generate(&env);
 
-    client.initialize(&admin);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
 
     let metadata = String::from_str(&env, "Unauthorized Mint");
     let puzzle_id = 202u32;
@@ -115,7 +119,7 @@ fn test_transfer_to_self() {
     let admin = Address::generate(&env);
     let user = Address::generate(&env);
 
-    client.initialize(&admin);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
 
     let puzzle_id = 303u32;
     client.mark_puzzle_completed(&user, &puzzle_id);
@@ -124,4 +128,489 @@ fn test_transfer_to_self() {
     let token_id = client.mint(&user, &puzzle_id, &metadata);
 
     client.transfer(&user, &user, &token_id); // should panic
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_admin_burn_records_reason() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let puzzle_id = 404u32;
+    client.mark_puzzle_completed(&user, &puzzle_id);
+    let token_id = client.mint(&user, &puzzle_id, &String::from_str(&env, "Wrongly Minted"));
+
+    let reason = symbol_short!("mismint");
+    client.admin_burn(&admin, &token_id, &Some(reason.clone()));
+
+    assert_eq!(client.total_supply(), 0u32);
+    assert_eq!(client.get_collection(&user).len(), 0);
+
+    // Reason is included in the burn event
+    let mut burned: Option<(u32, Option<Symbol>)> = None;
+    for (_, topics, data) in env.events().all().iter() {
+        let first: Val = topics.get(0).unwrap();
+        if Symbol::try_from_val(&env, &first) == Ok(symbol_short!("burn")) {
+            burned = Some(<(u32, Option<Symbol>)>::try_from_val(&env, &data).unwrap());
+        }
+    }
+    assert_eq!(burned, Some((token_id, Some(reason.clone()))));
+
+    // And recorded in the moderation log
+    let log = client.get_moderation_log();
+    assert_eq!(log.len(), 1);
+    let record = log.get(0).unwrap();
+    assert_eq!(record.token_id, token_id);
+    assert_eq!(record.owner, user);
+    assert_eq!(record.reason, Some(reason));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_admin_burn_non_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let token_id = client.craftmint(&user, &1u32, &String::from_str(&env, "Craft"));
+    client.admin_burn(&user, &token_id, &None);
+}
+
+#[test]
+fn test_admin_updates_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let token_id = client.craftmint(&user, &1u32, &String::from_str(&env, "ipfs://old"));
+    let new_metadata = String::from_str(&env, "ipfs://new");
+    client.update_metadata(&admin, &token_id, &new_metadata);
+
+    let achievement = client.get_achievement(&token_id).unwrap();
+    assert_eq!(achievement.metadata, new_metadata);
+    assert_eq!(achievement.owner, user);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(Symbol::try_from_val(&env, &topics.get(0).unwrap()), Ok(symbol_short!("meta_upd")));
+    assert_eq!(u32::try_from_val(&env, &topics.get(1).unwrap()), Ok(token_id));
+    assert_eq!(String::try_from_val(&env, &data), Ok(new_metadata));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_owner_cannot_update_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let token_id = client.craftmint(&user, &1u32, &String::from_str(&env, "ipfs://old"));
+    client.update_metadata(&user, &token_id, &String::from_str(&env, "ipfs://mine"));
+}
+
+#[test]
+fn test_approved_transfer_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let market = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let token_id = client.craftmint(&owner, &1u32, &String::from_str(&env, "Approved"));
+    client.approve(&owner, &market, &token_id);
+    assert_eq!(client.get_approved(&token_id), Some(market.clone()));
+
+    client.transfer_from(&market, &owner, &buyer, &token_id);
+    assert_eq!(client.owner_of(&token_id), buyer);
+    assert_eq!(client.get_collection(&buyer).len(), 1);
+
+    // The approval is cleared, so the market cannot move it again
+    assert_eq!(client.get_approved(&token_id), None);
+    assert!(client.try_transfer_from(&market, &buyer, &owner, &token_id).is_err());
+}
+
+#[test]
+fn test_operator_transfer_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let first = client.craftmint(&owner, &1u32, &String::from_str(&env, "One"));
+    let second = client.craftmint(&owner, &2u32, &String::from_str(&env, "Two"));
+    client.set_approval_for_all(&owner, &operator, &true);
+    assert!(client.is_approved_for_all(&owner, &operator));
+
+    client.transfer_from(&operator, &owner, &buyer, &first);
+    client.transfer_from(&operator, &owner, &buyer, &second);
+    assert_eq!(client.get_collection(&buyer).len(), 2);
+
+    // Revoked operators lose access
+    client.set_approval_for_all(&buyer, &operator, &false);
+    assert!(!client.is_approved_for_all(&buyer, &operator));
+    assert!(client.try_transfer_from(&operator, &buyer, &owner, &first).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Not approved")]
+fn test_unapproved_transfer_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let token_id = client.craftmint(&owner, &1u32, &String::from_str(&env, "Mine"));
+    client.transfer_from(&spender, &owner, &spender, &token_id);
+}
+
+#[test]
+#[should_panic(expected = "Not the owner")]
+fn test_approve_requires_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let token_id = client.craftmint(&owner, &1u32, &String::from_str(&env, "Mine"));
+    client.approve(&other, &other, &token_id);
+}
+
+#[test]
+fn test_batch_mint() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let puzzle_id = 7u32;
+    let mut recipients = Vec::new(&env);
+    for _ in 0..3 {
+        let user = Address::generate(&env);
+        client.mark_puzzle_completed(&user, &puzzle_id);
+        recipients.push_back(user);
+    }
+
+    let metadata = String::from_str(&env, "Quest Finisher");
+    let token_ids = client.batch_mint(&admin, &recipients, &puzzle_id, &metadata);
+
+    assert_eq!(token_ids, Vec::from_array(&env, [1u32, 2, 3]));
+    assert_eq!(client.total_supply(), 3);
+    for i in 0..3 {
+        let user = recipients.get(i).unwrap();
+        assert_eq!(client.owner_of(&token_ids.get(i).unwrap()), user);
+        // Completion flags are consumed
+        assert!(client.try_mint(&user, &puzzle_id, &metadata).is_err());
+    }
+}
+
+#[test]
+fn test_batch_mint_rejects_ineligible_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let eligible = Address::generate(&env);
+    let ineligible = Address::generate(&env);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let puzzle_id = 7u32;
+    client.mark_puzzle_completed(&eligible, &puzzle_id);
+
+    let recipients = Vec::from_array(&env, [eligible.clone(), ineligible]);
+    let metadata = String::from_str(&env, "Quest Finisher");
+    assert!(client.try_batch_mint(&admin, &recipients, &puzzle_id, &metadata).is_err());
+
+    // Nothing was minted and the eligible player keeps their flag
+    assert_eq!(client.total_supply(), 0);
+    assert_eq!(client.mint(&eligible, &puzzle_id, &metadata), 1);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_batch_mint_non_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+    client.mark_puzzle_completed(&user, &7u32);
+
+    let recipients = Vec::from_array(&env, [user.clone()]);
+    client.batch_mint(&user, &recipients, &7u32, &String::from_str(&env, "Mine"));
+}
+
+#[test]
+fn test_enumeration_after_burn() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user_a = Address::generate(&env);
+    let user_b = Address::generate(&env);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let metadata = String::from_str(&env, "Enumerable");
+    let first = client.craftmint(&user_a, &1u32, &metadata);
+    let second = client.craftmint(&user_a, &2u32, &metadata);
+    let third = client.craftmint(&user_b, &3u32, &metadata);
+
+    assert_eq!(client.balance_of(&user_a), 2);
+    assert_eq!(client.balance_of(&user_b), 1);
+    assert_eq!(client.token_by_index(&0), first);
+    assert_eq!(client.token_by_index(&1), second);
+    assert_eq!(client.token_by_index(&2), third);
+
+    client.burn(&second, &None);
+
+    assert_eq!(client.balance_of(&user_a), 1);
+    assert_eq!(client.total_supply(), 2);
+    assert_eq!(client.token_by_index(&0), first);
+    assert_eq!(client.token_by_index(&1), third);
+    assert!(client.try_token_by_index(&2).is_err());
+
+    // Transfers move balances but leave the global index alone
+    client.transfer(&user_a, &user_b, &first);
+    assert_eq!(client.balance_of(&user_a), 0);
+    assert_eq!(client.balance_of(&user_b), 2);
+    assert_eq!(client.token_by_index(&0), first);
+
+    // Burning the first token moves the last one into its slot
+    client.burn(&first, &None);
+    assert_eq!(client.total_supply(), 1);
+    assert_eq!(client.token_by_index(&0), third);
+    assert!(client.try_token_by_index(&1).is_err());
+
+    client.burn(&third, &None);
+    assert_eq!(client.total_supply(), 0);
+    assert!(client.try_token_by_index(&0).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Achievement already minted")]
+fn test_remint_after_remark_blocked() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let puzzle_id = 505u32;
+    let metadata = String::from_str(&env, "Once Only");
+    client.mark_puzzle_completed(&user, &puzzle_id);
+    client.mint(&user, &puzzle_id, &metadata);
+    assert!(client.has_minted(&user, &puzzle_id));
+
+    client.mark_puzzle_completed(&user, &puzzle_id);
+    client.mint(&user, &puzzle_id, &metadata);
+}
+
+#[test]
+fn test_paused_transfers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    let token_id = client.craftmint(&owner, &1u32, &String::from_str(&env, "Frozen"));
+    client.set_approval_for_all(&owner, &operator, &true);
+
+    client.set_paused(&admin, &true);
+    assert!(client.is_paused());
+    assert!(client.try_transfer(&owner, &buyer, &token_id).is_err());
+    assert!(client.try_transfer_from(&operator, &owner, &buyer, &token_id).is_err());
+    assert_eq!(client.owner_of(&token_id), owner);
+
+    // The admin can still clean up while paused
+    let stray = client.craftmint(&owner, &2u32, &String::from_str(&env, "Stray"));
+    client.admin_burn(&admin, &stray, &None);
+    assert_eq!(client.balance_of(&owner), 1);
+
+    client.set_paused(&admin, &false);
+    client.transfer(&owner, &buyer, &token_id);
+    assert_eq!(client.owner_of(&token_id), buyer);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_set_paused_non_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    client.set_paused(&user, &true);
+}
+
+#[test]
+fn test_collection_metadata_and_token_uri() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    assert_eq!(client.name(), String::from_str(&env, "Quest Achievements"));
+    assert_eq!(client.symbol(), String::from_str(&env, "QACH"));
+
+    // Without a base URI the token's own metadata is its URI
+    let metadata = String::from_str(&env, "ipfs://achievement");
+    let token_id = client.craftmint(&user, &1u32, &metadata);
+    assert_eq!(client.token_uri(&token_id), metadata);
+
+    client.set_base_uri(&admin, &String::from_str(&env, "https://quest.example/nft/"));
+    assert_eq!(client.token_uri(&token_id), String::from_str(&env, "https://quest.example/nft/1"));
+
+    for _ in 0..9 {
+        client.craftmint(&user, &1u32, &metadata);
+    }
+    assert_eq!(client.token_uri(&10), String::from_str(&env, "https://quest.example/nft/10"));
+}
+
+#[test]
+#[should_panic(expected = "Token does not exist")]
+fn test_token_uri_unknown_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    client.token_uri(&42);
+}
+
+#[test]
+fn test_verifier_marks_completions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+    assert_eq!(client.get_verifier(), None);
+
+    client.set_verifier(&admin, &verifier);
+    assert_eq!(client.get_verifier(), Some(verifier.clone()));
+
+    client.mark_puzzle_completed(&user, &5u32);
+    assert_eq!(env.auths()[0].0, verifier);
+
+    // The admin still holds its own privileges
+    let metadata = String::from_str(&env, "Puzzle 5");
+    let token_id = client.mint(&user, &5u32, &metadata);
+    client.update_metadata(&admin, &token_id, &String::from_str(&env, "Puzzle 5 (updated)"));
+    client.set_paused(&admin, &true);
+    assert!(client.is_paused());
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_set_verifier_non_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.initialize(&admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+
+    client.set_verifier(&user, &user);
+}
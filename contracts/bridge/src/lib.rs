@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Bytes, BytesN, Env, Map, Vec};
+use soroban_sdk::{contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token, Address, Bytes, BytesN, Env, Map, Vec};
 
 /// Cross-Chain Asset Bridge Contract
 ///
@@ -154,6 +154,9 @@ pub enum DataKey {
     UserNonces(Address),         // u64
     BridgeNonces,                // u64
     FeeBalance(Address),         // i128 - accumulated fees per token
+    Escrowed(Address),           // i128 - locked token amount held per token
+    NftContract,                 // Address - NFT contract trusted for wrapping
+    Locked,                      // bool - set while an asset-moving call is in progress
 }
 
 /// Custom error codes for the bridge contract
@@ -179,6 +182,8 @@ pub enum Error {
     NFTNotWrapped = 16,
     InvalidRecipient = 17,
     ReentrantCall = 18,
+    InsufficientEscrow = 19,
+    UntrustedNFTContract = 20,
 }
 
 // Constants
@@ -186,6 +191,13 @@ const MAX_VALIDATORS: u32 = 50;
 const BASIS_POINTS: u32 = 10000;
 const MAX_CHAIN_ID: u32 = 1000;
 
+/// Interface of the trusted NFT contract (AchievementNFT)
+#[contractclient(name = "NftClient")]
+pub trait NftInterface {
+    fn transfer(env: Env, from: Address, to: Address, token_id: u32);
+    fn owner_of(env: Env, token_id: u32) -> Address;
+}
+
 #[contract]
 pub struct BridgeContract;
 
@@ -200,6 +212,7 @@ impl BridgeContract {
         required_signatures: u32,
         chain_id: u32,
         fee_collector: Address,
+        nft_contract: Address,
     ) -> Result<(), Error> {
         let storage = env.storage().instance();
 
@@ -234,6 +247,7 @@ impl BridgeContract {
         storage.set(&DataKey::ValidatorSetVersion, &1u32);
         storage.set(&DataKey::UserNonces(env.current_contract_address()), &0u64);
         storage.set(&DataKey::BridgeNonces, &0u64);
+        storage.set(&DataKey::NftContract, &nft_contract);
 
         Ok(())
     }
@@ -259,13 +273,11 @@ impl BridgeContract {
         validators.push_back(validator.clone());
         env.storage().instance().set(&DataKey::Validators, &validators);
 
-        // Increment validator set version
-        let version: u32 = env.storage().instance().get(&DataKey::ValidatorSetVersion).unwrap_or(1);
-        env.storage().instance().set(&DataKey::ValidatorSetVersion, &(version + 1));
+        let version = Self::bump_validator_set_version(&env);
 
         env.events().publish(
             (symbol_short!("V_ADD"), validator),
-            version + 1,
+            version,
         );
 
         Ok(())
@@ -296,13 +308,11 @@ impl BridgeContract {
 
         env.storage().instance().set(&DataKey::Validators, &new_validators);
 
-        // Increment validator set version
-        let version: u32 = env.storage().instance().get(&DataKey::ValidatorSetVersion).unwrap_or(1);
-        env.storage().instance().set(&DataKey::ValidatorSetVersion, &(version + 1));
+        let version = Self::bump_validator_set_version(&env);
 
         env.events().publish(
             (symbol_short!("V_REM"), validator),
-            version + 1,
+            version,
         );
 
         Ok(())
@@ -360,6 +370,7 @@ impl BridgeContract {
         recipient: Bytes,
     ) -> Result<BytesN<32>, Error> {
         sender.require_auth();
+        Self::enter_guard(&env)?;
         Self::assert_not_paused(&env)?;
 
         if amount <= 0 {
@@ -410,6 +421,7 @@ impl BridgeContract {
             AssetType::Token => {
                 let token_client = token::Client::new(&env, &asset_address);
                 token_client.transfer(&sender, &env.current_contract_address(), &amount);
+                Self::adjust_escrow(&env, &asset_address, amount);
             }
             AssetType::NFT => {
                 // For NFTs, we need to handle the transfer
@@ -468,6 +480,7 @@ impl BridgeContract {
             (asset_type, amount, dest_chain),
         );
 
+        Self::exit_guard(&env);
         Ok(message_id)
     }
 
@@ -479,6 +492,7 @@ impl BridgeContract {
         signatures: Vec<ValidatorSignature>,
     ) -> Result<(), Error> {
         validator.require_auth();
+        Self::enter_guard(&env)?;
         Self::assert_not_paused(&env)?;
 
         // Verify validator is authorized
@@ -499,8 +513,9 @@ impl BridgeContract {
             }
         }
 
-        // Verify signatures
-        Self::verify_signatures(&env, &message, &signatures, &validators, config.required_signatures)?;
+        // Verify signatures against a single hash of the message
+        let message_hash = Self::message_hash(&env, &message);
+        Self::verify_signatures(&message_hash, &signatures, &validators, config.required_signatures)?;
 
         // Process the bridge action
         match message.action {
@@ -529,6 +544,7 @@ impl BridgeContract {
             (message.action, message.asset_amount),
         );
 
+        Self::exit_guard(&env);
         Ok(())
     }
 
@@ -539,6 +555,7 @@ impl BridgeContract {
         message_id: BytesN<32>,
     ) -> Result<(), Error> {
         caller.require_auth();
+        Self::enter_guard(&env)?;
 
         // Get locked asset info
         let locked_asset: LockedAsset = env.storage().instance()
@@ -561,38 +578,70 @@ impl BridgeContract {
             }
         }
 
-        // Refund assets to owner
-        match locked_asset.asset_type {
-            AssetType::Token => {
-                let token_client = token::Client::new(&env, &locked_asset.asset_address);
-                token_client.transfer(&env.current_contract_address(), &locked_asset.owner, &locked_asset.amount);
-            }
-            AssetType::NFT => {
-                // Handle NFT refund
+        Self::refund_locked_asset(&env, &message_id, &locked_asset, BridgeStatus::Cancelled);
+
+        env.events().publish(
+            (symbol_short!("B_CANCEL"), message_id),
+            locked_asset.amount,
+        );
+
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// Reject an invalid pending message and refund its owner - validator only
+    ///
+    /// Requires the same signature threshold as `complete_bridge`, signed over
+    /// the rejection of `message_id`. The message ends up `Failed`.
+    pub fn validator_reject(
+        env: Env,
+        validator: Address,
+        message_id: BytesN<32>,
+        signatures: Vec<ValidatorSignature>,
+    ) -> Result<(), Error> {
+        validator.require_auth();
+        Self::enter_guard(&env)?;
+
+        let validators: Vec<Address> = env.storage().instance().get(&DataKey::Validators).unwrap_or(Vec::new(&env));
+        if !validators.contains(&validator) {
+            return Err(Error::Unauthorized);
+        }
+
+        let config: BridgeConfig = env.storage().instance().get(&DataKey::Config).unwrap();
+
+        let locked_asset: LockedAsset = env.storage().instance()
+            .get(&DataKey::LockedAssets(message_id.clone()))
+            .ok_or(Error::AssetNotLocked)?;
+
+        let processed: Option<BridgeStatus> = env.storage().instance().get(&DataKey::ProcessedMessages)
+            .and_then(|m: Map<BytesN<32>, BridgeStatus>| m.get(message_id.clone()));
+
+        if let Some(status) = processed {
+            if status != BridgeStatus::Pending {
+                return Err(Error::MessageAlreadyProcessed);
             }
         }
 
-        // Update status
-        let mut processed_messages: Map<BytesN<32>, BridgeStatus> = env.storage().instance()
-            .get(&DataKey::ProcessedMessages)
-            .unwrap_or(Map::new(&env));
-        processed_messages.set(message_id.clone(), BridgeStatus::Cancelled);
-        env.storage().instance().set(&DataKey::ProcessedMessages, &processed_messages);
+        let rejection_hash = Self::rejection_hash(&env, &message_id);
+        Self::verify_signatures(&rejection_hash, &signatures, &validators, config.required_signatures)?;
 
-        // Remove locked asset record
-        env.storage().instance().remove(&DataKey::LockedAssets(message_id.clone()));
+        Self::refund_locked_asset(&env, &message_id, &locked_asset, BridgeStatus::Failed);
+
+        // Store signatures for audit
+        env.storage().instance().set(&DataKey::MessageSignatures(message_id.clone()), &signatures);
 
         env.events().publish(
-            (symbol_short!("B_CANCEL"), message_id),
+            (symbol_short!("B_REJECT"), message_id),
             locked_asset.amount,
         );
 
+        Self::exit_guard(&env);
         Ok(())
     }
 
     // ───────────── NFT WRAPPING FUNCTIONS ─────────────
 
-    /// Wrap an NFT for cross-chain transfer
+    /// Wrap an NFT for cross-chain transfer, escrowing it in the bridge
     pub fn wrap_nft(
         env: Env,
         owner: Address,
@@ -602,8 +651,11 @@ impl BridgeContract {
         recipient: Bytes,
     ) -> Result<i128, Error> {
         owner.require_auth();
+        Self::enter_guard(&env)?;
         Self::assert_not_paused(&env)?;
 
+        let nft_token_id = Self::nft_token_id(&env, &nft_contract, token_id)?;
+
         // Generate wrapped token ID
         let wrapped_token_id = Self::generate_wrapped_token_id(&env, nft_contract.clone(), token_id, dest_chain);
 
@@ -619,8 +671,8 @@ impl BridgeContract {
 
         env.storage().instance().set(&DataKey::WrappedNFTs(wrapped_token_id), &wrapped_nft);
 
-        // TODO: Implement actual NFT transfer from owner to bridge
-        // This would require calling the NFT contract's transfer function
+        // Escrow the original so it cannot be sold while wrapped
+        NftClient::new(&env, &nft_contract).transfer(&owner, &env.current_contract_address(), &nft_token_id);
 
         // Emit wrap event
         env.events().publish(
@@ -628,16 +680,18 @@ impl BridgeContract {
             (nft_contract.clone(), token_id, dest_chain),
         );
 
+        Self::exit_guard(&env);
         Ok(wrapped_token_id)
     }
 
-    /// Unwrap an NFT after cross-chain transfer
+    /// Unwrap an NFT after cross-chain transfer, releasing it from escrow
     pub fn unwrap_nft(
         env: Env,
         owner: Address,
         wrapped_token_id: i128,
     ) -> Result<(Address, i128), Error> {
         owner.require_auth();
+        Self::enter_guard(&env)?;
         Self::assert_not_paused(&env)?;
 
         let wrapped_nft: WrappedNFT = env.storage().instance()
@@ -648,20 +702,21 @@ impl BridgeContract {
             return Err(Error::Unauthorized);
         }
 
-        // TODO: Implement NFT minting/transfer back to owner
-        // This would require calling the original NFT contract
-
         // Remove wrapped NFT record
         env.storage().instance().remove(&DataKey::WrappedNFTs(wrapped_token_id));
 
         let original_contract = wrapped_nft.original_contract;
         let original_token_id = wrapped_nft.original_token_id;
 
+        let nft_token_id = Self::nft_token_id(&env, &original_contract, original_token_id)?;
+        NftClient::new(&env, &original_contract).transfer(&env.current_contract_address(), &owner, &nft_token_id);
+
         env.events().publish(
             (symbol_short!("N_UNWRAP"), wrapped_token_id),
             (original_contract.clone(), original_token_id),
         );
 
+        Self::exit_guard(&env);
         Ok((original_contract, original_token_id))
     }
 
@@ -675,6 +730,10 @@ impl BridgeContract {
         env.storage().instance().get(&DataKey::Validators).unwrap_or(Vec::new(&env))
     }
 
+    pub fn get_validator_set_version(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::ValidatorSetVersion).unwrap_or(1)
+    }
+
     pub fn get_message_status(env: Env, message_id: BytesN<32>) -> Option<BridgeStatus> {
         env.storage().instance().get(&DataKey::ProcessedMessages)
             .and_then(|m: Map<BytesN<32>, BridgeStatus>| m.get(message_id))
@@ -688,6 +747,16 @@ impl BridgeContract {
         env.storage().instance().get(&DataKey::WrappedNFTs(wrapped_token_id))
     }
 
+    /// NFT contract whose tokens can be wrapped
+    pub fn get_nft_contract(env: Env) -> Address {
+        env.storage().instance().get(&DataKey::NftContract).unwrap()
+    }
+
+    /// Amount of `token` currently locked in escrow by the bridge
+    pub fn get_escrowed(env: Env, token: Address) -> i128 {
+        env.storage().instance().get(&DataKey::Escrowed(token)).unwrap_or(0)
+    }
+
     // ───────────── INTERNAL HELPERS ─────────────
 
     fn generate_message_id(
@@ -731,6 +800,16 @@ impl BridgeContract {
         i128::from_be_bytes(bytes)
     }
 
+    /// Check the NFT contract is the trusted one and narrow the bridge's
+    /// i128 token id to the NFT contract's u32 id
+    fn nft_token_id(env: &Env, nft_contract: &Address, token_id: i128) -> Result<u32, Error> {
+        let trusted: Address = env.storage().instance().get(&DataKey::NftContract).unwrap();
+        if *nft_contract != trusted {
+            return Err(Error::UntrustedNFTContract);
+        }
+        u32::try_from(token_id).map_err(|_| Error::InvalidAssetAmount)
+    }
+
     fn get_next_user_nonce(env: &Env, user: &Address) -> u64 {
         let key = DataKey::UserNonces(user.clone());
         let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
@@ -761,26 +840,19 @@ impl BridgeContract {
     }
 
     fn verify_signatures(
-        env: &Env,
-        message: &BridgeMessage,
+        message_hash: &BytesN<32>,
         signatures: &Vec<ValidatorSignature>,
         validators: &Vec<Address>,
         required: u32,
     ) -> Result<(), Error> {
-        if (signatures.len() as u32) < required {
+        if signatures.len() < required {
             return Err(Error::InsufficientSignatures);
         }
 
-        let message_bytes = Self::message_to_bytes(env, message);
-        let message_hash = env.crypto().sha256(&message_bytes);
-
         let mut valid_signatures = 0u32;
 
         for sig in signatures.iter() {
-            if validators.contains(&sig.validator) {
-                // TODO: Implement actual signature verification
-                // For now, we'll assume signatures are valid in tests
-                // In production, this would verify Ed25519 signatures
+            if validators.contains(&sig.validator) && Self::verify_validator_signature(message_hash, &sig) {
                 valid_signatures += 1;
             }
         }
@@ -792,6 +864,26 @@ impl BridgeContract {
         Ok(())
     }
 
+    fn verify_validator_signature(_message_hash: &BytesN<32>, _sig: &ValidatorSignature) -> bool {
+        // TODO: Implement actual signature verification
+        // For now, we'll assume signatures are valid in tests
+        // In production, this would verify the Ed25519 signature over the message hash
+        true
+    }
+
+    /// Hash of the serialized message; computed once per call and shared by every signature check
+    fn message_hash(env: &Env, message: &BridgeMessage) -> BytesN<32> {
+        let message_bytes = Self::message_to_bytes(env, message);
+        env.crypto().sha256(&message_bytes).into()
+    }
+
+    /// Hash validators sign to reject a pending message
+    fn rejection_hash(env: &Env, message_id: &BytesN<32>) -> BytesN<32> {
+        let mut data = Bytes::from_slice(env, b"reject");
+        data.extend_from_slice(&message_id.to_array());
+        env.crypto().sha256(&data).into()
+    }
+
     fn message_to_bytes(env: &Env, message: &BridgeMessage) -> Bytes {
         let mut data = Bytes::new(env);
         data.extend_from_slice(&message.message_id.to_array());
@@ -804,7 +896,7 @@ impl BridgeContract {
         data.extend_from_slice(&[0u8; 32]); // Placeholder for address bytes
         data.extend_from_slice(&message.asset_amount.to_be_bytes());
         data.extend_from_slice(&[0u8; 32]); // Placeholder for sender address
-        data.extend_from_slice(message.recipient.to_buffer::<1024>().as_slice());
+        data.append(&message.recipient);
         data.extend_from_slice(&message.fee_amount.to_be_bytes());
         data.extend_from_slice(&message.timestamp.to_be_bytes());
         data.extend_from_slice(&message.nonce.to_be_bytes());
@@ -816,11 +908,22 @@ impl BridgeContract {
         // This would typically involve checking if the assets are locked
         // and then transferring them to the recipient
 
+        // Unlocking on this chain releases the asset locked under the message id
+        if message.dest_chain == Self::get_chain_id(env) {
+            return Self::release_locked_asset(env, message);
+        }
+
         match message.asset_type {
             AssetType::Token => {
+                // Never release more than the bridge holds in escrow for this token
+                if Self::get_escrowed(env.clone(), message.asset_address.clone()) < message.asset_amount {
+                    return Err(Error::InsufficientEscrow);
+                }
+
                 let token_client = token::Client::new(env, &message.asset_address);
                 let recipient_addr = Self::bytes_to_address(env, &message.recipient)?;
                 token_client.transfer(&env.current_contract_address(), &recipient_addr, &message.asset_amount);
+                Self::adjust_escrow(env, &message.asset_address, -message.asset_amount);
             }
             AssetType::NFT => {
                 // Handle NFT unlock
@@ -831,11 +934,100 @@ impl BridgeContract {
         Ok(())
     }
 
-    fn bytes_to_address(_env: &Env, _bytes: &Bytes) -> Result<Address, Error> {
-        // TODO: Implement proper address conversion from bytes
-        // This requires careful handling of different address formats
-        // For now, return an error to indicate this needs proper implementation
-        Err(Error::InvalidRecipient)
+    /// Pay out the asset locked under `message.message_id` to a Stellar
+    /// recipient on this chain and drop the lock
+    fn release_locked_asset(env: &Env, message: &BridgeMessage) -> Result<(), Error> {
+        let locked_asset: LockedAsset = env.storage().instance()
+            .get(&DataKey::LockedAssets(message.message_id.clone()))
+            .ok_or(Error::AssetNotLocked)?;
+
+        if locked_asset.asset_address != message.asset_address || locked_asset.asset_type != message.asset_type {
+            return Err(Error::InvalidMessage);
+        }
+
+        let recipient_addr = Self::bytes_to_address(env, &message.recipient)?;
+
+        match locked_asset.asset_type {
+            AssetType::Token => {
+                let token_client = token::Client::new(env, &locked_asset.asset_address);
+                token_client.transfer(&env.current_contract_address(), &recipient_addr, &locked_asset.amount);
+                Self::adjust_escrow(env, &locked_asset.asset_address, -locked_asset.amount);
+            }
+            AssetType::NFT => {
+                // Handle NFT release
+            }
+        }
+
+        env.storage().instance().remove(&DataKey::LockedAssets(message.message_id.clone()));
+
+        Ok(())
+    }
+
+    /// Return a locked asset to its owner, record the final status and drop the lock
+    fn refund_locked_asset(env: &Env, message_id: &BytesN<32>, locked_asset: &LockedAsset, status: BridgeStatus) {
+        match locked_asset.asset_type {
+            AssetType::Token => {
+                let token_client = token::Client::new(env, &locked_asset.asset_address);
+                token_client.transfer(&env.current_contract_address(), &locked_asset.owner, &locked_asset.amount);
+                Self::adjust_escrow(env, &locked_asset.asset_address, -locked_asset.amount);
+            }
+            AssetType::NFT => {
+                // Handle NFT refund
+            }
+        }
+
+        let mut processed_messages: Map<BytesN<32>, BridgeStatus> = env.storage().instance()
+            .get(&DataKey::ProcessedMessages)
+            .unwrap_or(Map::new(env));
+        processed_messages.set(message_id.clone(), status);
+        env.storage().instance().set(&DataKey::ProcessedMessages, &processed_messages);
+
+        env.storage().instance().remove(&DataKey::LockedAssets(message_id.clone()));
+    }
+
+    /// Decode a Stellar strkey (`G...` account or `C...` contract) recipient
+    fn bytes_to_address(_env: &Env, bytes: &Bytes) -> Result<Address, Error> {
+        // Other chains' address formats are not valid recipients here
+        if bytes.len() != 56 {
+            return Err(Error::InvalidRecipient);
+        }
+        match bytes.get(0) {
+            Some(b'G') | Some(b'C') => Ok(Address::from_string_bytes(bytes)),
+            _ => Err(Error::InvalidRecipient),
+        }
+    }
+
+    fn adjust_escrow(env: &Env, token: &Address, delta: i128) {
+        let escrowed = Self::get_escrowed(env.clone(), token.clone()) + delta;
+        env.storage().instance().set(&DataKey::Escrowed(token.clone()), &escrowed);
+    }
+
+    /// Increment the validator set version and emit the new version
+    fn bump_validator_set_version(env: &Env) -> u32 {
+        let version: u32 = env.storage().instance().get(&DataKey::ValidatorSetVersion).unwrap_or(1) + 1;
+        env.storage().instance().set(&DataKey::ValidatorSetVersion, &version);
+
+        env.events().publish((symbol_short!("V_VER"),), version);
+
+        version
+    }
+
+    /// Take the reentrancy lock for an asset-moving call.
+    ///
+    /// The host already rejects any call back into a contract that is still
+    /// on the call stack, so this is defense in depth over that rule rather
+    /// than the primary protection. An error return reverts the call's
+    /// storage writes, so only the success path needs `exit_guard`.
+    fn enter_guard(env: &Env) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Locked) {
+            return Err(Error::ReentrantCall);
+        }
+        env.storage().instance().set(&DataKey::Locked, &true);
+        Ok(())
+    }
+
+    fn exit_guard(env: &Env) {
+        env.storage().instance().remove(&DataKey::Locked);
     }
 
     fn get_chain_id(env: &Env) -> u32 {
@@ -863,7 +1055,34 @@ impl BridgeContract {
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
+    use soroban_sdk::{
+        testutils::{Address as _, Events},
+        Env, Symbol, TryFromVal, Val,
+    };
+
+    /// Minimal AchievementNFT-style contract: owner-signed transfers of u32 ids
+    #[contract]
+    pub struct MockNft;
+
+    #[contractimpl]
+    impl MockNft {
+        pub fn mint(env: Env, to: Address, token_id: u32) {
+            env.storage().persistent().set(&token_id, &to);
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) {
+            from.require_auth();
+            let owner: Address = env.storage().persistent().get(&token_id).expect("Token does not exist");
+            if owner != from {
+                panic!("Not the owner");
+            }
+            env.storage().persistent().set(&token_id, &to);
+        }
+
+        pub fn owner_of(env: Env, token_id: u32) -> Address {
+            env.storage().persistent().get(&token_id).expect("Token does not exist")
+        }
+    }
 
     #[test]
     fn test_bridge_initialization() {
@@ -876,7 +1095,7 @@ mod test {
         let admin = Address::generate(&env);
         let fee_collector = Address::generate(&env);
 
-        client.initialize(&admin, &5u32, &0u32, &fee_collector);
+        client.initialize(&admin, &5u32, &0u32, &fee_collector, &Address::generate(&env));
 
         let config = client.get_config();
         assert_eq!(config.admin, admin);
@@ -896,7 +1115,7 @@ mod test {
         let fee_collector = Address::generate(&env);
         let validator = Address::generate(&env);
 
-        client.initialize(&admin, &2u32, &0u32, &fee_collector);
+        client.initialize(&admin, &2u32, &0u32, &fee_collector, &Address::generate(&env));
         client.add_validator(&admin, &validator);
 
         let validators = client.get_validators();
@@ -904,6 +1123,36 @@ mod test {
         assert_eq!(validators.get(0).unwrap(), validator);
     }
 
+    #[test]
+    fn test_validator_set_version_bumps() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BridgeContract);
+        let client = BridgeContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let fee_collector = Address::generate(&env);
+
+        client.initialize(&admin, &2u32, &0u32, &fee_collector, &Address::generate(&env));
+        assert_eq!(client.get_validator_set_version(), 1);
+
+        client.add_validator(&admin, &Address::generate(&env));
+        client.add_validator(&admin, &Address::generate(&env));
+        assert_eq!(client.get_validator_set_version(), 3);
+
+        let mut versions: Vec<u32> = Vec::new(&env);
+        for (_, topics, data) in env.events().all().iter() {
+            let first: Val = topics.get(0).unwrap();
+            if Symbol::try_from_val(&env, &first) == Ok(Symbol::new(&env, "V_VER")) {
+                versions.push_back(u32::try_from_val(&env, &data).unwrap());
+            }
+        }
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions.get(0).unwrap(), 2);
+        assert_eq!(versions.get(1).unwrap(), 3);
+    }
+
     #[test]
     fn test_bridge_assets_token() {
         let env = Env::default();
@@ -918,7 +1167,7 @@ mod test {
         let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
         let token_admin_client = token::StellarAssetClient::new(&env, &token_contract.address());
 
-        client.initialize(&admin, &1u32, &0u32, &fee_collector);
+        client.initialize(&admin, &1u32, &0u32, &fee_collector, &Address::generate(&env));
 
         // For testing, we'll use a mock token - actual minting would be done externally
         // In a real scenario, the user would already have tokens
@@ -940,4 +1189,337 @@ mod test {
         assert_eq!(locked_asset.amount, 500);
         assert_eq!(locked_asset.owner, user);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_escrow_tracks_lock_and_cancel() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BridgeContract);
+        let client = BridgeContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let fee_collector = Address::generate(&env);
+        let user = Address::generate(&env);
+        let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+        let token_admin_client = token::StellarAssetClient::new(&env, &token_contract.address());
+
+        client.initialize(&admin, &1u32, &0u32, &fee_collector, &Address::generate(&env));
+        token_admin_client.mint(&user, &1000);
+
+        assert_eq!(client.get_escrowed(&token_contract.address()), 0);
+
+        let recipient = Bytes::from_array(&env, &[1u8; 32]);
+        let message_id = client.bridge_assets(&user, &token_contract.address(), &AssetType::Token, &500, &1u32, &recipient);
+        assert_eq!(client.get_escrowed(&token_contract.address()), 500);
+
+        client.cancel_bridge(&user, &message_id);
+        assert_eq!(client.get_escrowed(&token_contract.address()), 0);
+    }
+
+    #[test]
+    fn test_validator_reject_refunds_owner() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BridgeContract);
+        let client = BridgeContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let fee_collector = Address::generate(&env);
+        let user = Address::generate(&env);
+        let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+        let token_admin_client = token::StellarAssetClient::new(&env, &token_contract.address());
+        let token_client = token::Client::new(&env, &token_contract.address());
+
+        client.initialize(&admin, &2u32, &0u32, &fee_collector, &Address::generate(&env));
+        token_admin_client.mint(&user, &1000);
+
+        let mut signatures: Vec<ValidatorSignature> = Vec::new(&env);
+        for _ in 0..2 {
+            let validator = Address::generate(&env);
+            client.add_validator(&admin, &validator);
+            signatures.push_back(ValidatorSignature {
+                validator,
+                signature: BytesN::from_array(&env, &[7u8; 64]),
+            });
+        }
+        let validator = signatures.get(0).unwrap().validator;
+
+        let recipient = Bytes::from_array(&env, &[1u8; 32]);
+        let message_id = client.bridge_assets(&user, &token_contract.address(), &AssetType::Token, &500, &1u32, &recipient);
+        assert_eq!(token_client.balance(&user), 500);
+
+        // Below the signature threshold
+        let mut one_signature: Vec<ValidatorSignature> = Vec::new(&env);
+        one_signature.push_back(signatures.get(0).unwrap());
+        let result = client.try_validator_reject(&validator, &message_id, &one_signature);
+        assert_eq!(result, Err(Ok(Error::InsufficientSignatures)));
+
+        client.validator_reject(&validator, &message_id, &signatures);
+        assert_eq!(client.get_message_status(&message_id), Some(BridgeStatus::Failed));
+        assert_eq!(token_client.balance(&user), 1000);
+        assert_eq!(client.get_escrowed(&token_contract.address()), 0);
+        assert!(client.get_locked_asset(&message_id).is_none());
+
+        // A rejected message can no longer be completed or cancelled
+        let message = BridgeMessage {
+            message_id: message_id.clone(),
+            source_chain: 1,
+            dest_chain: 0,
+            action: BridgeAction::Unlock,
+            asset_type: AssetType::Token,
+            asset_address: token_contract.address(),
+            asset_amount: 500,
+            sender: user.clone(),
+            recipient,
+            fee_amount: 0,
+            fee_token: None,
+            timestamp: 0,
+            nonce: 1,
+        };
+        let result = client.try_complete_bridge(&validator, &message, &signatures);
+        assert_eq!(result, Err(Ok(Error::MessageAlreadyProcessed)));
+        let result = client.try_cancel_bridge(&user, &message_id);
+        assert_eq!(result, Err(Ok(Error::AssetNotLocked)));
+
+        // Non-validators cannot reject
+        let result = client.try_validator_reject(&user, &message_id, &signatures);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_complete_bridge_with_max_signatures() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BridgeContract);
+        let client = BridgeContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let fee_collector = Address::generate(&env);
+
+        client.initialize(&admin, &MAX_VALIDATORS, &0u32, &fee_collector, &Address::generate(&env));
+
+        let mut signatures: Vec<ValidatorSignature> = Vec::new(&env);
+        for _ in 0..MAX_VALIDATORS {
+            let validator = Address::generate(&env);
+            client.add_validator(&admin, &validator);
+            signatures.push_back(ValidatorSignature {
+                validator,
+                signature: BytesN::from_array(&env, &[7u8; 64]),
+            });
+        }
+
+        // Unlocking on this chain releases an existing lock to a Stellar recipient
+        let sender = Address::generate(&env);
+        let asset_address = Address::generate(&env);
+        let remote = Bytes::from_array(&env, &[1u8; 32]);
+        let message_id = client.bridge_assets(&sender, &asset_address, &AssetType::NFT, &1, &1u32, &remote);
+        let mut strkey = [0u8; 56];
+        sender.to_string().copy_into_slice(&mut strkey);
+
+        let message = BridgeMessage {
+            message_id: message_id.clone(),
+            source_chain: 1,
+            dest_chain: 0,
+            action: BridgeAction::Unlock,
+            asset_type: AssetType::NFT,
+            asset_address,
+            asset_amount: 1,
+            sender,
+            recipient: Bytes::from_array(&env, &strkey),
+            fee_amount: 0,
+            fee_token: None,
+            timestamp: 0,
+            nonce: 1,
+        };
+
+        // Completing with every validator signing must fit in the default budget
+        env.budget().reset_default();
+        let validator = signatures.get(0).unwrap().validator;
+        client.complete_bridge(&validator, &message, &signatures);
+
+        assert_eq!(client.get_message_status(&message_id), Some(BridgeStatus::Completed));
+    }
+
+    #[test]
+    fn test_wrap_and_unwrap_nft_escrows_token() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BridgeContract);
+        let client = BridgeContractClient::new(&env, &contract_id);
+        let nft_id = env.register_contract(None, MockNft);
+        let nft = MockNftClient::new(&env, &nft_id);
+
+        let admin = Address::generate(&env);
+        let fee_collector = Address::generate(&env);
+        let owner = Address::generate(&env);
+
+        client.initialize(&admin, &1u32, &0u32, &fee_collector, &nft_id);
+        assert_eq!(client.get_nft_contract(), nft_id);
+        nft.mint(&owner, &7);
+
+        let recipient = Bytes::from_array(&env, &[1u8; 32]);
+        let wrapped_id = client.wrap_nft(&owner, &nft_id, &7, &1u32, &recipient);
+        assert_eq!(nft.owner_of(&7), contract_id);
+        assert_eq!(client.get_wrapped_nft(&wrapped_id).unwrap().owner, owner);
+
+        // Only the wrapper can unwrap
+        let stranger = Address::generate(&env);
+        assert_eq!(client.try_unwrap_nft(&stranger, &wrapped_id), Err(Ok(Error::Unauthorized)));
+
+        assert_eq!(client.unwrap_nft(&owner, &wrapped_id), (nft_id.clone(), 7));
+        assert_eq!(nft.owner_of(&7), owner);
+        assert!(client.get_wrapped_nft(&wrapped_id).is_none());
+    }
+
+    #[test]
+    fn test_wrap_nft_rejects_untrusted_contract() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BridgeContract);
+        let client = BridgeContractClient::new(&env, &contract_id);
+        let trusted = env.register_contract(None, MockNft);
+        let other_id = env.register_contract(None, MockNft);
+        let other = MockNftClient::new(&env, &other_id);
+
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        client.initialize(&admin, &1u32, &0u32, &Address::generate(&env), &trusted);
+        other.mint(&owner, &7);
+
+        let recipient = Bytes::from_array(&env, &[1u8; 32]);
+        let result = client.try_wrap_nft(&owner, &other_id, &7, &1u32, &recipient);
+        assert_eq!(result, Err(Ok(Error::UntrustedNFTContract)));
+        assert_eq!(other.owner_of(&7), owner);
+
+        // Token ids outside the NFT contract's range are rejected too
+        let result = client.try_wrap_nft(&owner, &trusted, &-1, &1u32, &recipient);
+        assert_eq!(result, Err(Ok(Error::InvalidAssetAmount)));
+    }
+
+    #[test]
+    fn test_wrap_nft_requires_ownership() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BridgeContract);
+        let client = BridgeContractClient::new(&env, &contract_id);
+        let nft_id = env.register_contract(None, MockNft);
+        let nft = MockNftClient::new(&env, &nft_id);
+
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let thief = Address::generate(&env);
+        client.initialize(&admin, &1u32, &0u32, &Address::generate(&env), &nft_id);
+        nft.mint(&owner, &7);
+
+        let recipient = Bytes::from_array(&env, &[1u8; 32]);
+        assert!(client.try_wrap_nft(&thief, &nft_id, &7, &1u32, &recipient).is_err());
+        assert_eq!(nft.owner_of(&7), owner);
+    }
+
+    #[test]
+    fn test_guarded_calls_fail_while_locked() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BridgeContract);
+        let client = BridgeContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let nft = Address::generate(&env);
+        client.initialize(&admin, &1u32, &0u32, &Address::generate(&env), &nft);
+
+        env.as_contract(&contract_id, || {
+            env.storage().instance().set(&DataKey::Locked, &true);
+        });
+
+        let recipient = Bytes::from_array(&env, &[1u8; 32]);
+        let message_id = BytesN::from_array(&env, &[9u8; 32]);
+        assert_eq!(
+            client.try_bridge_assets(&user, &Address::generate(&env), &AssetType::Token, &500, &1u32, &recipient),
+            Err(Ok(Error::ReentrantCall))
+        );
+        assert_eq!(client.try_cancel_bridge(&user, &message_id), Err(Ok(Error::ReentrantCall)));
+        assert_eq!(
+            client.try_validator_reject(&user, &message_id, &Vec::new(&env)),
+            Err(Ok(Error::ReentrantCall))
+        );
+        assert_eq!(client.try_wrap_nft(&user, &nft, &1, &1u32, &recipient), Err(Ok(Error::ReentrantCall)));
+        assert_eq!(client.try_unwrap_nft(&user, &1), Err(Ok(Error::ReentrantCall)));
+    }
+
+    #[test]
+    fn test_unlock_to_stellar_recipient_on_same_chain() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BridgeContract);
+        let client = BridgeContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+        let token_admin_client = token::StellarAssetClient::new(&env, &token_contract.address());
+        let token_client = token::Client::new(&env, &token_contract.address());
+
+        client.initialize(&admin, &1u32, &0u32, &Address::generate(&env), &Address::generate(&env));
+        token_admin_client.mint(&user, &1000);
+
+        let validator = Address::generate(&env);
+        client.add_validator(&admin, &validator);
+        let mut signatures: Vec<ValidatorSignature> = Vec::new(&env);
+        signatures.push_back(ValidatorSignature {
+            validator: validator.clone(),
+            signature: BytesN::from_array(&env, &[7u8; 64]),
+        });
+
+        let remote = Bytes::from_array(&env, &[1u8; 32]);
+        let message_id = client.bridge_assets(&user, &token_contract.address(), &AssetType::Token, &500, &1u32, &remote);
+        assert_eq!(client.get_escrowed(&token_contract.address()), 500);
+
+        // Strkey bytes of the Stellar recipient
+        let strkey = payee.to_string();
+        let mut buf = [0u8; 56];
+        strkey.copy_into_slice(&mut buf);
+
+        let mut message = BridgeMessage {
+            message_id: message_id.clone(),
+            source_chain: 1,
+            dest_chain: 0,
+            action: BridgeAction::Unlock,
+            asset_type: AssetType::Token,
+            asset_address: token_contract.address(),
+            asset_amount: 500,
+            sender: user.clone(),
+            recipient: Bytes::from_array(&env, &[1u8; 32]),
+            fee_amount: 0,
+            fee_token: None,
+            timestamp: 0,
+            nonce: 1,
+        };
+
+        // A non-Stellar recipient cannot be paid here
+        let result = client.try_complete_bridge(&validator, &message, &signatures);
+        assert_eq!(result, Err(Ok(Error::InvalidRecipient)));
+
+        message.recipient = Bytes::from_array(&env, &buf);
+        client.complete_bridge(&validator, &message, &signatures);
+
+        assert_eq!(token_client.balance(&payee), 500);
+        assert_eq!(token_client.balance(&contract_id), 0);
+        assert_eq!(client.get_escrowed(&token_contract.address()), 0);
+        assert!(client.get_locked_asset(&message_id).is_none());
+        assert_eq!(client.get_message_status(&message_id), Some(BridgeStatus::Completed));
+
+        // The lock is gone, so it cannot be released or cancelled again
+        let result = client.try_cancel_bridge(&user, &message_id);
+        assert_eq!(result, Err(Ok(Error::AssetNotLocked)));
+    }
+}
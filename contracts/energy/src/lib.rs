@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Vec};
 
 /// Energy and Stamina Management Contract
 ///
@@ -60,6 +60,10 @@ pub struct EnergyConfig {
     pub refill_token_cost: i128,
     /// Maximum energy that can be gifted per day
     pub max_gift_per_day: u32,
+    /// Token cost per unit of permanent max energy upgrade
+    pub upgrade_cost_per_unit: i128,
+    /// Hard cap on a player's max energy after upgrades
+    pub absolute_max_energy: u32,
     /// Contract paused state
     pub paused: bool,
 }
@@ -69,7 +73,8 @@ pub enum DataKey {
     Config,
     PlayerEnergy(Address),
     TotalPlayers,
-    DailyGiftReset, // Last daily reset timestamp
+    RefillRevenue,  // Refill and upgrade tokens collected and not yet withdrawn
+    RevenueCollector,
 }
 
 /// Custom error codes for the energy contract
@@ -88,6 +93,7 @@ pub enum Error {
     GiftLimitExceeded = 9,
     Unauthorized = 10,
     InvalidTimestamp = 11,
+    InsufficientTokenBalance = 12,
 }
 
 // Constants
@@ -133,6 +139,8 @@ impl EnergyContract {
             puzzle_energy_cost,
             refill_token_cost,
             max_gift_per_day: 20, // Max 20 energy gifts per day
+            upgrade_cost_per_unit: 10, // 10 tokens per extra max energy unit
+            absolute_max_energy: default_max_energy * 2,
             paused: false,
         };
 
@@ -178,6 +186,28 @@ impl EnergyContract {
         Ok(())
     }
 
+    /// Update max energy upgrade pricing and cap (admin only)
+    pub fn set_upgrade_config(
+        env: Env,
+        admin: Address,
+        upgrade_cost_per_unit: i128,
+        absolute_max_energy: u32,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        if upgrade_cost_per_unit < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut config: EnergyConfig = env.storage().instance().get(&DataKey::Config).unwrap();
+        config.upgrade_cost_per_unit = upgrade_cost_per_unit;
+        config.absolute_max_energy = absolute_max_energy;
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        Ok(())
+    }
+
     /// Pause/unpause the contract (admin only)
     pub fn set_paused(env: Env, admin: Address, paused: bool) -> Result<(), Error> {
         admin.require_auth();
@@ -190,6 +220,101 @@ impl EnergyContract {
         Ok(())
     }
 
+    /// Zero the daily gift counters for the listed players (admin only)
+    ///
+    /// Players without stored energy data are skipped.
+    pub fn reset_gift_counters(env: Env, admin: Address, players: Vec<Address>) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        let now = env.ledger().timestamp();
+
+        for player in players.iter() {
+            let key = DataKey::PlayerEnergy(player);
+            if let Some(mut player_energy) = env.storage().instance().get::<_, PlayerEnergy>(&key) {
+                player_energy.gifted_today = 0;
+                player_energy.last_gift_reset = now;
+                env.storage().instance().set(&key, &player_energy);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Grant energy to a player, e.g. as compensation after an outage (admin only)
+    ///
+    /// Regeneration is applied first and the result is capped at the player's `max_energy`.
+    pub fn grant_energy(env: Env, admin: Address, player: Address, amount: u32) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+        Self::assert_not_paused(&env)?;
+
+        if amount == 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let config: EnergyConfig = env.storage().instance().get(&DataKey::Config).unwrap();
+
+        let mut player_energy = Self::get_or_create_player_energy(&env, player.clone());
+        Self::update_energy_regeneration(&env, &player, &mut player_energy, &config);
+
+        player_energy.current_energy = player_energy.current_energy
+            .saturating_add(amount)
+            .min(player_energy.max_energy);
+        player_energy.last_update = env.ledger().timestamp();
+
+        env.storage().instance().set(&DataKey::PlayerEnergy(player.clone()), &player_energy);
+
+        // Emit grant event
+        env.events().publish(
+            (symbol_short!("E_GRANT"), player),
+            (amount, player_energy.current_energy),
+        );
+
+        Ok(())
+    }
+
+    /// Set the address that receives withdrawn refill revenue (admin only)
+    pub fn set_revenue_collector(env: Env, admin: Address, collector: Address) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        env.storage().instance().set(&DataKey::RevenueCollector, &collector);
+
+        Ok(())
+    }
+
+    /// Withdraw accumulated refill revenue to the revenue collector (admin only)
+    ///
+    /// The collector defaults to the admin when none has been configured.
+    pub fn withdraw_revenue(env: Env, admin: Address, amount: i128) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        let revenue: i128 = env.storage().instance().get(&DataKey::RefillRevenue).unwrap_or(0);
+        if amount <= 0 || amount > revenue {
+            return Err(Error::InvalidAmount);
+        }
+
+        let config: EnergyConfig = env.storage().instance().get(&DataKey::Config).unwrap();
+        let collector: Address = env.storage().instance()
+            .get(&DataKey::RevenueCollector)
+            .unwrap_or(config.admin);
+
+        let token_client = token::Client::new(&env, &config.reward_token);
+        token_client.transfer(&env.current_contract_address(), &collector, &amount);
+
+        env.storage().instance().set(&DataKey::RefillRevenue, &(revenue - amount));
+
+        // Emit withdrawal event
+        env.events().publish(
+            (symbol_short!("R_WITHDR"), collector),
+            (amount, revenue - amount),
+        );
+
+        Ok(())
+    }
+
     // ───────────── PLAYER FUNCTIONS ─────────────
 
     /// Get or create player energy data
@@ -197,27 +322,48 @@ impl EnergyContract {
         Self::get_or_create_player_energy(&env, player)
     }
 
-    /// Consume energy for puzzle attempt
+    /// Consume energy for puzzle attempt at the configured default cost
     ///
     /// # Returns
     /// * `Ok(())` - Energy consumed successfully
     /// * `Err(Error::InsufficientEnergy)` - Player doesn't have enough energy
     pub fn consume_energy_for_puzzle(env: Env, player: Address) -> Result<(), Error> {
+        let config: EnergyConfig = env.storage().instance().get(&DataKey::Config)
+            .ok_or(Error::NotInitialized)?;
+
+        Self::consume_energy(env, player, config.puzzle_energy_cost)
+    }
+
+    /// Consume a custom amount of energy, e.g. for harder puzzles
+    ///
+    /// # Arguments
+    /// * `player` - Player spending the energy
+    /// * `cost` - Amount of energy to deduct (must be greater than zero)
+    ///
+    /// # Returns
+    /// * `Ok(())` - Energy consumed successfully
+    /// * `Err(Error::InvalidAmount)` - Cost is zero
+    /// * `Err(Error::InsufficientEnergy)` - Player doesn't have enough energy
+    pub fn consume_energy(env: Env, player: Address, cost: u32) -> Result<(), Error> {
         player.require_auth();
         let player_addr = player.clone();
         Self::assert_not_paused(&env)?;
 
+        if cost == 0 {
+            return Err(Error::InvalidAmount);
+        }
+
         let config: EnergyConfig = env.storage().instance().get(&DataKey::Config)
             .ok_or(Error::NotInitialized)?;
 
         let mut player_energy = Self::get_or_create_player_energy(&env, player_addr.clone());
-        Self::update_energy_regeneration(&env, &mut player_energy, &config);
+        Self::update_energy_regeneration(&env, &player_addr, &mut player_energy, &config);
 
-        if player_energy.current_energy < config.puzzle_energy_cost {
+        if player_energy.current_energy < cost {
             return Err(Error::InsufficientEnergy);
         }
 
-        player_energy.current_energy -= config.puzzle_energy_cost;
+        player_energy.current_energy -= cost;
         player_energy.last_update = env.ledger().timestamp();
 
         env.storage().instance().set(&DataKey::PlayerEnergy(player.clone()), &player_energy);
@@ -225,7 +371,7 @@ impl EnergyContract {
         // Emit consumption event
         env.events().publish(
             (symbol_short!("E_USE"), player_addr.clone()),
-            (config.puzzle_energy_cost, player_energy.current_energy),
+            (cost, player_energy.current_energy),
         );
 
         Ok(())
@@ -255,6 +401,10 @@ impl EnergyContract {
         // Transfer tokens to contract
         token_client.transfer(&player_addr, &env.current_contract_address(), &config.refill_token_cost);
 
+        let revenue: i128 = env.storage().instance().get(&DataKey::RefillRevenue).unwrap_or(0);
+        let revenue = revenue + config.refill_token_cost;
+        env.storage().instance().set(&DataKey::RefillRevenue, &revenue);
+
         // Update player energy to maximum
         let mut player_energy = Self::get_or_create_player_energy(&env, player_addr.clone());
         let energy_refilled = player_energy.max_energy - player_energy.current_energy;
@@ -264,15 +414,71 @@ impl EnergyContract {
 
         env.storage().instance().set(&DataKey::PlayerEnergy(player.clone()), &player_energy);
 
-        // Emit refill event
+        // Emit refill event (energy refilled, tokens paid, revenue held by the contract)
         env.events().publish(
             (symbol_short!("E_REFILL"), player_addr.clone()),
-            energy_refilled,
+            (energy_refilled, config.refill_token_cost, revenue),
         );
 
         Ok(energy_refilled)
     }
 
+    /// Permanently raise a player's max energy by paying reward tokens
+    ///
+    /// # Arguments
+    /// * `player` - Player buying the upgrade
+    /// * `additional` - Extra max energy units to buy
+    ///
+    /// # Returns
+    /// * `Ok(u32)` - The player's new max energy
+    /// * `Err(Error::MaxEnergyExceeded)` - Upgrade would exceed `absolute_max_energy`
+    /// * `Err(Error::InsufficientTokenBalance)` - Player can't pay for the upgrade
+    pub fn upgrade_max_energy(env: Env, player: Address, additional: u32) -> Result<u32, Error> {
+        player.require_auth();
+        Self::assert_not_paused(&env)?;
+
+        if additional == 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let config: EnergyConfig = env.storage().instance().get(&DataKey::Config)
+            .ok_or(Error::NotInitialized)?;
+
+        let mut player_energy = Self::get_or_create_player_energy(&env, player.clone());
+        Self::update_energy_regeneration(&env, &player, &mut player_energy, &config);
+
+        let new_max = player_energy.max_energy
+            .checked_add(additional)
+            .ok_or(Error::MaxEnergyExceeded)?;
+        if new_max > config.absolute_max_energy {
+            return Err(Error::MaxEnergyExceeded);
+        }
+
+        let cost = additional as i128 * config.upgrade_cost_per_unit;
+        let token_client = token::Client::new(&env, &config.reward_token);
+        if token_client.balance(&player) < cost {
+            return Err(Error::InsufficientTokenBalance);
+        }
+
+        if cost > 0 {
+            token_client.transfer(&player, &env.current_contract_address(), &cost);
+
+            let revenue: i128 = env.storage().instance().get(&DataKey::RefillRevenue).unwrap_or(0);
+            env.storage().instance().set(&DataKey::RefillRevenue, &(revenue + cost));
+        }
+
+        player_energy.max_energy = new_max;
+        env.storage().instance().set(&DataKey::PlayerEnergy(player.clone()), &player_energy);
+
+        // Emit upgrade event
+        env.events().publish(
+            (symbol_short!("E_UPGRADE"), player),
+            (additional, new_max, cost),
+        );
+
+        Ok(new_max)
+    }
+
     /// Gift energy to another player
     ///
     /// # Arguments
@@ -303,11 +509,8 @@ impl EnergyContract {
         let config: EnergyConfig = env.storage().instance().get(&DataKey::Config)
             .ok_or(Error::NotInitialized)?;
 
-        // Reset daily gift counters if needed
-        Self::reset_daily_gifts_if_needed(&env);
-
         let mut from_energy = Self::get_or_create_player_energy(&env, from_player.clone());
-        Self::update_energy_regeneration(&env, &mut from_energy, &config);
+        Self::update_energy_regeneration(&env, &from_player, &mut from_energy, &config);
 
         // Check sender has enough energy
         if from_energy.current_energy < amount {
@@ -320,7 +523,7 @@ impl EnergyContract {
         }
 
         let mut to_energy = Self::get_or_create_player_energy(&env, to_player.clone());
-        Self::update_energy_regeneration(&env, &mut to_energy, &config);
+        Self::update_energy_regeneration(&env, &to_player, &mut to_energy, &config);
 
         // Check receiver won't exceed max energy
         if to_energy.current_energy + amount > to_energy.max_energy {
@@ -347,6 +550,94 @@ impl EnergyContract {
         Ok(())
     }
 
+    /// Gift energy from one player to several recipients at once
+    ///
+    /// The total is checked against the sender's energy and daily gift
+    /// limit up front. Recipients who would exceed their max energy are
+    /// skipped instead of failing the whole batch.
+    ///
+    /// # Arguments
+    /// * `from_player` - Player giving energy
+    /// * `recipients` - Players receiving energy
+    /// * `amounts` - Amount for each recipient, matched by index
+    ///
+    /// # Returns
+    /// * `Ok(Vec<u32>)` - Amount actually delivered to each recipient
+    /// * `Err(Error)` - Batch failed
+    pub fn gift_energy_batch(
+        env: Env,
+        from_player: Address,
+        recipients: Vec<Address>,
+        amounts: Vec<u32>,
+    ) -> Result<Vec<u32>, Error> {
+        from_player.require_auth();
+        Self::assert_not_paused(&env)?;
+
+        if recipients.is_empty() || recipients.len() != amounts.len() {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut total: u32 = 0;
+        for i in 0..recipients.len() {
+            let amount = amounts.get(i).unwrap();
+            if amount == 0 || recipients.get(i).unwrap() == from_player {
+                return Err(Error::InvalidAmount);
+            }
+            total = total.checked_add(amount).ok_or(Error::InvalidAmount)?;
+        }
+
+        let config: EnergyConfig = env.storage().instance().get(&DataKey::Config)
+            .ok_or(Error::NotInitialized)?;
+
+        let mut from_energy = Self::get_or_create_player_energy(&env, from_player.clone());
+        Self::update_energy_regeneration(&env, &from_player, &mut from_energy, &config);
+
+        // Check sender can cover the whole batch
+        if from_energy.current_energy < total {
+            return Err(Error::InsufficientEnergy);
+        }
+
+        if from_energy.gifted_today + total > config.max_gift_per_day {
+            return Err(Error::GiftLimitExceeded);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut delivered = Vec::new(&env);
+
+        for i in 0..recipients.len() {
+            let to_player = recipients.get(i).unwrap();
+            let amount = amounts.get(i).unwrap();
+
+            let mut to_energy = Self::get_or_create_player_energy(&env, to_player.clone());
+            Self::update_energy_regeneration(&env, &to_player, &mut to_energy, &config);
+
+            // Skip recipients who would exceed their max energy
+            if to_energy.current_energy + amount > to_energy.max_energy {
+                env.storage().instance().set(&DataKey::PlayerEnergy(to_player.clone()), &to_energy);
+                delivered.push_back(0);
+                continue;
+            }
+
+            to_energy.current_energy += amount;
+            to_energy.last_update = now;
+            env.storage().instance().set(&DataKey::PlayerEnergy(to_player.clone()), &to_energy);
+
+            from_energy.current_energy -= amount;
+            from_energy.gifted_today += amount;
+
+            env.events().publish(
+                (symbol_short!("E_GIFT"), from_player.clone(), to_player),
+                amount,
+            );
+            delivered.push_back(amount);
+        }
+
+        from_energy.last_update = now;
+        env.storage().instance().set(&DataKey::PlayerEnergy(from_player.clone()), &from_energy);
+
+        Ok(delivered)
+    }
+
     /// Apply a boost/powerup to a player
     ///
     /// # Arguments
@@ -370,7 +661,11 @@ impl EnergyContract {
             return Err(Error::InvalidBoostType);
         }
 
+        let config: EnergyConfig = env.storage().instance().get(&DataKey::Config)
+            .ok_or(Error::NotInitialized)?;
+
         let mut player_energy = Self::get_or_create_player_energy(&env, player.clone());
+        Self::update_energy_regeneration(&env, &player, &mut player_energy, &config);
 
         // Check if boost already active
         if player_energy.active_boost != BoostType::None && player_energy.boost_expires_at > env.ledger().timestamp() {
@@ -393,14 +688,143 @@ impl EnergyContract {
         Ok(())
     }
 
+    /// Gift the remaining duration of an active boost to another player
+    ///
+    /// The giver's boost is cleared and the recipient gets the same boost type
+    /// for whatever time was left on it.
+    ///
+    /// # Arguments
+    /// * `from_player` - Player giving away their boost
+    /// * `to_player` - Player receiving the boost
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Seconds of boost transferred
+    /// * `Err(Error)` - Gift failed
+    pub fn gift_boost(
+        env: Env,
+        from_player: Address,
+        to_player: Address,
+    ) -> Result<u64, Error> {
+        from_player.require_auth();
+        Self::assert_not_paused(&env)?;
+
+        if from_player == to_player {
+            return Err(Error::InvalidAmount);
+        }
+
+        let config: EnergyConfig = env.storage().instance().get(&DataKey::Config)
+            .ok_or(Error::NotInitialized)?;
+
+        let now = env.ledger().timestamp();
+
+        let mut from_energy = Self::get_or_create_player_energy(&env, from_player.clone());
+        Self::update_energy_regeneration(&env, &from_player, &mut from_energy, &config);
+
+        // Giver must still have an active boost
+        if from_energy.active_boost == BoostType::None || from_energy.boost_expires_at <= now {
+            return Err(Error::InvalidBoostType);
+        }
+
+        let mut to_energy = Self::get_or_create_player_energy(&env, to_player.clone());
+        Self::update_energy_regeneration(&env, &to_player, &mut to_energy, &config);
+
+        // Recipient can't already have an active boost
+        if to_energy.active_boost != BoostType::None && to_energy.boost_expires_at > now {
+            return Err(Error::BoostAlreadyActive);
+        }
+
+        let boost_type = from_energy.active_boost;
+        let remaining = from_energy.boost_expires_at - now;
+
+        from_energy.active_boost = BoostType::None;
+        from_energy.boost_expires_at = 0;
+        from_energy.last_update = now;
+
+        to_energy.active_boost = boost_type;
+        to_energy.boost_expires_at = now + remaining;
+        to_energy.last_update = now;
+
+        env.storage().instance().set(&DataKey::PlayerEnergy(from_player.clone()), &from_energy);
+        env.storage().instance().set(&DataKey::PlayerEnergy(to_player.clone()), &to_energy);
+
+        // Emit boost gift event
+        env.events().publish(
+            (symbol_short!("B_GIFT"), from_player, to_player),
+            (boost_type, remaining),
+        );
+
+        Ok(remaining)
+    }
+
     /// Get current energy for a player (with regeneration applied)
+    ///
+    /// The regenerated state is saved so boost expiry is only reported once.
     pub fn get_current_energy(env: Env, player: Address) -> u32 {
         let config: EnergyConfig = env.storage().instance().get(&DataKey::Config).unwrap();
-        let mut player_energy = Self::get_or_create_player_energy(&env, player);
-        Self::update_energy_regeneration(&env, &mut player_energy, &config);
+        let mut player_energy = Self::get_or_create_player_energy(&env, player.clone());
+        Self::update_energy_regeneration(&env, &player, &mut player_energy, &config);
+        env.storage().instance().set(&DataKey::PlayerEnergy(player), &player_energy);
         player_energy.current_energy
     }
 
+    /// Get seconds until a player's energy is full (0 if already full)
+    ///
+    /// Uses the boosted rate while an active boost lasts and the base rate afterwards.
+    /// Returns `u64::MAX` if energy is not full and the base regeneration rate is zero.
+    pub fn time_until_full(env: Env, player: Address) -> u64 {
+        let config: EnergyConfig = env.storage().instance().get(&DataKey::Config).unwrap();
+
+        // Players without stored energy start at full capacity
+        let mut player_energy: PlayerEnergy = match env.storage().instance().get(&DataKey::PlayerEnergy(player)) {
+            Some(energy) => energy,
+            None => return 0,
+        };
+        Self::regenerate(&env, &mut player_energy, &config);
+
+        let mut deficit = player_energy.max_energy.saturating_sub(player_energy.current_energy) as u64;
+        if deficit == 0 {
+            return 0;
+        }
+
+        let base_rate = config.base_regen_rate as u64;
+        if base_rate == 0 {
+            return u64::MAX;
+        }
+
+        let mut seconds = 0u64;
+
+        // Regenerate at the boosted rate until the boost expires
+        let boost_window = Self::boosted_seconds(&player_energy, u64::MAX);
+        if boost_window > 0 {
+            let boosted_rate = base_rate * Self::boost_multiplier(player_energy.active_boost) as u64;
+            let boosted_gain = boost_window.saturating_mul(boosted_rate);
+            if boosted_gain >= deficit {
+                return deficit.div_ceil(boosted_rate);
+            }
+            deficit -= boosted_gain;
+            seconds = boost_window;
+        }
+
+        seconds + deficit.div_ceil(base_rate)
+    }
+
+    /// Get a player's full energy state with regeneration, boost expiry and
+    /// gift counter resets applied, without saving anything
+    pub fn get_player_state(env: Env, player: Address) -> PlayerEnergy {
+        let config: EnergyConfig = env.storage().instance().get(&DataKey::Config).unwrap();
+
+        let mut player_energy = match env.storage().instance().get(&DataKey::PlayerEnergy(player)) {
+            Some(mut energy) => {
+                Self::reset_daily_gifts_if_needed(&env, &mut energy);
+                energy
+            }
+            None => Self::new_player_energy(&env, &config),
+        };
+        Self::regenerate(&env, &mut player_energy, &config);
+
+        player_energy
+    }
+
     /// Get player energy info without updating regeneration
     pub fn get_player_energy_info(env: Env, player: Address) -> Option<PlayerEnergy> {
         env.storage().instance().get(&DataKey::PlayerEnergy(player))
@@ -416,27 +840,22 @@ impl EnergyContract {
         env.storage().instance().get(&DataKey::TotalPlayers).unwrap_or(0)
     }
 
+    /// Get refill and upgrade revenue collected and not yet withdrawn
+    pub fn get_refill_revenue(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::RefillRevenue).unwrap_or(0)
+    }
+
     // ───────────── INTERNAL HELPERS ─────────────
 
     fn get_or_create_player_energy(env: &Env, player: Address) -> PlayerEnergy {
-        if let Some(energy) = env.storage().instance().get(&DataKey::PlayerEnergy(player.clone())) {
-            // Reset daily gifts if needed
-            Self::reset_daily_gifts_if_needed(env);
+        if let Some(mut energy) = env.storage().instance().get(&DataKey::PlayerEnergy(player.clone())) {
+            // Reset this player's daily gift counter if a full day has passed
+            Self::reset_daily_gifts_if_needed(env, &mut energy);
             energy
         } else {
             // Create new player energy
             let config: EnergyConfig = env.storage().instance().get(&DataKey::Config).unwrap();
-            let current_time = env.ledger().timestamp();
-
-            let energy = PlayerEnergy {
-                current_energy: config.default_max_energy,
-                max_energy: config.default_max_energy,
-                last_update: current_time,
-                active_boost: BoostType::None,
-                boost_expires_at: 0,
-                gifted_today: 0,
-                last_gift_reset: current_time,
-            };
+            let energy = Self::new_player_energy(env, &config);
 
             env.storage().instance().set(&DataKey::PlayerEnergy(player), &energy);
 
@@ -448,52 +867,99 @@ impl EnergyContract {
         }
     }
 
-    fn update_energy_regeneration(env: &Env, player_energy: &mut PlayerEnergy, config: &EnergyConfig) {
+    /// Energy state for a player who has never been seen before
+    fn new_player_energy(env: &Env, config: &EnergyConfig) -> PlayerEnergy {
+        let current_time = env.ledger().timestamp();
+
+        PlayerEnergy {
+            current_energy: config.default_max_energy,
+            max_energy: config.default_max_energy,
+            last_update: current_time,
+            active_boost: BoostType::None,
+            boost_expires_at: 0,
+            gifted_today: 0,
+            last_gift_reset: current_time,
+        }
+    }
+
+    /// Apply regeneration and report a boost that expired in the process
+    ///
+    /// Callers must save `player_energy` afterwards so the expiry is only emitted once.
+    fn update_energy_regeneration(env: &Env, player: &Address, player_energy: &mut PlayerEnergy, config: &EnergyConfig) {
+        if let Some(expired_boost) = Self::regenerate(env, player_energy, config) {
+            env.events().publish(
+                (symbol_short!("B_EXPIRE"), player.clone()),
+                expired_boost,
+            );
+        }
+    }
+
+    /// Apply regeneration in place, returning the boost type if it expired and was cleared
+    fn regenerate(env: &Env, player_energy: &mut PlayerEnergy, config: &EnergyConfig) -> Option<BoostType> {
         let current_time = env.ledger().timestamp();
 
         // Use saturating_sub to prevent underflow on timestamp issues
-        let time_elapsed = current_time.saturating_sub(player_energy.last_update) as u32;
+        let time_elapsed = current_time.saturating_sub(player_energy.last_update);
 
         if time_elapsed == 0 {
-            return; // No time has passed
+            return None; // No time has passed
         }
 
-        // Calculate regeneration multiplier from active boost
-        let multiplier = if player_energy.active_boost != BoostType::None && player_energy.boost_expires_at > current_time {
-            match player_energy.active_boost {
-                BoostType::DoubleRegen => 2,
-                BoostType::TripleRegen => 3,
-                BoostType::QuintupleRegen => 5,
-                BoostType::None => 1,
+        // Split the elapsed time into the part covered by the boost and the part after it expired
+        let boosted_secs = Self::boosted_seconds(player_energy, time_elapsed);
+        let base_secs = time_elapsed - boosted_secs;
+        let multiplier = Self::boost_multiplier(player_energy.active_boost) as u64;
+        let base_rate = config.base_regen_rate as u64;
+
+        let regenerated = boosted_secs
+            .saturating_mul(base_rate * multiplier)
+            .saturating_add(base_secs.saturating_mul(base_rate));
+
+        // Boost expired, reset it
+        let mut expired_boost = None;
+        if player_energy.boost_expires_at <= current_time {
+            if player_energy.active_boost != BoostType::None {
+                expired_boost = Some(player_energy.active_boost);
             }
-        } else {
-            // Boost expired, reset it
-            if player_energy.boost_expires_at <= current_time {
-                player_energy.active_boost = BoostType::None;
-                player_energy.boost_expires_at = 0;
-            }
-            1
-        };
-
-        // Optimized calculation: multiply time_elapsed by (base_regen_rate * multiplier)
-        // This avoids intermediate variable creation
-        let regenerated = time_elapsed * (config.base_regen_rate * multiplier);
+            player_energy.active_boost = BoostType::None;
+            player_energy.boost_expires_at = 0;
+        }
 
         // Apply regeneration with saturation (capped at max_energy)
-        player_energy.current_energy = player_energy.current_energy.saturating_add(regenerated).min(player_energy.max_energy);
+        player_energy.current_energy = (player_energy.current_energy as u64)
+            .saturating_add(regenerated)
+            .min(player_energy.max_energy as u64) as u32;
         player_energy.last_update = current_time;
+
+        expired_boost
     }
 
-    fn reset_daily_gifts_if_needed(env: &Env) {
-        let current_time = env.ledger().timestamp();
-        let last_reset: u64 = env.storage().instance().get(&DataKey::DailyGiftReset).unwrap_or(0);
+    /// Seconds of the next `window` seconds (starting at `last_update`) during which the boost is active
+    fn boosted_seconds(player_energy: &PlayerEnergy, window: u64) -> u64 {
+        if player_energy.active_boost == BoostType::None {
+            return 0;
+        }
+        player_energy.boost_expires_at
+            .saturating_sub(player_energy.last_update)
+            .min(window)
+    }
+
+    fn boost_multiplier(boost_type: BoostType) -> u32 {
+        match boost_type {
+            BoostType::None => 1,
+            BoostType::DoubleRegen => 2,
+            BoostType::TripleRegen => 3,
+            BoostType::QuintupleRegen => 5,
+        }
+    }
 
-        if current_time - last_reset >= SECONDS_PER_DAY {
-            // Reset all players' daily gift counters
-            // Note: In a real implementation, you might want to iterate through all players
-            // For now, we'll reset on access (lazy reset)
+    fn reset_daily_gifts_if_needed(env: &Env, player_energy: &mut PlayerEnergy) {
+        let current_time = env.ledger().timestamp();
 
-            env.storage().instance().set(&DataKey::DailyGiftReset, &current_time);
+        // Lazy per-player reset: each player's window starts at their own last reset
+        if current_time.saturating_sub(player_energy.last_gift_reset) >= SECONDS_PER_DAY {
+            player_energy.gifted_today = 0;
+            player_energy.last_gift_reset = current_time;
         }
     }
 
@@ -520,13 +986,25 @@ impl EnergyContract {
 mod test {
     use super::*;
     use soroban_sdk::{
-        testutils::{Address as _, Ledger},
-        Env,
+        testutils::{Address as _, Events, Ledger},
+        token::{Client as TokenClient, StellarAssetClient},
+        vec, Env, IntoVal, Symbol, TryFromVal, Val,
     };
 
-    #[test]
-    fn test_initialization() {
-        let env = Env::default();
+    fn count_events(env: &Env, name: Symbol) -> u32 {
+        let mut count = 0;
+        for (_, topics, _) in env.events().all().iter() {
+            let first: Val = topics.get(0).unwrap();
+            if Symbol::try_from_val(env, &first).map(|s| s == name).unwrap_or(false) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn test_initialization() {
+        let env = Env::default();
         env.mock_all_auths();
 
         let contract_id = env.register_contract(None, EnergyContract);
@@ -666,4 +1144,518 @@ mod test {
         let result = client.try_consume_energy_for_puzzle(&player);
         assert_eq!(result, Err(Ok(Error::InsufficientEnergy)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_refill_revenue_accumulates_and_withdraws() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        let reward_token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        StellarAssetClient::new(&env, &reward_token).mint(&player, &1000);
+        let token_client = TokenClient::new(&env, &reward_token);
+
+        client.initialize(&admin, &reward_token, &1, &100, &10, &50);
+
+        client.consume_energy_for_puzzle(&player);
+        client.instant_refill(&player);
+        client.consume_energy_for_puzzle(&player);
+        client.instant_refill(&player);
+
+        assert_eq!(client.get_refill_revenue(), 100);
+        assert_eq!(token_client.balance(&contract_id), 100);
+
+        // Cannot withdraw more than collected
+        let result = client.try_withdraw_revenue(&admin, &101);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+        client.withdraw_revenue(&admin, &60);
+        assert_eq!(client.get_refill_revenue(), 40);
+        assert_eq!(token_client.balance(&admin), 60);
+
+        // Withdrawals go to the configured collector once set
+        let collector = Address::generate(&env);
+        client.set_revenue_collector(&admin, &collector);
+        client.withdraw_revenue(&admin, &40);
+        assert_eq!(client.get_refill_revenue(), 0);
+        assert_eq!(token_client.balance(&collector), 40);
+
+        // Non-admin cannot withdraw
+        let result = client.try_withdraw_revenue(&player, &1);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_daily_gift_limit_resets_per_player() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let reward_token = Address::generate(&env);
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+
+        client.initialize(&admin, &reward_token, &0, &100, &10, &50);
+
+        // Make room in player2 so gifts are not capped by max energy
+        for _ in 0..5 {
+            client.consume_energy_for_puzzle(&player2);
+        }
+
+        // Gift up to the daily limit
+        client.gift_energy(&player1, &player2, &20);
+        let result = client.try_gift_energy(&player1, &player2, &1);
+        assert_eq!(result, Err(Ok(Error::GiftLimitExceeded)));
+
+        // Still limited just before a full day has passed
+        env.ledger().with_mut(|li| li.timestamp += 86400 - 1);
+        let result = client.try_gift_energy(&player1, &player2, &1);
+        assert_eq!(result, Err(Ok(Error::GiftLimitExceeded)));
+
+        // After 24h + 1s the player's own counter resets
+        env.ledger().with_mut(|li| li.timestamp += 2);
+        client.gift_energy(&player1, &player2, &20);
+
+        let info = client.get_player_energy_info(&player1).unwrap();
+        assert_eq!(info.gifted_today, 20);
+        assert_eq!(info.current_energy, 60);
+    }
+
+    #[test]
+    fn test_consume_custom_energy_cost() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let reward_token = Address::generate(&env);
+        let player = Address::generate(&env);
+
+        client.initialize(&admin, &reward_token, &1, &100, &10, &50);
+
+        client.consume_energy(&player, &35);
+        assert_eq!(client.get_current_energy(&player), 65);
+
+        client.consume_energy(&player, &5);
+        assert_eq!(client.get_current_energy(&player), 60);
+
+        // Zero cost is rejected
+        let result = client.try_consume_energy(&player, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+        // A hard puzzle costing more than the current energy fails
+        let result = client.try_consume_energy(&player, &61);
+        assert_eq!(result, Err(Ok(Error::InsufficientEnergy)));
+        assert_eq!(client.get_current_energy(&player), 60);
+    }
+
+    #[test]
+    fn test_grant_energy() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let reward_token = Address::generate(&env);
+        let player = Address::generate(&env);
+
+        client.initialize(&admin, &reward_token, &0, &100, &10, &50);
+
+        for _ in 0..3 {
+            client.consume_energy_for_puzzle(&player);
+        }
+        assert_eq!(client.get_current_energy(&player), 70);
+
+        // Non-admin is rejected
+        let result = client.try_grant_energy(&player, &player, &10);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.grant_energy(&admin, &player, &20);
+        assert_eq!(client.get_current_energy(&player), 90);
+
+        // Granting beyond the cap saturates at max_energy
+        client.grant_energy(&admin, &player, &50);
+        assert_eq!(client.get_current_energy(&player), 100);
+
+        // Respects pause state
+        client.set_paused(&admin, &true);
+        let result = client.try_grant_energy(&admin, &player, &10);
+        assert_eq!(result, Err(Ok(Error::ContractPaused)));
+    }
+
+    #[test]
+    fn test_time_until_full() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let reward_token = Address::generate(&env);
+        let player = Address::generate(&env);
+
+        client.initialize(&admin, &reward_token, &1, &100, &10, &50);
+
+        // Unknown and full players need no time
+        assert_eq!(client.time_until_full(&player), 0);
+
+        for _ in 0..5 {
+            client.consume_energy_for_puzzle(&player);
+        }
+
+        // 50 missing energy at 1 energy/second
+        assert_eq!(client.time_until_full(&player), 50);
+
+        env.ledger().with_mut(|li| li.timestamp += 20);
+        assert_eq!(client.time_until_full(&player), 30);
+
+        env.ledger().with_mut(|li| li.timestamp += 30);
+        assert_eq!(client.time_until_full(&player), 0);
+        assert_eq!(client.get_current_energy(&player), 100);
+    }
+
+    #[test]
+    fn test_time_until_full_with_expiring_boost() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let reward_token = Address::generate(&env);
+        let player = Address::generate(&env);
+
+        client.initialize(&admin, &reward_token, &1, &100, &10, &50);
+
+        for _ in 0..5 {
+            client.consume_energy_for_puzzle(&player);
+        }
+
+        // 2x boost for 10 seconds: 20 energy while boosted, then 30 more at the base rate
+        client.apply_boost(&player, &BoostType::DoubleRegen, &10);
+        assert_eq!(client.time_until_full(&player), 10 + 30);
+
+        // A boost long enough to cover the whole deficit: ceil(50 / 2)
+        let other = Address::generate(&env);
+        for _ in 0..5 {
+            client.consume_energy_for_puzzle(&other);
+        }
+        client.apply_boost(&other, &BoostType::DoubleRegen, &100);
+        assert_eq!(client.time_until_full(&other), 25);
+
+        // Regeneration agrees with the prediction across the boost expiry
+        env.ledger().with_mut(|li| li.timestamp += 39);
+        assert_eq!(client.get_current_energy(&player), 99);
+        assert_eq!(client.time_until_full(&player), 1);
+        env.ledger().with_mut(|li| li.timestamp += 1);
+        assert_eq!(client.get_current_energy(&player), 100);
+    }
+
+    #[test]
+    fn test_upgrade_max_energy() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        let reward_token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        StellarAssetClient::new(&env, &reward_token).mint(&player, &300);
+        let token_client = TokenClient::new(&env, &reward_token);
+
+        client.initialize(&admin, &reward_token, &1, &100, &10, &50);
+
+        // 20 units at 10 tokens each
+        let new_max = client.upgrade_max_energy(&player, &20);
+        assert_eq!(new_max, 120);
+        assert_eq!(client.get_player_energy(&player).max_energy, 120);
+        assert_eq!(token_client.balance(&player), 100);
+        assert_eq!(client.get_refill_revenue(), 200);
+
+        // Regeneration now fills up to the upgraded cap
+        client.consume_energy(&player, &50);
+        env.ledger().with_mut(|li| li.timestamp += 100);
+        assert_eq!(client.get_current_energy(&player), 120);
+
+        // Not enough tokens for 20 more units
+        let result = client.try_upgrade_max_energy(&player, &20);
+        assert_eq!(result, Err(Ok(Error::InsufficientTokenBalance)));
+        assert_eq!(token_client.balance(&player), 100);
+    }
+
+    #[test]
+    fn test_upgrade_max_energy_cap() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        let reward_token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        StellarAssetClient::new(&env, &reward_token).mint(&player, &10_000);
+
+        client.initialize(&admin, &reward_token, &1, &100, &10, &50);
+        client.set_upgrade_config(&admin, &1, &150);
+
+        client.upgrade_max_energy(&player, &50);
+        assert_eq!(client.get_player_energy(&player).max_energy, 150);
+
+        let result = client.try_upgrade_max_energy(&player, &1);
+        assert_eq!(result, Err(Ok(Error::MaxEnergyExceeded)));
+    }
+
+    #[test]
+    fn test_boost_expiry_event_emitted_once() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let reward_token = Address::generate(&env);
+        let player = Address::generate(&env);
+
+        client.initialize(&admin, &reward_token, &1, &100, &10, &50);
+
+        client.apply_boost(&player, &BoostType::TripleRegen, &10);
+        env.ledger().with_mut(|li| li.timestamp += 20);
+
+        client.get_current_energy(&player);
+        assert_eq!(count_events(&env, symbol_short!("B_EXPIRE")), 1);
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("B_EXPIRE"), player.clone()).into_val(&env));
+        assert_eq!(BoostType::try_from_val(&env, &data).unwrap(), BoostType::TripleRegen);
+        assert_eq!(client.get_player_energy(&player).active_boost, BoostType::None);
+
+        // Later regenerations do not report the same expiry again
+        env.ledger().with_mut(|li| li.timestamp += 5);
+        client.get_current_energy(&player);
+        client.consume_energy_for_puzzle(&player);
+        assert_eq!(count_events(&env, symbol_short!("B_EXPIRE")), 1);
+    }
+
+    #[test]
+    fn test_gift_energy_batch_skips_full_recipient() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let reward_token = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+        let player3 = Address::generate(&env);
+
+        client.initialize(&admin, &reward_token, &1, &100, &10, &50);
+
+        // player2 stays at max energy and must be skipped
+        client.consume_energy_for_puzzle(&player1);
+        client.consume_energy_for_puzzle(&player3);
+
+        let delivered = client.gift_energy_batch(
+            &sender,
+            &vec![&env, player1.clone(), player2.clone(), player3.clone()],
+            &vec![&env, 5u32, 5u32, 10u32],
+        );
+
+        assert_eq!(delivered, vec![&env, 5u32, 0u32, 10u32]);
+        assert_eq!(client.get_current_energy(&sender), 85);
+        assert_eq!(client.get_current_energy(&player1), 95);
+        assert_eq!(client.get_current_energy(&player2), 100);
+        assert_eq!(client.get_current_energy(&player3), 100);
+        assert_eq!(client.get_player_energy_info(&sender).unwrap().gifted_today, 15);
+
+        // Mismatched lengths are rejected
+        let result = client.try_gift_energy_batch(
+            &sender,
+            &vec![&env, player1.clone()],
+            &vec![&env, 1u32, 1u32],
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+    }
+
+    #[test]
+    fn test_gift_energy_batch_daily_limit() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let reward_token = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+
+        client.initialize(&admin, &reward_token, &1, &100, &10, &50);
+
+        client.consume_energy_for_puzzle(&player1);
+        client.consume_energy_for_puzzle(&player2);
+
+        // One over the daily limit fails as a whole
+        let result = client.try_gift_energy_batch(
+            &sender,
+            &vec![&env, player1.clone(), player2.clone()],
+            &vec![&env, 10u32, 11u32],
+        );
+        assert_eq!(result, Err(Ok(Error::GiftLimitExceeded)));
+        assert_eq!(client.get_current_energy(&player1), 90);
+
+        // Exactly the daily limit succeeds
+        let delivered = client.gift_energy_batch(
+            &sender,
+            &vec![&env, player1.clone(), player2.clone()],
+            &vec![&env, 10u32, 10u32],
+        );
+        assert_eq!(delivered, vec![&env, 10u32, 10u32]);
+        assert_eq!(client.get_current_energy(&sender), 80);
+
+        let result = client.try_gift_energy(&sender, &player1, &1);
+        assert_eq!(result, Err(Ok(Error::GiftLimitExceeded)));
+    }
+
+    #[test]
+    fn test_admin_reset_gift_counters() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let reward_token = Address::generate(&env);
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+
+        client.initialize(&admin, &reward_token, &1, &100, &10, &50);
+
+        client.consume_energy_for_puzzle(&player2);
+        client.consume_energy_for_puzzle(&player2);
+        client.consume_energy_for_puzzle(&player2);
+        client.consume_energy_for_puzzle(&player2);
+
+        // player1 reaches the daily gift limit
+        client.gift_energy(&player1, &player2, &20);
+        let result = client.try_gift_energy(&player1, &player2, &5);
+        assert_eq!(result, Err(Ok(Error::GiftLimitExceeded)));
+
+        // Non-admin cannot reset
+        let result = client.try_reset_gift_counters(&player1, &vec![&env, player1.clone()]);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.reset_gift_counters(&admin, &vec![&env, player1.clone()]);
+        assert_eq!(client.get_player_energy_info(&player1).unwrap().gifted_today, 0);
+
+        client.gift_energy(&player1, &player2, &5);
+        assert_eq!(client.get_player_energy_info(&player1).unwrap().gifted_today, 5);
+    }
+
+    #[test]
+    fn test_gift_boost_moves_remaining_duration() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let reward_token = Address::generate(&env);
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+        let player3 = Address::generate(&env);
+
+        client.initialize(&admin, &reward_token, &1, &100, &10, &50);
+
+        // Nothing to gift without an active boost
+        let result = client.try_gift_boost(&player1, &player2);
+        assert_eq!(result, Err(Ok(Error::InvalidBoostType)));
+
+        client.apply_boost(&player1, &BoostType::DoubleRegen, &100);
+        env.ledger().with_mut(|li| li.timestamp += 40);
+
+        let remaining = client.gift_boost(&player1, &player2);
+        assert_eq!(remaining, 60);
+
+        let giver = client.get_player_energy(&player1);
+        assert_eq!(giver.active_boost, BoostType::None);
+        assert_eq!(giver.boost_expires_at, 0);
+
+        let now = env.ledger().timestamp();
+        let receiver = client.get_player_energy(&player2);
+        assert_eq!(receiver.active_boost, BoostType::DoubleRegen);
+        assert_eq!(receiver.boost_expires_at, now + 60);
+        assert_eq!(count_events(&env, symbol_short!("B_GIFT")), 1);
+
+        // Recipient with an active boost is rejected
+        client.apply_boost(&player3, &BoostType::TripleRegen, &100);
+        let result = client.try_gift_boost(&player3, &player2);
+        assert_eq!(result, Err(Ok(Error::BoostAlreadyActive)));
+    }
+
+    #[test]
+    fn test_get_player_state_applies_regeneration_without_saving() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, EnergyContract);
+        let client = EnergyContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let reward_token = Address::generate(&env);
+        let player = Address::generate(&env);
+        let newcomer = Address::generate(&env);
+
+        client.initialize(&admin, &reward_token, &1, &100, &10, &50);
+
+        for _ in 0..5 {
+            client.consume_energy_for_puzzle(&player);
+        }
+        client.apply_boost(&player, &BoostType::DoubleRegen, &10);
+        let stored = client.get_player_energy_info(&player).unwrap();
+        assert_eq!(stored.current_energy, 50);
+
+        // 10 boosted seconds at 2x plus 5 base seconds
+        env.ledger().with_mut(|li| li.timestamp += 15);
+
+        let state = client.get_player_state(&player);
+        assert_eq!(state.current_energy, 75);
+        assert_eq!(state.active_boost, BoostType::None);
+        assert_eq!(state.last_update, env.ledger().timestamp());
+
+        // Stored state is untouched until a mutating call
+        let unchanged = client.get_player_energy_info(&player).unwrap();
+        assert_eq!(unchanged.current_energy, stored.current_energy);
+        assert_eq!(unchanged.last_update, stored.last_update);
+        assert_eq!(unchanged.active_boost, BoostType::DoubleRegen);
+        assert_eq!(count_events(&env, symbol_short!("B_EXPIRE")), 0);
+
+        client.consume_energy(&player, &5);
+        assert_eq!(client.get_player_energy_info(&player).unwrap().current_energy, 70);
+
+        // Unknown players get default state without being created
+        assert_eq!(client.get_player_state(&newcomer).current_energy, 100);
+        assert!(client.get_player_energy_info(&newcomer).is_none());
+    }
+}
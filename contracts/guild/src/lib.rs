@@ -1,8 +1,8 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype,
-    Address, Env, String, Vec, Map, Symbol, token,
+    contract, contractimpl, contracttype, symbol_short,
+    Address, Env, String, Vec, Symbol, token,
 };
 
 //
@@ -36,6 +36,8 @@ pub enum DataKey {
     Proposal(u32),             // Proposal
     ProposalCounter,           // u32
     Competition(u32),          // Competition
+    Contribution(Address),     // i128 - net treasury deposits
+    Voted(u32, Address),       // bool - (proposal_id, member) has voted
 }
 
 //
@@ -49,6 +51,9 @@ pub enum DataKey {
 pub struct GuildConfig {
     pub name: String,
     pub disbanded: bool,
+    pub quorum: u32,           // minimum weighted yes + no votes for a proposal to pass
+    pub officer_weight: u32,   // votes counted per officer ballot (members count 1)
+    pub leader_weight: u32,    // votes counted per leader ballot
 }
 
 #[contracttype]
@@ -59,6 +64,7 @@ pub struct Proposal {
     pub no: u32,
     pub deadline: u64,
     pub executed: bool,
+    pub passed: bool,
 }
 
 #[contracttype]
@@ -92,7 +98,13 @@ impl GuildContract {
 
         env.storage().persistent().set(
             &DataKey::Config,
-            &GuildConfig { name, disbanded: false },
+            &GuildConfig {
+                name,
+                disbanded: false,
+                quorum: 0,
+                officer_weight: 1,
+                leader_weight: 1,
+            },
         );
 
         env.storage().instance().set(&DataKey::TreasuryToken, &token_address);
@@ -120,6 +132,54 @@ impl GuildContract {
         Self::set_role_internal(&env, target, role);
     }
 
+    /// Remove a member (leader only). Leaders have to be demoted first.
+    pub fn kick(env: Env, leader: Address, target: Address) {
+        leader.require_auth();
+        Self::assert_leader(&env, &leader);
+        Self::assert_active(&env);
+
+        match Self::get_role(env.clone(), target.clone()) {
+            None => panic!("Not a member"),
+            Some(Role::Leader) => panic!("Cannot kick a leader"),
+            Some(_) => {}
+        }
+
+        Self::remove_member_internal(&env, target);
+    }
+
+    /// Leave the guild. Leaders have to hand over leadership first.
+    pub fn leave(env: Env, member: Address) {
+        member.require_auth();
+        Self::assert_active(&env);
+
+        match Self::get_role(env.clone(), member.clone()) {
+            None => panic!("Not a member"),
+            Some(Role::Leader) => panic!("Leader cannot leave"),
+            Some(_) => {}
+        }
+
+        Self::remove_member_internal(&env, member);
+    }
+
+    /// Hand leadership to an existing member; the old leader becomes an officer
+    pub fn transfer_leadership(env: Env, leader: Address, new_leader: Address) {
+        leader.require_auth();
+        Self::assert_leader(&env, &leader);
+        Self::assert_active(&env);
+
+        if leader == new_leader {
+            panic!("Already leader");
+        }
+        if Self::get_role(env.clone(), new_leader.clone()).is_none() {
+            panic!("Not a member");
+        }
+
+        Self::set_role_internal(&env, leader.clone(), Role::Officer);
+        Self::set_role_internal(&env, new_leader.clone(), Role::Leader);
+
+        env.events().publish((symbol_short!("LEAD_XFER"), leader), new_leader);
+    }
+
     // ───────────── TREASURY ─────────────
 
     pub fn deposit(env: Env, member: Address, amount: i128) {
@@ -131,6 +191,9 @@ impl GuildContract {
         let client = token::Client::new(&env, &token_addr);
 
         client.transfer(&member, &env.current_contract_address(), &amount);
+
+        let contribution = Self::get_contribution(env.clone(), member.clone());
+        env.storage().persistent().set(&DataKey::Contribution(member), &(contribution + amount));
     }
 
     pub fn withdraw(env: Env, officer: Address, amount: i128) {
@@ -147,9 +210,20 @@ impl GuildContract {
             panic!("Insufficient funds");
         }
 
+        // Contributions only ever grow; withdrawals don't reduce a share
         client.transfer(&env.current_contract_address(), &officer, &amount);
     }
 
+    pub fn get_contribution(env: Env, member: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::Contribution(member)).unwrap_or(0)
+    }
+
+    pub fn get_treasury_balance(env: Env) -> i128 {
+        let token_addr: Address =
+            env.storage().instance().get(&DataKey::TreasuryToken).unwrap();
+        token::Client::new(&env, &token_addr).balance(&env.current_contract_address())
+    }
+
     // ───────────── SHARED RESOURCES ─────────────
 
     pub fn add_resource(env: Env, officer: Address, resource: Symbol, amount: i128) {
@@ -164,6 +238,31 @@ impl GuildContract {
         env.storage().persistent().set(&DataKey::Resource(resource), &current);
     }
 
+    pub fn spend_resource(env: Env, officer: Address, resource: Symbol, amount: i128) {
+        officer.require_auth();
+        Self::assert_officer_or_leader(&env, &officer);
+        Self::assert_active(&env);
+
+        if amount <= 0 {
+            panic!("Invalid amount");
+        }
+
+        let current: i128 =
+            env.storage().persistent().get(&DataKey::Resource(resource.clone())).unwrap_or(0);
+
+        if current < amount {
+            panic!("Insufficient resource");
+        }
+
+        env.storage().persistent().set(&DataKey::Resource(resource.clone()), &(current - amount));
+
+        env.events().publish((symbol_short!("RES_SPEND"), officer, resource), amount);
+    }
+
+    pub fn get_resource(env: Env, resource: Symbol) -> i128 {
+        env.storage().persistent().get(&DataKey::Resource(resource)).unwrap_or(0)
+    }
+
     // ───────────── ACHIEVEMENTS ─────────────
 
     pub fn add_achievement(env: Env, officer: Address, achievement: Symbol) {
@@ -195,6 +294,7 @@ impl GuildContract {
             no: 0,
             deadline,
             executed: false,
+            passed: false,
         };
 
         env.storage().persistent().set(&DataKey::Proposal(id), &proposal);
@@ -207,7 +307,8 @@ impl GuildContract {
         member.require_auth();
         Self::assert_active(&env);
 
-        if Self::get_role(env.clone(), member).is_none() {
+        let weight = Self::get_vote_weight(env.clone(), member.clone());
+        if weight == 0 {
             panic!("Not a member");
         }
 
@@ -218,13 +319,107 @@ impl GuildContract {
             panic!("Voting closed");
         }
 
+        let voted_key = DataKey::Voted(proposal_id, member);
+        if env.storage().persistent().has(&voted_key) {
+            panic!("Already voted");
+        }
+        env.storage().persistent().set(&voted_key, &true);
+
         if approve {
-            proposal.yes += 1;
+            proposal.yes += weight;
         } else {
-            proposal.no += 1;
+            proposal.no += weight;
+        }
+
+        env.storage().persistent().set(&DataKey::Proposal(proposal_id), &proposal);
+    }
+
+    pub fn has_voted(env: Env, proposal_id: u32, member: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Voted(proposal_id, member))
+    }
+
+    pub fn set_quorum(env: Env, leader: Address, quorum: u32) {
+        leader.require_auth();
+        Self::assert_leader(&env, &leader);
+        Self::assert_active(&env);
+
+        let mut config: GuildConfig =
+            env.storage().persistent().get(&DataKey::Config).unwrap();
+        config.quorum = quorum;
+        env.storage().persistent().set(&DataKey::Config, &config);
+    }
+
+    pub fn set_vote_weights(env: Env, leader: Address, officer_weight: u32, leader_weight: u32) {
+        leader.require_auth();
+        Self::assert_leader(&env, &leader);
+        Self::assert_active(&env);
+
+        if officer_weight == 0 || leader_weight == 0 {
+            panic!("Weight must be positive");
+        }
+
+        let mut config: GuildConfig =
+            env.storage().persistent().get(&DataKey::Config).unwrap();
+        config.officer_weight = officer_weight;
+        config.leader_weight = leader_weight;
+        env.storage().persistent().set(&DataKey::Config, &config);
+    }
+
+    /// Votes a ballot from `member` counts for; 0 for non-members
+    pub fn get_vote_weight(env: Env, member: Address) -> u32 {
+        let config: GuildConfig =
+            env.storage().persistent().get(&DataKey::Config).unwrap();
+
+        match Self::get_role(env, member) {
+            Some(Role::Member) => 1,
+            Some(Role::Officer) => config.officer_weight,
+            Some(Role::Leader) => config.leader_weight,
+            None => 0,
+        }
+    }
+
+    /// Settle a proposal once voting has closed. It passes with at least
+    /// `quorum` weighted votes cast and more weighted yes than no votes.
+    pub fn execute_proposal(env: Env, caller: Address, proposal_id: u32) -> bool {
+        caller.require_auth();
+        Self::assert_active(&env);
+
+        if Self::get_role(env.clone(), caller).is_none() {
+            panic!("Not a member");
+        }
+
+        let mut proposal: Proposal =
+            env.storage().persistent().get(&DataKey::Proposal(proposal_id)).unwrap();
+
+        if proposal.executed {
+            panic!("Already executed");
+        }
+
+        if env.ledger().timestamp() <= proposal.deadline {
+            panic!("Voting still open");
         }
 
+        let config: GuildConfig =
+            env.storage().persistent().get(&DataKey::Config).unwrap();
+
+        proposal.passed = proposal.yes + proposal.no >= config.quorum && proposal.yes > proposal.no;
+        proposal.executed = true;
         env.storage().persistent().set(&DataKey::Proposal(proposal_id), &proposal);
+
+        env.events().publish(
+            (symbol_short!("proposal"), proposal_id),
+            (proposal.passed, proposal.yes, proposal.no),
+        );
+
+        proposal.passed
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Option<Proposal> {
+        env.storage().persistent().get(&DataKey::Proposal(proposal_id))
+    }
+
+    pub fn get_config(env: Env) -> GuildConfig {
+        env.storage().persistent().get(&DataKey::Config).unwrap()
     }
 
     // ───────────── INTER-GUILD COMPETITION ─────────────
@@ -250,9 +445,19 @@ impl GuildContract {
 
     // ───────────── DISBAND ─────────────
 
+    /// Disband and split the treasury equally between members
     pub fn disband(env: Env, leader: Address) {
+        Self::disband_internal(&env, leader, false);
+    }
+
+    /// Disband and split the treasury pro-rata to each member's net deposits
+    pub fn disband_proportional(env: Env, leader: Address) {
+        Self::disband_internal(&env, leader, true);
+    }
+
+    fn disband_internal(env: &Env, leader: Address, proportional: bool) {
         leader.require_auth();
-        Self::assert_leader(&env, &leader);
+        Self::assert_leader(env, &leader);
 
         let mut config: GuildConfig =
             env.storage().persistent().get(&DataKey::Config).unwrap();
@@ -263,20 +468,48 @@ impl GuildContract {
 
         let token_addr: Address =
             env.storage().instance().get(&DataKey::TreasuryToken).unwrap();
-        let client = token::Client::new(&env, &token_addr);
+        let client = token::Client::new(env, &token_addr);
 
         let members: Vec<Address> =
-            env.storage().persistent().get(&DataKey::MembersList).unwrap();
+            env.storage().persistent().get(&DataKey::MembersList).unwrap_or(Vec::new(env));
+
+        config.disbanded = true;
+        env.storage().persistent().set(&DataKey::Config, &config);
+
+        if members.is_empty() {
+            return;
+        }
 
         let total = client.balance(&env.current_contract_address());
-        let share = total / members.len() as i128;
 
+        // Pro-rata: members who never contributed get nothing. If nobody has
+        // contributed, fall back to an equal split.
+        let mut total_contribution: i128 = 0;
+        if proportional {
+            for m in members.iter() {
+                total_contribution += Self::get_contribution(env.clone(), m);
+            }
+        }
+
+        let mut distributed: i128 = 0;
         for m in members.iter() {
-            client.transfer(&env.current_contract_address(), &m, &share);
+            let share = if total_contribution > 0 {
+                total * Self::get_contribution(env.clone(), m.clone()) / total_contribution
+            } else {
+                total / members.len() as i128
+            };
+
+            if share > 0 {
+                client.transfer(&env.current_contract_address(), &m, &share);
+                distributed += share;
+            }
         }
 
-        config.disbanded = true;
-        env.storage().persistent().set(&DataKey::Config, &config);
+        // Rounding dust goes to the disbanding leader rather than staying stranded
+        let remainder = total - distributed;
+        if remainder > 0 {
+            client.transfer(&env.current_contract_address(), &leader, &remainder);
+        }
     }
 
     // ───────────── HELPERS ─────────────
@@ -293,10 +526,26 @@ impl GuildContract {
         }
     }
 
+    fn remove_member_internal(env: &Env, user: Address) {
+        env.storage().persistent().remove(&DataKey::Member(user.clone()));
+
+        let mut members: Vec<Address> =
+            env.storage().persistent().get(&DataKey::MembersList).unwrap_or(Vec::new(env));
+
+        if let Some(index) = members.first_index_of(&user) {
+            members.remove(index);
+            env.storage().persistent().set(&DataKey::MembersList, &members);
+        }
+    }
+
     pub fn get_role(env: Env, user: Address) -> Option<Role> {
         env.storage().persistent().get(&DataKey::Member(user))
     }
 
+    pub fn get_members(env: Env) -> Vec<Address> {
+        env.storage().persistent().get(&DataKey::MembersList).unwrap_or(Vec::new(&env))
+    }
+
     fn assert_leader(env: &Env, user: &Address) {
         if Self::get_role(env.clone(), user.clone()) != Some(Role::Leader) {
             panic!("Leader only");
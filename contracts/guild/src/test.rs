@@ -2,8 +2,8 @@
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger}, 
-    Address, Env, String, Symbol,
+    testutils::{Address as _, Events, Ledger}, 
+    Address, Env, IntoVal, String, Symbol, TryFromVal,
     token::StellarAssetClient,
     token::Client as TokenClient,
 };
@@ -72,6 +72,138 @@ fn test_guild_lifecycle() {
     assert_eq!(token_client.balance(&officer), 400);
 }
 
+#[test]
+fn test_disband_proportional_to_sole_contributor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let leader = Address::generate(&env);
+    let officer = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_addr, token_client) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    let contract_id = env.register_contract(None, GuildContract);
+    let client = GuildContractClient::new(&env, &contract_id);
+    client.initialize(&leader, &String::from_str(&env, "Stellar Knights"), &token_addr);
+
+    client.join(&member);
+    client.set_role(&leader, &officer, &Role::Officer);
+
+    token_admin_client.mint(&member, &1000);
+    client.deposit(&member, &1000);
+    token_admin_client.mint(&contract_id, &200); // Total 1200
+
+    client.disband_proportional(&leader);
+
+    // Only the member contributed, so they receive the whole treasury
+    assert_eq!(token_client.balance(&member), 1200);
+    assert_eq!(token_client.balance(&leader), 0);
+    assert_eq!(token_client.balance(&officer), 0);
+}
+
+#[test]
+fn test_disband_weighted_by_contribution() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let leader = Address::generate(&env);
+    let small = Address::generate(&env);
+    let large = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_addr, token_client) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    let contract_id = env.register_contract(None, GuildContract);
+    let client = GuildContractClient::new(&env, &contract_id);
+    client.initialize(&leader, &String::from_str(&env, "Pro Rata"), &token_addr);
+
+    client.join(&small);
+    client.join(&large);
+
+    token_admin_client.mint(&small, &100);
+    token_admin_client.mint(&large, &200);
+    client.deposit(&small, &100);
+    client.deposit(&large, &100);
+    client.deposit(&large, &100);
+
+    assert_eq!(client.get_contribution(&small), 100);
+    assert_eq!(client.get_contribution(&large), 200);
+
+    client.disband_proportional(&leader);
+
+    // Twice the deposits, twice the share; the non-contributing leader gets nothing
+    assert_eq!(token_client.balance(&small), 100);
+    assert_eq!(token_client.balance(&large), 200);
+    assert_eq!(token_client.balance(&leader), 0);
+}
+
+#[test]
+fn test_withdraw_keeps_contribution_for_disband() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let leader = Address::generate(&env);
+    let officer = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_addr, token_client) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    let contract_id = env.register_contract(None, GuildContract);
+    let client = GuildContractClient::new(&env, &contract_id);
+    client.initialize(&leader, &String::from_str(&env, "Ledger Keepers"), &token_addr);
+
+    client.join(&officer);
+    client.join(&member);
+    client.set_role(&leader, &officer, &Role::Officer);
+
+    token_admin_client.mint(&officer, &300);
+    token_admin_client.mint(&member, &100);
+    client.deposit(&officer, &300);
+    client.deposit(&member, &100);
+
+    client.withdraw(&officer, &200);
+    assert_eq!(client.get_contribution(&officer), 300);
+
+    client.disband_proportional(&leader);
+
+    // The remaining 200 still splits 3:1 by deposits
+    assert_eq!(token_client.balance(&officer), 200 + 150);
+    assert_eq!(token_client.balance(&member), 50);
+    assert_eq!(token_client.balance(&leader), 0);
+}
+
+#[test]
+fn test_disband_equal_split_without_contributions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let leader = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_addr, token_client) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    let contract_id = env.register_contract(None, GuildContract);
+    let client = GuildContractClient::new(&env, &contract_id);
+    client.initialize(&leader, &String::from_str(&env, "Sponsored"), &token_addr);
+    client.join(&member);
+
+    // Funds arrive without any member deposit
+    token_admin_client.mint(&contract_id, &500);
+
+    client.disband(&leader);
+
+    assert_eq!(token_client.balance(&leader), 250);
+    assert_eq!(token_client.balance(&member), 250);
+}
+
 #[test]
 #[should_panic(expected = "Officer or Leader only")]
 fn test_unauthorized_resource_addition() {
@@ -88,4 +220,435 @@ fn test_unauthorized_resource_addition() {
     client.initialize(&leader, &String::from_str(&env, "DAO"), &token_addr);
     
     client.add_resource(&stranger, &Symbol::new(&env, "Iron"), &100);
-}
\ No newline at end of file
+}
+
+fn setup_guild(env: &Env) -> (GuildContractClient<'_>, Address) {
+    let leader = Address::generate(env);
+    let token_addr = Address::generate(env);
+
+    let contract_id = env.register_contract(None, GuildContract);
+    let client = GuildContractClient::new(env, &contract_id);
+    client.initialize(&leader, &String::from_str(env, "Council"), &token_addr);
+
+    (client, leader)
+}
+
+#[test]
+fn test_execute_passing_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, leader) = setup_guild(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    client.join(&member1);
+    client.join(&member2);
+    client.set_quorum(&leader, &3);
+    assert_eq!(client.get_config().quorum, 3);
+
+    env.ledger().set_timestamp(1000);
+    let proposal_id = client.create_proposal(&leader, &2000);
+    client.vote(&leader, &proposal_id, &true);
+    client.vote(&member1, &proposal_id, &true);
+    client.vote(&member2, &proposal_id, &false);
+
+    env.ledger().set_timestamp(2001);
+    assert!(client.execute_proposal(&member1, &proposal_id));
+
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert!(proposal.executed);
+    assert!(proposal.passed);
+}
+
+#[test]
+fn test_execute_proposal_without_quorum_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, leader) = setup_guild(&env);
+    let member = Address::generate(&env);
+    client.join(&member);
+    client.set_quorum(&leader, &3);
+
+    env.ledger().set_timestamp(1000);
+    let proposal_id = client.create_proposal(&leader, &2000);
+    client.vote(&leader, &proposal_id, &true);
+    client.vote(&member, &proposal_id, &true);
+
+    // Still open at the deadline itself
+    env.ledger().set_timestamp(2000);
+    assert!(client.try_execute_proposal(&leader, &proposal_id).is_err());
+
+    env.ledger().set_timestamp(2001);
+    assert!(!client.execute_proposal(&leader, &proposal_id));
+
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert!(proposal.executed);
+    assert!(!proposal.passed);
+}
+
+#[test]
+#[should_panic(expected = "Already executed")]
+fn test_execute_proposal_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, leader) = setup_guild(&env);
+
+    env.ledger().set_timestamp(1000);
+    let proposal_id = client.create_proposal(&leader, &2000);
+    client.vote(&leader, &proposal_id, &true);
+
+    env.ledger().set_timestamp(2001);
+    client.execute_proposal(&leader, &proposal_id);
+    client.execute_proposal(&leader, &proposal_id);
+}
+
+#[test]
+fn test_distinct_members_each_vote_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, leader) = setup_guild(&env);
+    let member = Address::generate(&env);
+    client.join(&member);
+
+    env.ledger().set_timestamp(1000);
+    let proposal_id = client.create_proposal(&leader, &2000);
+
+    client.vote(&member, &proposal_id, &true);
+    assert!(client.has_voted(&proposal_id, &member));
+    assert!(!client.has_voted(&proposal_id, &leader));
+    assert!(client.try_vote(&member, &proposal_id, &true).is_err());
+
+    client.vote(&leader, &proposal_id, &false);
+
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert_eq!(proposal.yes, 1);
+    assert_eq!(proposal.no, 1);
+}
+
+#[test]
+#[should_panic(expected = "Already voted")]
+fn test_double_vote_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, leader) = setup_guild(&env);
+
+    env.ledger().set_timestamp(1000);
+    let proposal_id = client.create_proposal(&leader, &2000);
+
+    client.vote(&leader, &proposal_id, &true);
+    client.vote(&leader, &proposal_id, &false);
+}
+
+#[test]
+fn test_kicked_and_departed_members_excluded_from_disband() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let leader = Address::generate(&env);
+    let stays = Address::generate(&env);
+    let kicked = Address::generate(&env);
+    let leaves = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_addr, token_client) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    let contract_id = env.register_contract(None, GuildContract);
+    let client = GuildContractClient::new(&env, &contract_id);
+    client.initialize(&leader, &String::from_str(&env, "Strict"), &token_addr);
+
+    for member in [&stays, &kicked, &leaves] {
+        client.join(member);
+        token_admin_client.mint(member, &100);
+        client.deposit(member, &100);
+    }
+
+    client.kick(&leader, &kicked);
+    client.leave(&leaves);
+
+    assert_eq!(client.get_role(&kicked), None);
+    assert_eq!(client.get_role(&leaves), None);
+    assert_eq!(client.get_role(&stays), Some(Role::Member));
+
+    client.disband_proportional(&leader);
+
+    // The remaining contributor takes the whole treasury
+    assert_eq!(token_client.balance(&stays), 300);
+    assert_eq!(token_client.balance(&kicked), 0);
+    assert_eq!(token_client.balance(&leaves), 0);
+}
+
+#[test]
+#[should_panic(expected = "Cannot kick a leader")]
+fn test_cannot_kick_leader() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, leader) = setup_guild(&env);
+    let co_leader = Address::generate(&env);
+    client.set_role(&leader, &co_leader, &Role::Leader);
+
+    client.kick(&leader, &co_leader);
+}
+
+#[test]
+#[should_panic(expected = "Leader cannot leave")]
+fn test_leader_cannot_leave() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, leader) = setup_guild(&env);
+    client.leave(&leader);
+}
+
+#[test]
+fn test_disband_single_member_guild() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let leader = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_addr, token_client) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    let contract_id = env.register_contract(None, GuildContract);
+    let client = GuildContractClient::new(&env, &contract_id);
+    client.initialize(&leader, &String::from_str(&env, "Solo"), &token_addr);
+
+    token_admin_client.mint(&contract_id, &777);
+    client.disband(&leader);
+
+    assert_eq!(token_client.balance(&leader), 777);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    assert!(client.get_config().disbanded);
+}
+
+#[test]
+fn test_disband_sends_remainder_to_leader() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let leader = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_addr, token_client) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    let contract_id = env.register_contract(None, GuildContract);
+    let client = GuildContractClient::new(&env, &contract_id);
+    client.initialize(&leader, &String::from_str(&env, "Uneven"), &token_addr);
+    client.join(&member1);
+    client.join(&member2);
+
+    // 100 split three ways leaves 1 unit of dust
+    token_admin_client.mint(&contract_id, &100);
+    client.disband(&leader);
+
+    assert_eq!(token_client.balance(&member1), 33);
+    assert_eq!(token_client.balance(&member2), 33);
+    assert_eq!(token_client.balance(&leader), 34);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_treasury_resource_and_member_views() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let leader = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_addr, _token_client) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    let contract_id = env.register_contract(None, GuildContract);
+    let client = GuildContractClient::new(&env, &contract_id);
+    client.initialize(&leader, &String::from_str(&env, "Ledgers"), &token_addr);
+    client.join(&member);
+
+    assert_eq!(client.get_treasury_balance(), 0);
+    token_admin_client.mint(&member, &500);
+    client.deposit(&member, &350);
+    assert_eq!(client.get_treasury_balance(), 350);
+
+    let wood = Symbol::new(&env, "Wood");
+    assert_eq!(client.get_resource(&wood), 0);
+    client.add_resource(&leader, &wood, &40);
+    client.add_resource(&leader, &wood, &2);
+    assert_eq!(client.get_resource(&wood), 42);
+
+    let members = client.get_members();
+    assert_eq!(members.len(), 2);
+    assert!(members.contains(&leader));
+    assert!(members.contains(&member));
+
+    client.leave(&member);
+    assert_eq!(client.get_members().len(), 1);
+}
+
+#[test]
+fn test_spend_resource() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, leader) = setup_guild(&env);
+    let officer = Address::generate(&env);
+    client.set_role(&leader, &officer, &Role::Officer);
+
+    let ore = Symbol::new(&env, "Ore");
+    client.add_resource(&leader, &ore, &100);
+
+    client.spend_resource(&officer, &ore, &30);
+    assert_eq!(client.get_resource(&ore), 70);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("RES_SPEND"), officer.clone(), ore.clone()).into_val(&env));
+    assert_eq!(i128::try_from_val(&env, &data).unwrap(), 30);
+
+    // Overspending is rejected and leaves the pool untouched
+    assert!(client.try_spend_resource(&officer, &ore, &71).is_err());
+    assert_eq!(client.get_resource(&ore), 70);
+
+    client.spend_resource(&leader, &ore, &70);
+    assert_eq!(client.get_resource(&ore), 0);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient resource")]
+fn test_spend_missing_resource() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, leader) = setup_guild(&env);
+    client.spend_resource(&leader, &Symbol::new(&env, "Silk"), &1);
+}
+
+#[test]
+fn test_transfer_leadership() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, leader) = setup_guild(&env);
+    let member = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.join(&member);
+    client.join(&other);
+
+    client.transfer_leadership(&leader, &member);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("LEAD_XFER"), leader.clone()).into_val(&env));
+    assert_eq!(Address::try_from_val(&env, &data).unwrap(), member);
+    assert_eq!(client.get_role(&member), Some(Role::Leader));
+    assert_eq!(client.get_role(&leader), Some(Role::Officer));
+
+    // The old leader keeps officer rights but loses leader-only ones
+    client.add_resource(&leader, &Symbol::new(&env, "Gem"), &1);
+    assert!(client.try_set_role(&leader, &other, &Role::Officer).is_err());
+    assert!(client.try_kick(&leader, &other).is_err());
+
+    client.set_role(&member, &other, &Role::Officer);
+    assert_eq!(client.get_role(&other), Some(Role::Officer));
+
+    // The old leader can now be removed
+    client.kick(&member, &leader);
+    assert_eq!(client.get_role(&leader), None);
+}
+
+#[test]
+#[should_panic(expected = "Not a member")]
+fn test_transfer_leadership_to_non_member() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, leader) = setup_guild(&env);
+    client.transfer_leadership(&leader, &Address::generate(&env));
+}
+
+#[test]
+fn test_officer_vote_outweighs_members() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, leader) = setup_guild(&env);
+    let officer = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    client.set_role(&leader, &officer, &Role::Officer);
+    client.join(&member1);
+    client.join(&member2);
+
+    client.set_vote_weights(&leader, &3, &5);
+    assert_eq!(client.get_vote_weight(&member1), 1);
+    assert_eq!(client.get_vote_weight(&officer), 3);
+    assert_eq!(client.get_vote_weight(&leader), 5);
+    assert_eq!(client.get_vote_weight(&Address::generate(&env)), 0);
+
+    env.ledger().set_timestamp(1000);
+    let proposal_id = client.create_proposal(&officer, &2000);
+    client.vote(&officer, &proposal_id, &true);
+    client.vote(&member1, &proposal_id, &false);
+    client.vote(&member2, &proposal_id, &false);
+
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert_eq!(proposal.yes, 3);
+    assert_eq!(proposal.no, 2);
+
+    env.ledger().set_timestamp(2001);
+    assert!(client.execute_proposal(&member1, &proposal_id));
+}
+
+#[test]
+fn test_proportional_and_equal_disband() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let leader = Address::generate(&env);
+    let member1 = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_addr, token_client) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    // Same deposits, disbanded both ways
+    let setup = |name: &str| {
+        let contract_id = env.register_contract(None, GuildContract);
+        let client = GuildContractClient::new(&env, &contract_id);
+        client.initialize(&leader, &String::from_str(&env, name), &token_addr);
+        client.join(&member1);
+        client.join(&member2);
+
+        token_admin_client.mint(&member1, &100);
+        token_admin_client.mint(&member2, &300);
+        client.deposit(&member1, &100);
+        client.deposit(&member2, &300);
+        // Treasury growth that isn't anyone's deposit
+        token_admin_client.mint(&contract_id, &101);
+        (contract_id, client)
+    };
+
+    let (proportional_id, proportional) = setup("Pro Rata");
+    proportional.disband_proportional(&leader);
+
+    // 501 split 1:3, with the unit of dust going to the leader
+    assert_eq!(token_client.balance(&member1), 125);
+    assert_eq!(token_client.balance(&member2), 375);
+    assert_eq!(token_client.balance(&leader), 1);
+    assert_eq!(token_client.balance(&proportional_id), 0);
+
+    let (equal_id, equal) = setup("Equal");
+    equal.disband(&leader);
+
+    // 501 split three ways regardless of deposits
+    assert_eq!(token_client.balance(&member1), 125 + 167);
+    assert_eq!(token_client.balance(&member2), 375 + 167);
+    assert_eq!(token_client.balance(&leader), 1 + 167);
+    assert_eq!(token_client.balance(&equal_id), 0);
+}
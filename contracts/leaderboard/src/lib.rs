@@ -27,10 +27,18 @@ pub enum DataKey {
     Config,                                    // LeaderboardConfig
     PlayerScore(Address, TimePeriod, u64),     // PlayerScore - (player, period, period_id)
     TopScores(TimePeriod, u64),                // Vec<PlayerScore> - sorted top scores for period
-    PlayerAllTimeScore(Address),               // i128 - cumulative all-time score
+    PlayerAllTimeScore(Address),               // i128 - cumulative all-time score; set for every counted player
     TotalPlayers,                              // u32
     HighScore(TimePeriod),                     // i128 - record high score per period type
     Verifier(Address),                         // bool - authorized score verifiers
+    CumulativeTopScores,                       // Vec<PlayerScore> - sorted by cumulative all-time total
+    LastSubmission(Address),                   // u64 - timestamp of the player's last submission
+    DecayedPeriods(Address),                   // u32 - decay periods already applied since then
+    ActiveSeason,                              // u32 - season currently receiving submissions
+    Season(u32),                               // Season
+    SeasonScore(u32, Address),                 // i128 - player's total for a season
+    SeasonTop(u32),                            // Vec<PlayerScore> - live sorted board for a season
+    SeasonFinal(u32),                          // Vec<PlayerScore> - archived final ranking
 }
 
 //
@@ -47,6 +55,17 @@ pub struct LeaderboardConfig {
     pub daily_period_length: u64,  // Seconds (86400 for 24 hours)
     pub weekly_period_length: u64, // Seconds (604800 for 7 days)
     pub paused: bool,
+    pub decay_bps: u32,            // All-time score lost per inactive decay period (0 = off)
+    pub decay_period_seconds: u64, // Length of a decay period in seconds
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Season {
+    pub season_id: u32,
+    pub start_ts: u64,
+    pub end_ts: u64,     // Submissions at or after this time don't count
+    pub finalized: bool,
 }
 
 #[contracttype]
@@ -68,6 +87,8 @@ pub struct PlayerScore {
 const DEFAULT_DAILY_PERIOD: u64 = 86_400;     // 24 hours
 const DEFAULT_WEEKLY_PERIOD: u64 = 604_800;   // 7 days
 const DEFAULT_MAX_TOP_ENTRIES: u32 = 100;
+const BASIS_POINTS: i128 = 10_000;
+const DECAY_SCALE: i128 = 1_000_000_000_000_000_000; // 1e18 fixed point for decay
 
 //
 // ──────────────────────────────────────────────────────────
@@ -79,6 +100,10 @@ const DEFAULT_MAX_TOP_ENTRIES: u32 = 100;
 const NEW_HIGH_SCORE: Symbol = symbol_short!("hi_score");
 const SCORE_SUBMIT: Symbol = symbol_short!("submit");
 const RANK_CHANGE: Symbol = symbol_short!("rank_chg");
+const PLAYER_RM: Symbol = symbol_short!("player_rm");
+const SCORE_DECAY: Symbol = symbol_short!("decay");
+const SEASON_START: Symbol = symbol_short!("season_st");
+const SEASON_END: Symbol = symbol_short!("season_fn");
 
 //
 // ──────────────────────────────────────────────────────────
@@ -117,6 +142,8 @@ impl LeaderboardContract {
             daily_period_length: DEFAULT_DAILY_PERIOD,
             weekly_period_length: DEFAULT_WEEKLY_PERIOD,
             paused: false,
+            decay_bps: 0,
+            decay_period_seconds: 0,
         };
 
         env.storage().persistent().set(&DataKey::Config, &config);
@@ -188,6 +215,149 @@ impl LeaderboardContract {
         env.storage().persistent().set(&DataKey::Config, &config);
     }
 
+    /// Configure all-time score decay for inactive players (admin only)
+    ///
+    /// # Arguments
+    /// * `decay_bps` - Share of the all-time score lost per decay period, in basis points (0 disables decay)
+    /// * `decay_period_seconds` - Inactivity needed for each decay step
+    pub fn set_decay_config(env: Env, admin: Address, decay_bps: u32, decay_period_seconds: u64) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+
+        if decay_bps as i128 > BASIS_POINTS {
+            panic!("Decay cannot exceed 100%");
+        }
+        if decay_bps > 0 && decay_period_seconds == 0 {
+            panic!("Decay period must be positive");
+        }
+
+        let mut config: LeaderboardConfig =
+            env.storage().persistent().get(&DataKey::Config).unwrap();
+        config.decay_bps = decay_bps;
+        config.decay_period_seconds = decay_period_seconds;
+        env.storage().persistent().set(&DataKey::Config, &config);
+    }
+
+    /// Remove a player from the leaderboard (admin only)
+    ///
+    /// Clears the cumulative total, current period scores and active season
+    /// score, pulls the player from the current top lists and decrements the
+    /// player count. Scores from past periods and seasons are left in place.
+    pub fn remove_player(env: Env, admin: Address, player: Address) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+
+        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let current_time = env.ledger().timestamp();
+
+        for period in [TimePeriod::Daily, TimePeriod::Weekly, TimePeriod::AllTime] {
+            let period_id = Self::get_current_period_id(&config, period, current_time);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::PlayerScore(player.clone(), period, period_id));
+            Self::remove_from_board(&env, &DataKey::TopScores(period, period_id), &player);
+        }
+        Self::remove_from_board(&env, &DataKey::CumulativeTopScores, &player);
+
+        if let Some(season_id) = env.storage().persistent().get::<_, u32>(&DataKey::ActiveSeason) {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::SeasonScore(season_id, player.clone()));
+            Self::remove_from_board(&env, &DataKey::SeasonTop(season_id), &player);
+        }
+
+        let all_time: Option<i128> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PlayerAllTimeScore(player.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PlayerAllTimeScore(player.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::LastSubmission(player.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::DecayedPeriods(player.clone()));
+
+        // Every player with an all-time entry was counted in submit_score
+        if all_time.is_some() {
+            let total: u32 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::TotalPlayers)
+                .unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&DataKey::TotalPlayers, &total.saturating_sub(1));
+        }
+
+        env.events().publish((PLAYER_RM, player), all_time.unwrap_or(0));
+    }
+
+    // ───────────── SEASONS ─────────────
+
+    /// Start a named season (admin only)
+    ///
+    /// Submissions count toward the season until `end_ts` or until it is
+    /// finalized, whichever comes first. Only one season runs at a time.
+    pub fn start_season(env: Env, admin: Address, season_id: u32, end_ts: u64) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+
+        if env.storage().persistent().has(&DataKey::ActiveSeason) {
+            panic!("Season already active");
+        }
+        if env.storage().persistent().has(&DataKey::Season(season_id)) {
+            panic!("Season already exists");
+        }
+
+        let now = env.ledger().timestamp();
+        if end_ts <= now {
+            panic!("Season must end in the future");
+        }
+
+        let season = Season {
+            season_id,
+            start_ts: now,
+            end_ts,
+            finalized: false,
+        };
+        env.storage().persistent().set(&DataKey::Season(season_id), &season);
+        env.storage().persistent().set(&DataKey::ActiveSeason, &season_id);
+
+        env.events().publish((SEASON_START, season_id), end_ts);
+    }
+
+    /// Close the active season and archive its top-N ranking (admin only)
+    pub fn finalize_season(env: Env, admin: Address, season_id: u32) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+
+        let mut season: Season = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Season(season_id))
+            .expect("Season not found");
+        if season.finalized {
+            panic!("Season already finalized");
+        }
+
+        let results: Vec<PlayerScore> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SeasonTop(season_id))
+            .unwrap_or(Vec::new(&env));
+        env.storage().persistent().set(&DataKey::SeasonFinal(season_id), &results);
+        env.storage().persistent().remove(&DataKey::SeasonTop(season_id));
+
+        season.finalized = true;
+        env.storage().persistent().set(&DataKey::Season(season_id), &season);
+        env.storage().persistent().remove(&DataKey::ActiveSeason);
+
+        env.events().publish((SEASON_END, season_id), results.len());
+    }
+
     // ───────────── SCORE SUBMISSION ─────────────
 
     /// Submit a verified score for a player
@@ -206,48 +376,113 @@ impl LeaderboardContract {
             panic!("Score must be non-negative");
         }
 
+        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        Self::record_score(&env, &config, &player, score, env.ledger().timestamp());
+    }
+
+    /// Submit verified scores for several players in one call
+    /// Can be called by admin or authorized verifier
+    ///
+    /// Every score is validated before any is applied, so one negative score
+    /// reverts the whole batch.
+    ///
+    /// # Arguments
+    /// * `submitter` - The admin or verifier submitting the scores
+    /// * `players` - The players who earned the scores
+    /// * `scores` - Score for each player, matched by index
+    pub fn submit_scores_batch(env: Env, submitter: Address, players: Vec<Address>, scores: Vec<i128>) {
+        submitter.require_auth();
+        Self::assert_not_paused(&env);
+        Self::assert_authorized_submitter(&env, &submitter);
+
+        if players.len() != scores.len() {
+            panic!("Players and scores length mismatch");
+        }
+
+        for score in scores.iter() {
+            if score < 0 {
+                panic!("Score must be non-negative");
+            }
+        }
+
         let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
         let current_time = env.ledger().timestamp();
 
-        // Calculate period IDs
-        let daily_period_id = current_time / config.daily_period_length;
-        let weekly_period_id = current_time / config.weekly_period_length;
-        let all_time_period_id = 0u64; // All-time uses 0 as period ID
+        for (player, score) in players.iter().zip(scores.iter()) {
+            Self::record_score(&env, &config, &player, score, current_time);
+        }
+    }
 
-        // Update scores for each time period
-        Self::update_period_score(&env, &config, &player, score, TimePeriod::Daily, daily_period_id, current_time);
-        Self::update_period_score(&env, &config, &player, score, TimePeriod::Weekly, weekly_period_id, current_time);
-        Self::update_period_score(&env, &config, &player, score, TimePeriod::AllTime, all_time_period_id, current_time);
+    /// Decay an inactive player's all-time scores
+    /// Anyone can call this since it only ever lowers scores
+    ///
+    /// Each full decay period since the player's last submission removes
+    /// `decay_bps` of their cumulative total and all-time period score. Periods
+    /// already applied are not applied again. Returns the new cumulative total.
+    ///
+    /// # Arguments
+    /// * `caller` - Whoever triggers the decay
+    /// * `player` - The player whose scores decay
+    pub fn apply_decay(env: Env, caller: Address, player: Address) -> i128 {
+        caller.require_auth();
+        Self::assert_not_paused(&env);
 
-        // Update cumulative all-time score
-        let current_all_time: i128 = env
+        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let total = Self::get_player_all_time_total(env.clone(), player.clone());
+        if config.decay_bps == 0 || total == 0 {
+            return total;
+        }
+
+        let last_submission: u64 = match env.storage().persistent().get(&DataKey::LastSubmission(player.clone())) {
+            Some(ts) => ts,
+            None => return total,
+        };
+        let elapsed_periods = (env.ledger().timestamp().saturating_sub(last_submission)
+            / config.decay_period_seconds) as u32;
+        let applied: u32 = env
             .storage()
             .persistent()
-            .get(&DataKey::PlayerAllTimeScore(player.clone()))
+            .get(&DataKey::DecayedPeriods(player.clone()))
             .unwrap_or(0);
-        
-        let is_new_player = current_all_time == 0;
-        let new_all_time = current_all_time + score;
-        
+        if elapsed_periods <= applied {
+            return total;
+        }
+        let periods = elapsed_periods - applied;
         env.storage()
             .persistent()
-            .set(&DataKey::PlayerAllTimeScore(player.clone()), &new_all_time);
+            .set(&DataKey::DecayedPeriods(player.clone()), &elapsed_periods);
 
-        // Update total players count if new player
-        if is_new_player {
-            let total: u32 = env
-                .storage()
-                .persistent()
-                .get(&DataKey::TotalPlayers)
-                .unwrap_or(0);
-            env.storage()
-                .persistent()
-                .set(&DataKey::TotalPlayers, &(total + 1));
+        // Cumulative total and the cumulative board; a fully decayed player
+        // stays counted but drops off the board
+        let new_total = Self::decay_score(total, config.decay_bps, periods);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PlayerAllTimeScore(player.clone()), &new_total);
+        if new_total > 0 {
+            let cumulative_score = PlayerScore {
+                player: player.clone(),
+                score: new_total,
+                timestamp: last_submission,
+                period: TimePeriod::AllTime,
+                period_id: 0,
+            };
+            Self::insert_into_board(&env, &config, &DataKey::CumulativeTopScores, &cumulative_score);
+        } else {
+            Self::remove_from_board(&env, &DataKey::CumulativeTopScores, &player);
+        }
+
+        // All-time period score and its board
+        let all_time_key = DataKey::PlayerScore(player.clone(), TimePeriod::AllTime, 0);
+        if let Some(mut all_time) = env.storage().persistent().get::<_, PlayerScore>(&all_time_key) {
+            all_time.score = Self::decay_score(all_time.score, config.decay_bps, periods);
+            env.storage().persistent().set(&all_time_key, &all_time);
+            Self::update_top_scores_list(&env, &config, &all_time, TimePeriod::AllTime, 0);
         }
 
-        // Emit score submission event
         env.events()
-            .publish((SCORE_SUBMIT, player.clone()), (score, current_time));
+            .publish((SCORE_DECAY, player), (total, new_total));
+
+        new_total
     }
 
     /// Update a player's score directly (replace existing score)
@@ -326,6 +561,10 @@ impl LeaderboardContract {
     /// Get a player's rank for a specific time period
     /// Returns 0 if player not found in top rankings
     ///
+    /// `AllTime` ranks the all-time period board, which an admin can overwrite
+    /// with `update_score`. Use `get_cumulative_rank` to rank by the cumulative
+    /// total from `get_player_all_time_total`.
+    ///
     /// # Arguments
     /// * `player` - Player address
     /// * `period` - The time period (Daily, Weekly, AllTime)
@@ -343,13 +582,45 @@ impl LeaderboardContract {
         for i in 0..top_scores.len() {
             let score = top_scores.get(i).unwrap();
             if score.player == player {
-                return (i + 1) as u32; // Rank is 1-indexed
+                return i + 1; // Rank is 1-indexed
             }
         }
 
         0 // Not ranked
     }
 
+    /// Get the top N players by cumulative all-time total
+    pub fn get_top_cumulative_players(env: Env, limit: u32) -> Vec<PlayerScore> {
+        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let top_scores: Vec<PlayerScore> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CumulativeTopScores)
+            .unwrap_or(Vec::new(&env));
+
+        let mut result = Vec::new(&env);
+        for i in 0..top_scores.len().min(limit).min(config.max_top_entries) {
+            result.push_back(top_scores.get(i).unwrap());
+        }
+
+        result
+    }
+
+    /// Get a player's rank by cumulative all-time total (sum of every submission)
+    /// Returns 0 if player not found in top rankings
+    pub fn get_cumulative_rank(env: Env, player: Address) -> u32 {
+        let top_scores: Vec<PlayerScore> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CumulativeTopScores)
+            .unwrap_or(Vec::new(&env));
+
+        match top_scores.iter().position(|score| score.player == player) {
+            Some(index) => index as u32 + 1,
+            None => 0, // Not ranked
+        }
+    }
+
     /// Get a player's score for a specific time period
     pub fn get_player_score(env: Env, player: Address, period: TimePeriod) -> Option<PlayerScore> {
         let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
@@ -369,6 +640,46 @@ impl LeaderboardContract {
             .unwrap_or(0)
     }
 
+    /// Get a player's current daily score, current weekly score and cumulative
+    /// all-time total in one call. Periods without a score return 0.
+    pub fn get_player_all_scores(env: Env, player: Address) -> (i128, i128, i128) {
+        let daily = Self::get_player_score(env.clone(), player.clone(), TimePeriod::Daily)
+            .map(|s| s.score)
+            .unwrap_or(0);
+        let weekly = Self::get_player_score(env.clone(), player.clone(), TimePeriod::Weekly)
+            .map(|s| s.score)
+            .unwrap_or(0);
+        let all_time = Self::get_player_all_time_total(env, player);
+
+        (daily, weekly, all_time)
+    }
+
+    /// Get the archived final ranking of a season (empty until finalized)
+    pub fn get_season_results(env: Env, season_id: u32) -> Vec<PlayerScore> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SeasonFinal(season_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get a season's details
+    pub fn get_season(env: Env, season_id: u32) -> Option<Season> {
+        env.storage().persistent().get(&DataKey::Season(season_id))
+    }
+
+    /// Get the season currently receiving submissions, if any
+    pub fn get_active_season(env: Env) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::ActiveSeason)
+    }
+
+    /// Get a player's total for a season
+    pub fn get_season_score(env: Env, season_id: u32, player: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SeasonScore(season_id, player))
+            .unwrap_or(0)
+    }
+
     /// Get the current high score record for a time period type
     pub fn get_high_score(env: Env, period: TimePeriod) -> i128 {
         env.storage()
@@ -415,6 +726,71 @@ impl LeaderboardContract {
         }
     }
 
+    /// Apply a validated score to every period board, the cumulative total and
+    /// the player count, and emit the submission event
+    fn record_score(env: &Env, config: &LeaderboardConfig, player: &Address, score: i128, current_time: u64) {
+        // Calculate period IDs
+        let daily_period_id = current_time / config.daily_period_length;
+        let weekly_period_id = current_time / config.weekly_period_length;
+        let all_time_period_id = 0u64; // All-time uses 0 as period ID
+
+        // Update scores for each time period
+        Self::update_period_score(env, config, player, score, TimePeriod::Daily, daily_period_id, current_time);
+        Self::update_period_score(env, config, player, score, TimePeriod::Weekly, weekly_period_id, current_time);
+        Self::update_period_score(env, config, player, score, TimePeriod::AllTime, all_time_period_id, current_time);
+
+        // Update cumulative all-time score
+        let stored_all_time: Option<i128> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PlayerAllTimeScore(player.clone()));
+
+        // The entry marks membership, so a total of 0 doesn't make a player new
+        let is_new_player = stored_all_time.is_none();
+        let new_all_time = stored_all_time.unwrap_or(0) + score;
+        
+        env.storage()
+            .persistent()
+            .set(&DataKey::PlayerAllTimeScore(player.clone()), &new_all_time);
+
+        if score > 0 {
+            let cumulative_score = PlayerScore {
+                player: player.clone(),
+                score: new_all_time,
+                timestamp: current_time,
+                period: TimePeriod::AllTime,
+                period_id: all_time_period_id,
+            };
+            Self::insert_into_board(env, config, &DataKey::CumulativeTopScores, &cumulative_score);
+        }
+
+        // Update total players count if new player
+        if is_new_player {
+            let total: u32 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::TotalPlayers)
+                .unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&DataKey::TotalPlayers, &(total + 1));
+        }
+
+        Self::record_season_score(env, config, player, score, current_time);
+
+        // Activity restarts the decay clock
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastSubmission(player.clone()), &current_time);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::DecayedPeriods(player.clone()));
+
+        // Emit score submission event
+        env.events()
+            .publish((SCORE_SUBMIT, player.clone()), (score, current_time));
+    }
+
     fn update_period_score(
         env: &Env,
         config: &LeaderboardConfig,
@@ -432,8 +808,11 @@ impl LeaderboardContract {
 
         let new_score = match existing_score {
             Some(mut existing) => {
-                existing.score += score;
-                existing.timestamp = current_time;
+                // Keep the time the score was first reached for tie-breaking
+                if score > 0 {
+                    existing.score += score;
+                    existing.timestamp = current_time;
+                }
                 existing
             }
             None => PlayerScore {
@@ -479,62 +858,141 @@ impl LeaderboardContract {
         period: TimePeriod,
         period_id: u64,
     ) {
+        let key = DataKey::TopScores(period, period_id);
+
+        // Emit rank change event if rank changed
+        if let Some((old_rank, new_rank)) = Self::insert_into_board(env, config, &key, player_score) {
+            env.events().publish(
+                (RANK_CHANGE, player_score.player.clone()),
+                (period, old_rank, new_rank),
+            );
+        }
+    }
+
+    /// Place `player_score` on the sorted board stored under `key`, replacing the
+    /// player's previous entry. Returns `(old_rank, new_rank)` if the rank changed.
+    fn insert_into_board(
+        env: &Env,
+        config: &LeaderboardConfig,
+        key: &DataKey,
+        player_score: &PlayerScore,
+    ) -> Option<(u32, u32)> {
         let top_scores: Vec<PlayerScore> = env
             .storage()
             .persistent()
-            .get(&DataKey::TopScores(period, period_id))
+            .get(key)
             .unwrap_or(Vec::new(env));
 
+        // On a full board, a player not already listed must outrank the lowest
+        // entry; otherwise the existing entry stays and no ranks change
+        let on_board = top_scores.iter().any(|existing| existing.player == player_score.player);
+        if !on_board && top_scores.len() >= config.max_top_entries {
+            let lowest = top_scores.get(top_scores.len() - 1).unwrap();
+            if Self::ranks_above(&lowest, player_score) {
+                return None;
+            }
+        }
+
         // Remove existing entry for this player if present
-        let mut new_list: Vec<PlayerScore> = Vec::new(env);
+        let mut list = top_scores;
         let mut old_rank: u32 = 0;
-        let mut index: u32 = 1;
-        
-        for existing in top_scores.iter() {
-            if existing.player != player_score.player {
-                new_list.push_back(existing);
+        if on_board {
+            let old_index = list
+                .iter()
+                .position(|existing| existing.player == player_score.player)
+                .unwrap() as u32;
+            list.remove(old_index);
+            old_rank = old_index + 1;
+        }
+
+        // Binary search for the first entry the new score outranks (descending order)
+        let mut low: u32 = 0;
+        let mut high: u32 = list.len();
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if Self::ranks_above(&list.get(mid).unwrap(), player_score) {
+                low = mid + 1;
             } else {
-                old_rank = index;
+                high = mid;
             }
-            index += 1;
         }
 
-        // Insert new score in sorted position (descending order)
-        let mut inserted = false;
-        let mut final_list: Vec<PlayerScore> = Vec::new(env);
         let mut new_rank: u32 = 0;
-        index = 1;
-
-        for existing in new_list.iter() {
-            if !inserted && player_score.score > existing.score {
-                final_list.push_back(player_score.clone());
-                new_rank = index;
-                inserted = true;
-                index += 1;
-            }
-            if final_list.len() < config.max_top_entries {
-                final_list.push_back(existing);
-            }
-            index += 1;
+        if low < config.max_top_entries {
+            list.insert(low, player_score.clone());
+            new_rank = low + 1;
         }
-
-        // If not inserted yet and list not full, append
-        if !inserted && final_list.len() < config.max_top_entries {
-            final_list.push_back(player_score.clone());
-            new_rank = final_list.len() as u32;
+        while list.len() > config.max_top_entries {
+            list.pop_back();
         }
 
-        // Emit rank change event if rank changed
+        env.storage().persistent().set(key, &list);
+
         if new_rank > 0 && new_rank != old_rank {
-            env.events().publish(
-                (RANK_CHANGE, player_score.player.clone()),
-                (period, old_rank, new_rank),
-            );
+            Some((old_rank, new_rank))
+        } else {
+            None
         }
+    }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::TopScores(period, period_id), &final_list);
+    fn remove_from_board(env: &Env, key: &DataKey, player: &Address) {
+        let mut list: Vec<PlayerScore> = match env.storage().persistent().get(key) {
+            Some(list) => list,
+            None => return,
+        };
+        if let Some(index) = list.iter().position(|entry| entry.player == *player) {
+            list.remove(index as u32);
+            env.storage().persistent().set(key, &list);
+        }
+    }
+
+    /// Add a score to the active season, if one is running
+    fn record_season_score(env: &Env, config: &LeaderboardConfig, player: &Address, score: i128, current_time: u64) {
+        let season_id: u32 = match env.storage().persistent().get(&DataKey::ActiveSeason) {
+            Some(id) => id,
+            None => return,
+        };
+        let season: Season = env.storage().persistent().get(&DataKey::Season(season_id)).unwrap();
+        if current_time >= season.end_ts || score == 0 {
+            return;
+        }
+
+        let key = DataKey::SeasonScore(season_id, player.clone());
+        let total: i128 = env.storage().persistent().get(&key).unwrap_or(0) + score;
+        env.storage().persistent().set(&key, &total);
+
+        let season_score = PlayerScore {
+            player: player.clone(),
+            score: total,
+            timestamp: current_time,
+            period: TimePeriod::AllTime,
+            period_id: season_id as u64,
+        };
+        Self::insert_into_board(env, config, &DataKey::SeasonTop(season_id), &season_score);
+    }
+
+    /// Apply `periods` rounds of `decay_bps` decay to `score`. The kept
+    /// fraction is raised to the power of `periods` by squaring, so the cost
+    /// grows with the number of bits in `periods` rather than its value.
+    fn decay_score(score: i128, decay_bps: u32, periods: u32) -> i128 {
+        let mut base = (BASIS_POINTS - decay_bps as i128) * (DECAY_SCALE / BASIS_POINTS);
+        let mut keep = DECAY_SCALE;
+        let mut exp = periods;
+        while exp > 0 && keep > 0 {
+            if exp & 1 == 1 {
+                keep = keep * base / DECAY_SCALE;
+            }
+            base = base * base / DECAY_SCALE;
+            exp >>= 1;
+        }
+        // Split the score so the multiplication can't overflow
+        score / DECAY_SCALE * keep + score % DECAY_SCALE * keep / DECAY_SCALE
+    }
+
+    /// Whether `a` ranks at or above `b`: higher score first, and on equal
+    /// scores whoever reached the score earlier
+    fn ranks_above(a: &PlayerScore, b: &PlayerScore) -> bool {
+        a.score > b.score || (a.score == b.score && a.timestamp <= b.timestamp)
     }
 
     fn assert_admin(env: &Env, user: &Address) {
@@ -1,9 +1,22 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, Symbol,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short,
+    xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
 };
 
+/// Ledgers that must close between `commit_solution` and `reveal_solution`,
+/// so a commitment can never be made and revealed in the same ledger.
+const MIN_REVEAL_DELAY: u32 = 1;
+
+const SOLVED: Symbol = symbol_short!("SOLVED");
+
+/// The part of AchievementNFT used to grant achievements on a solve
+#[contractclient(name = "AchievementClient")]
+pub trait AchievementInterface {
+    fn mark_puzzle_completed(env: Env, user: Address, puzzle_id: u32);
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct PuzzleMeta {
@@ -39,7 +52,7 @@ mod double_claim_test {
         let preimage = Bytes::from_array(&env, &[9u8; 4]);
         let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
         let now = env.ledger().timestamp();
-        client.set_puzzle(&7, &hash, &(now - 1), &(now + 1000), &3, &5);
+        client.set_puzzle(&admin, &7, &hash, &(now - 1), &(now + 1000), &3, &5);
 
         assert!(client.verify_solution(&player, &7, &preimage));
         // Second attempt should panic
@@ -47,12 +60,33 @@ mod double_claim_test {
     }
 }
 
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// Another attempt on the same puzzle within the cooldown
+    TooFrequent = 1,
+    /// The player has used up their wrong answers on this puzzle
+    TooManyAttempts = 2,
+    /// The ledger time is outside the puzzle's window
+    PuzzleNotOpen = 3,
+}
+
 #[contracttype]
 pub enum DataKey {
     Admin,
     Puzzle(u32),
-    Completed(Address, u32),
+    Completed(Address, u32, u32),
     Rewards(Address),
+    AttemptCooldown,
+    LastAttempt(Address, u32),
+    CommitRequired,
+    Commitment(Address, u32),
+    SolveCount(u32),
+    PuzzleVersion(u32),
+    MaxAttempts,
+    Attempts(Address, u32, u32),
+    AchievementContract,
 }
 
 #[contract]
@@ -68,18 +102,29 @@ impl PuzzleVerification {
         env.storage().instance().set(&DataKey::Admin, &admin);
     }
 
-    fn require_admin(env: &Env) {
-        let admin: Address = env
+    fn assert_admin(env: &Env, admin: &Address) {
+        let stored: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
             .expect("admin");
+        if *admin != stored {
+            panic!("unauthorized");
+        }
         admin.require_auth();
     }
 
-    /// Admin: define or update a puzzle
+    fn in_window(env: &Env, meta: &PuzzleMeta) -> bool {
+        let now = env.ledger().timestamp();
+        (meta.start_ts == 0 || now >= meta.start_ts) && (meta.end_ts == 0 || now <= meta.end_ts)
+    }
+
+    /// Admin: define or update a puzzle. A zero `start_ts` or `end_ts`
+    /// leaves that end of the window open.
+    #[allow(clippy::too_many_arguments)]
     pub fn set_puzzle(
         env: Env,
+        admin: Address,
         puzzle_id: u32,
         solution_hash: BytesN<32>,
         start_ts: u64,
@@ -87,8 +132,8 @@ impl PuzzleVerification {
         difficulty: u32,
         reward_points: i128,
     ) {
-        Self::require_admin(&env);
-        if end_ts <= start_ts {
+        Self::assert_admin(&env, &admin);
+        if start_ts != 0 && end_ts != 0 && end_ts <= start_ts {
             panic!("invalid time window");
         }
         let meta = PuzzleMeta {
@@ -99,46 +144,259 @@ impl PuzzleVerification {
             difficulty,
             reward_points,
         };
+        write_persistent(&env, &DataKey::Puzzle(puzzle_id), &meta);
+    }
+
+    /// Admin: replace a puzzle's solution hash, e.g. after a leak. Bumps the
+    /// puzzle version so earlier completions no longer count and players can
+    /// solve it again.
+    pub fn rotate_solution(env: Env, admin: Address, puzzle_id: u32, new_hash: BytesN<32>) {
+        Self::assert_admin(&env, &admin);
+        let mut meta = Self::get_puzzle(env.clone(), puzzle_id).expect("puzzle");
+        meta.solution_hash = new_hash;
+        write_persistent(&env, &DataKey::Puzzle(puzzle_id), &meta);
+
+        let version = Self::get_puzzle_version(env.clone(), puzzle_id) + 1;
+        write_persistent(&env, &DataKey::PuzzleVersion(puzzle_id), &version);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::SolveCount(puzzle_id));
+
+        env.events().publish(
+            (Symbol::new(&env, "puzzle"), Symbol::new(&env, "rotated")),
+            (puzzle_id, version),
+        );
+    }
+
+    /// Current solution version of a puzzle, starting at 1
+    pub fn get_puzzle_version(env: Env, puzzle_id: u32) -> u32 {
+        read_persistent(&env, &DataKey::PuzzleVersion(puzzle_id)).unwrap_or(1)
+    }
+
+    /// Admin: set the minimum number of seconds between verification attempts
+    /// by the same player on the same puzzle. Zero disables the cooldown.
+    pub fn set_attempt_cooldown(env: Env, admin: Address, cooldown_secs: u64) {
+        Self::assert_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::AttemptCooldown, &cooldown_secs);
+    }
+
+    pub fn get_attempt_cooldown(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::AttemptCooldown)
+            .unwrap_or(0)
+    }
+
+    /// Admin: set the AchievementNFT contract to mark solves on, or None to
+    /// run standalone. This contract must be that contract's verifier.
+    pub fn set_achievement_contract(env: Env, admin: Address, contract: Option<Address>) {
+        Self::assert_admin(&env, &admin);
+        match contract {
+            Some(contract) => env
+                .storage()
+                .instance()
+                .set(&DataKey::AchievementContract, &contract),
+            None => env.storage().instance().remove(&DataKey::AchievementContract),
+        }
+    }
+
+    pub fn get_achievement_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::AchievementContract)
+    }
+
+    /// Admin: set how many wrong answers a player may submit per puzzle.
+    /// Zero means unlimited.
+    pub fn set_max_attempts(env: Env, admin: Address, max_attempts: u32) {
+        Self::assert_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxAttempts, &max_attempts);
+    }
+
+    pub fn get_max_attempts(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxAttempts)
+            .unwrap_or(0)
+    }
+
+    /// Number of failed attempts the player has made on the current version
+    /// of the puzzle
+    pub fn attempts_of(env: Env, player: Address, puzzle_id: u32) -> u32 {
+        let version = Self::get_puzzle_version(env.clone(), puzzle_id);
+        read_persistent(&env, &DataKey::Attempts(player, puzzle_id, version)).unwrap_or(0)
+    }
+
+    /// Admin: set solution hashes for many puzzles at once.
+    /// Existing puzzles keep their window, difficulty and rewards; new puzzles
+    /// are open from now on with difficulty 1 and no reward points until
+    /// configured with `set_puzzle`.
+    pub fn set_puzzles(
+        env: Env,
+        admin: Address,
+        puzzle_ids: Vec<u32>,
+        solution_hashes: Vec<BytesN<32>>,
+    ) {
+        Self::assert_admin(&env, &admin);
+
+        if puzzle_ids.len() != solution_hashes.len() {
+            panic!("length mismatch");
+        }
+
+        let now = env.ledger().timestamp();
+        for (puzzle_id, solution_hash) in puzzle_ids.iter().zip(solution_hashes.iter()) {
+            let meta = match Self::get_puzzle(env.clone(), puzzle_id) {
+                Some(mut meta) => {
+                    meta.solution_hash = solution_hash;
+                    meta
+                }
+                None => PuzzleMeta {
+                    id: puzzle_id,
+                    solution_hash,
+                    start_ts: now,
+                    end_ts: 0,
+                    difficulty: 1,
+                    reward_points: 0,
+                },
+            };
+            write_persistent(&env, &DataKey::Puzzle(puzzle_id), &meta);
+        }
+    }
+
+    /// Admin: when set, solutions may only be submitted through
+    /// `commit_solution` / `reveal_solution`.
+    pub fn set_commit_required(env: Env, admin: Address, required: bool) {
+        Self::assert_admin(&env, &admin);
         env.storage()
             .instance()
-            .set(&DataKey::Puzzle(puzzle_id), &meta);
+            .set(&DataKey::CommitRequired, &required);
     }
 
-    /// Verify solution preimage by hashing on-chain and credit rewards once
+    pub fn is_commit_required(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::CommitRequired)
+            .unwrap_or(false)
+    }
+
+    /// Verify solution preimage by hashing on-chain and credit rewards once.
+    /// Returns false for a puzzle that has not been set.
     pub fn verify_solution(
         env: Env,
         player: Address,
         puzzle_id: u32,
         solution_preimage: Bytes,
-    ) -> bool {
+    ) -> Result<bool, Error> {
+        player.require_auth();
+        if Self::is_commit_required(env.clone()) {
+            panic!("commit required");
+        }
+        Self::check_solution(env, player, puzzle_id, solution_preimage)
+    }
+
+    /// Commit to a solution without revealing it:
+    /// `commitment = sha256(player.to_xdr() || solution_preimage)`.
+    /// Committing again replaces the previous commitment.
+    pub fn commit_solution(env: Env, player: Address, puzzle_id: u32, commitment: BytesN<32>) {
+        player.require_auth();
+        let ledger = env.ledger().sequence();
+        write_persistent(
+            &env,
+            &DataKey::Commitment(player, puzzle_id),
+            &(commitment, ledger),
+        );
+    }
+
+    /// Reveal a previously committed solution. The reveal must land at least
+    /// `MIN_REVEAL_DELAY` ledgers after the commit and match the commitment;
+    /// the commitment is consumed whether or not the solution is correct.
+    pub fn reveal_solution(
+        env: Env,
+        player: Address,
+        puzzle_id: u32,
+        solution_preimage: Bytes,
+    ) -> Result<bool, Error> {
         player.require_auth();
 
+        let key = DataKey::Commitment(player.clone(), puzzle_id);
+        let (commitment, committed_at): (BytesN<32>, u32) =
+            read_persistent(&env, &key).expect("no commitment");
+        if env.ledger().sequence() < committed_at.saturating_add(MIN_REVEAL_DELAY) {
+            panic!("reveal too early");
+        }
+        if commitment_of(&env, &player, &solution_preimage) != commitment {
+            panic!("commitment mismatch");
+        }
+        env.storage().persistent().remove(&key);
+
+        Self::check_solution(env, player, puzzle_id, solution_preimage)
+    }
+
+    pub fn get_commitment(env: Env, player: Address, puzzle_id: u32) -> Option<BytesN<32>> {
+        read_persistent::<(BytesN<32>, u32)>(&env, &DataKey::Commitment(player, puzzle_id))
+            .map(|(commitment, _)| commitment)
+    }
+
+    fn check_solution(
+        env: Env,
+        player: Address,
+        puzzle_id: u32,
+        solution_preimage: Bytes,
+    ) -> Result<bool, Error> {
         // Prevent double-claim
         if Self::is_completed(env.clone(), player.clone(), puzzle_id) {
             panic!("puzzle already completed");
         }
 
         // Load puzzle and enforce time window
-        let meta: PuzzleMeta = env
-            .storage()
-            .instance()
-            .get(&DataKey::Puzzle(puzzle_id))
-            .expect("puzzle");
+        let meta = match Self::get_puzzle(env.clone(), puzzle_id) {
+            Some(meta) => meta,
+            None => return Ok(false),
+        };
+        if !Self::in_window(&env, &meta) {
+            return Err(Error::PuzzleNotOpen);
+        }
         let now = env.ledger().timestamp();
-        if now < meta.start_ts || now > meta.end_ts {
-            panic!("puzzle not active");
+        let version = Self::get_puzzle_version(env.clone(), puzzle_id);
+
+        // Lock out players who have used up their wrong answers
+        let failed = Self::attempts_of(env.clone(), player.clone(), puzzle_id);
+        let max_attempts = Self::get_max_attempts(env.clone());
+        if max_attempts > 0 && failed >= max_attempts {
+            return Err(Error::TooManyAttempts);
         }
 
+        // Rate-limit attempts per (player, puzzle)
+        let attempt_key = DataKey::LastAttempt(player.clone(), puzzle_id);
+        let cooldown = Self::get_attempt_cooldown(env.clone());
+        if cooldown > 0 {
+            if let Some(last) = read_persistent::<u64>(&env, &attempt_key) {
+                if now < last.saturating_add(cooldown) {
+                    return Err(Error::TooFrequent);
+                }
+            }
+        }
+        write_persistent(&env, &attempt_key, &now);
+
         // Hash the provided solution and compare
         let computed: BytesN<32> = env.crypto().sha256(&solution_preimage).into();
         if computed != meta.solution_hash {
-            return false;
+            write_persistent(
+                &env,
+                &DataKey::Attempts(player, puzzle_id, version),
+                &(failed + 1),
+            );
+            return Ok(false);
         }
 
         // Mark completed
-        env.storage()
-            .instance()
-            .set(&DataKey::Completed(player.clone(), puzzle_id), &true);
+        write_persistent(
+            &env,
+            &DataKey::Completed(player.clone(), puzzle_id, version),
+            &true,
+        );
 
         // Difficulty-based reward scaling: scale reward_points by difficulty factor (>=1)
         let scaled = meta.reward_points * (meta.difficulty as i128).max(1);
@@ -152,20 +410,29 @@ impl PuzzleVerification {
             .instance()
             .set(&DataKey::Rewards(player.clone()), &rewards);
 
+        // Count distinct solvers
+        let solves = Self::solve_count(env.clone(), puzzle_id) + 1;
+        write_persistent(&env, &DataKey::SolveCount(puzzle_id), &solves);
+
         // Emit completion event
         env.events().publish(
             (Symbol::new(&env, "puzzle"), Symbol::new(&env, "completed")),
-            (player, puzzle_id, scaled),
+            (player.clone(), puzzle_id, scaled),
         );
+        env.events().publish((SOLVED, player.clone(), puzzle_id), solves);
 
-        true
+        // Let the player mint their achievement
+        if let Some(achievements) = Self::get_achievement_contract(env.clone()) {
+            AchievementClient::new(&env, &achievements).mark_puzzle_completed(&player, &puzzle_id);
+        }
+
+        Ok(true)
     }
 
+    /// Whether the player has solved the current version of the puzzle
     pub fn is_completed(env: Env, player: Address, puzzle_id: u32) -> bool {
-        env.storage()
-            .instance()
-            .get(&DataKey::Completed(player, puzzle_id))
-            .unwrap_or(false)
+        let version = Self::get_puzzle_version(env.clone(), puzzle_id);
+        read_persistent(&env, &DataKey::Completed(player, puzzle_id, version)).unwrap_or(false)
     }
 
     pub fn rewards_of(env: Env, player: Address) -> i128 {
@@ -176,10 +443,51 @@ impl PuzzleVerification {
     }
 
     pub fn get_puzzle(env: Env, puzzle_id: u32) -> Option<PuzzleMeta> {
-        env.storage().instance().get(&DataKey::Puzzle(puzzle_id))
+        read_persistent(&env, &DataKey::Puzzle(puzzle_id))
+    }
+
+    /// Whether the puzzle exists and the ledger time is within its window
+    pub fn is_puzzle_open(env: Env, puzzle_id: u32) -> bool {
+        match Self::get_puzzle(env.clone(), puzzle_id) {
+            Some(meta) => Self::in_window(&env, &meta),
+            None => false,
+        }
+    }
+
+    /// Number of players who have solved the current version of the puzzle
+    pub fn solve_count(env: Env, puzzle_id: u32) -> u32 {
+        read_persistent(&env, &DataKey::SolveCount(puzzle_id)).unwrap_or(0)
     }
 }
 
+// Puzzles, completion flags and attempt records each get their own
+// persistent entry and TTL rather than growing the shared instance entry.
+const LEDGER_THRESHOLD: u32 = 518_400; // ~30 days @ 5s/ledger
+const LEDGER_BUMP: u32 = 1_036_800; // ~60 days @ 5s/ledger
+
+fn commitment_of(env: &Env, player: &Address, solution_preimage: &Bytes) -> BytesN<32> {
+    let mut data = player.clone().to_xdr(env);
+    data.append(solution_preimage);
+    env.crypto().sha256(&data).into()
+}
+
+fn write_persistent<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().persistent().set(key, value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+fn read_persistent<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, LEDGER_THRESHOLD, LEDGER_BUMP);
+    }
+    value
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -205,7 +513,7 @@ mod test {
         let preimage = Bytes::from_array(&env, &[7u8; 5]);
         let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
         let now = env.ledger().timestamp();
-        client.set_puzzle(&1, &hash, &(now - 1), &(now + 1000), &2, &50);
+        client.set_puzzle(&admin, &1, &hash, &(now - 1), &(now + 1000), &2, &50);
 
         // Wrong preimage
         let wrong = Bytes::from_array(&env, &[8u8; 5]);
@@ -222,7 +530,6 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "puzzle not active")]
     fn test_expiration_enforced() {
         let env = Env::default();
         let contract_id = env.register_contract(None, PuzzleVerification);
@@ -239,9 +546,597 @@ mod test {
         let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
         let now = env.ledger().timestamp();
         // Expired window
-        client.set_puzzle(&42, &hash, &(now - 100), &(now - 50), &1, &10);
+        client.set_puzzle(&admin, &42, &hash, &(now - 100), &(now - 50), &1, &10);
+
+        // Attempt verify should be rejected
+        assert_eq!(
+            client.try_verify_solution(&player, &42, &preimage),
+            Err(Ok(Error::PuzzleNotOpen))
+        );
+    }
+
+    #[test]
+    fn test_set_puzzles_bulk() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        env.ledger().set_timestamp(1_000);
+
+        let mut ids = Vec::new(&env);
+        let mut hashes = Vec::new(&env);
+        let mut preimages = Vec::new(&env);
+        for i in 0..3u32 {
+            let preimage = Bytes::from_array(&env, &[i as u8 + 1; 4]);
+            ids.push_back(10 + i);
+            hashes.push_back(env.crypto().sha256(&preimage).into());
+            preimages.push_back(preimage);
+        }
+
+        client.set_puzzles(&admin, &ids, &hashes);
+
+        for i in 0..3u32 {
+            let id = ids.get(i).unwrap();
+            assert!(client.get_puzzle(&id).is_some());
+            assert!(client.verify_solution(&player, &id, &preimages.get(i).unwrap()));
+            assert!(client.is_completed(&player, &id));
+        }
+    }
+
+    #[test]
+    fn test_verify_unknown_puzzle_returns_false() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+        client.set_attempt_cooldown(&admin, &60);
+
+        env.ledger().set_timestamp(1_000);
+
+        let preimage = Bytes::from_array(&env, &[3u8; 4]);
+        assert!(!client.verify_solution(&player, &99, &preimage));
+        assert!(!client.is_completed(&player, &99));
+
+        // The miss does not start a cooldown once the puzzle is set
+        client.set_puzzle(&admin, &99, &env.crypto().sha256(&preimage).into(), &900, &10_000, &1, &10);
+        assert!(client.verify_solution(&player, &99, &preimage));
+    }
+
+    #[test]
+    fn test_attempt_cooldown() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+        client.set_attempt_cooldown(&admin, &60);
+
+        env.ledger().set_timestamp(1_000);
+
+        let preimage = Bytes::from_array(&env, &[4u8; 4]);
+        let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+        client.set_puzzle(&admin, &3, &hash, &900, &10_000, &1, &10);
+
+        let wrong = Bytes::from_array(&env, &[5u8; 4]);
+        assert!(!client.verify_solution(&player, &3, &wrong));
+
+        // Second attempt within the cooldown is rejected
+        env.ledger().set_timestamp(1_030);
+        assert_eq!(
+            client.try_verify_solution(&player, &3, &preimage),
+            Err(Ok(Error::TooFrequent))
+        );
+        assert!(!client.is_completed(&player, &3));
+
+        // Allowed once the cooldown has elapsed
+        env.ledger().set_timestamp(1_060);
+        assert!(client.verify_solution(&player, &3, &preimage));
+        assert!(client.is_completed(&player, &3));
+    }
+
+    #[test]
+    #[should_panic(expected = "length mismatch")]
+    fn test_set_puzzles_length_mismatch() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        let mut ids = Vec::new(&env);
+        ids.push_back(1u32);
+        ids.push_back(2u32);
+        let mut hashes: Vec<BytesN<32>> = Vec::new(&env);
+        hashes.push_back(BytesN::from_array(&env, &[0u8; 32]));
+
+        client.set_puzzles(&admin, &ids, &hashes);
+    }
+
+    #[test]
+    fn test_admin_can_overwrite_puzzle() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        env.ledger().set_timestamp(1_000);
+
+        let old = Bytes::from_array(&env, &[1u8; 4]);
+        let new = Bytes::from_array(&env, &[2u8; 4]);
+        client.set_puzzle(&admin, &5, &env.crypto().sha256(&old).into(), &900, &10_000, &1, &10);
+        client.set_puzzle(&admin, &5, &env.crypto().sha256(&new).into(), &900, &10_000, &1, &10);
+
+        assert!(!client.verify_solution(&player, &5, &old));
+        assert!(client.verify_solution(&player, &5, &new));
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized")]
+    fn test_non_admin_cannot_set_puzzle() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let attacker = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        let hash: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &[0u8; 4])).into();
+        client.set_puzzle(&attacker, &1, &hash, &0, &10_000, &1, &10);
+    }
+
+    #[test]
+    fn test_set_puzzle_requires_admin_signature() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        // Naming the admin without their signature is not enough
+        env.set_auths(&[]);
+        let hash: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &[0u8; 4])).into();
+        assert!(client.try_set_puzzle(&admin, &1, &hash, &0, &10_000, &1, &10).is_err());
+        assert!(client.get_puzzle(&1).is_none());
+    }
+
+    #[test]
+    fn test_many_puzzles_persist_independently() {
+        use soroban_sdk::testutils::storage::Persistent as _;
+
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        env.ledger().set_timestamp(1_000);
+
+        let mut preimages = Vec::new(&env);
+        for id in 0..20u32 {
+            let preimage = Bytes::from_array(&env, &[id as u8; 4]);
+            let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+            client.set_puzzle(&admin, &id, &hash, &900, &10_000, &1, &1);
+            preimages.push_back(preimage);
+        }
+
+        // Each player solves a different subset
+        for id in 0..20u32 {
+            let player = if id % 2 == 0 { &player1 } else { &player2 };
+            assert!(client.verify_solution(player, &id, &preimages.get(id).unwrap()));
+        }
+
+        for id in 0..20u32 {
+            assert_eq!(client.is_completed(&player1, &id), id % 2 == 0);
+            assert_eq!(client.is_completed(&player2, &id), id % 2 == 1);
+        }
+
+        // Nothing lands in instance storage; every entry carries its own TTL
+        env.as_contract(&contract_id, || {
+            assert!(!env.storage().instance().has(&DataKey::Puzzle(3)));
+            assert!(!env.storage().instance().has(&DataKey::Completed(player2.clone(), 3, 1)));
+            assert_eq!(env.storage().persistent().get_ttl(&DataKey::Puzzle(3)), LEDGER_BUMP);
+            assert_eq!(
+                env.storage().persistent().get_ttl(&DataKey::Completed(player2.clone(), 3, 1)),
+                LEDGER_BUMP
+            );
+        });
+
+        // Keep the contract alive and jump past the default entry TTL
+        env.as_contract(&contract_id, || {
+            env.storage().instance().extend_ttl(LEDGER_BUMP, LEDGER_BUMP);
+        });
+        env.ledger().with_mut(|li| li.sequence_number += LEDGER_BUMP - 1);
+
+        assert!(client.is_completed(&player2, &19));
+        assert!(client.get_puzzle(&19).is_some());
+    }
+
+    #[test]
+    fn test_commit_reveal_flow() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+        client.set_commit_required(&admin, &true);
+        assert!(client.try_set_commit_required(&player, &false).is_err());
+
+        env.ledger().set_timestamp(1_000);
+        let preimage = Bytes::from_array(&env, &[4u8; 4]);
+        let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+        client.set_puzzle(&admin, &1, &hash, &900, &10_000, &2, &10);
+
+        // Direct submissions are refused while commit-reveal is enforced
+        assert!(client.try_verify_solution(&player, &1, &preimage).is_err());
+
+        let commitment = commitment_of(&env, &player, &preimage);
+        client.commit_solution(&player, &1, &commitment);
+        assert_eq!(client.get_commitment(&player, &1), Some(commitment));
+
+        // Same ledger: too early
+        assert!(client.try_reveal_solution(&player, &1, &preimage).is_err());
+
+        env.ledger().with_mut(|li| li.sequence_number += MIN_REVEAL_DELAY);
+        assert!(client.reveal_solution(&player, &1, &preimage));
+        assert!(client.is_completed(&player, &1));
+        assert_eq!(client.rewards_of(&player), 20);
+        assert_eq!(client.get_commitment(&player, &1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "commitment mismatch")]
+    fn test_reveal_mismatched_commitment_panics() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        let copycat = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        env.ledger().set_timestamp(1_000);
+        let preimage = Bytes::from_array(&env, &[4u8; 4]);
+        let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+        client.set_puzzle(&admin, &1, &hash, &900, &10_000, &1, &10);
+
+        // A commitment copied from another player does not match for the copier
+        let commitment = commitment_of(&env, &player, &preimage);
+        client.commit_solution(&copycat, &1, &commitment);
+        env.ledger().with_mut(|li| li.sequence_number += MIN_REVEAL_DELAY);
+        client.reveal_solution(&copycat, &1, &preimage);
+    }
+
+    #[test]
+    fn test_solved_event_and_solve_count() {
+        use soroban_sdk::testutils::Events as _;
+
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        env.ledger().set_timestamp(1_000);
+
+        let preimage = Bytes::from_array(&env, &[6u8; 4]);
+        let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+        client.set_puzzle(&admin, &8, &hash, &900, &10_000, &1, &10);
+        assert_eq!(client.solve_count(&8), 0);
+
+        // A failed attempt neither counts nor emits
+        let wrong = Bytes::from_array(&env, &[7u8; 4]);
+        assert!(!client.verify_solution(&player1, &8, &wrong));
+        assert_eq!(client.solve_count(&8), 0);
+        assert!(env.events().all().is_empty());
+
+        assert!(client.verify_solution(&player1, &8, &preimage));
+        assert_eq!(client.solve_count(&8), 1);
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(), symbol_short!("SOLVED"));
+        assert_eq!(Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap(), player1);
+        assert_eq!(u32::try_from_val(&env, &topics.get(2).unwrap()).unwrap(), 8);
+        assert_eq!(u32::try_from_val(&env, &data).unwrap(), 1);
+
+        // A repeat claim is rejected and leaves the count alone
+        assert!(client.try_verify_solution(&player1, &8, &preimage).is_err());
+        assert_eq!(client.solve_count(&8), 1);
+
+        assert!(client.verify_solution(&player2, &8, &preimage));
+        assert_eq!(client.solve_count(&8), 2);
+    }
+
+    #[test]
+    fn test_rotate_solution_invalidates_completions() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        env.ledger().set_timestamp(1_000);
+
+        let v1 = Bytes::from_array(&env, &[1u8; 4]);
+        let v2 = Bytes::from_array(&env, &[2u8; 4]);
+        client.set_puzzle(&admin, &4, &env.crypto().sha256(&v1).into(), &900, &10_000, &1, &10);
+        assert_eq!(client.get_puzzle_version(&4), 1);
+
+        assert!(client.verify_solution(&player, &4, &v1));
+        assert!(client.is_completed(&player, &4));
+        assert_eq!(client.solve_count(&4), 1);
+
+        client.rotate_solution(&admin, &4, &env.crypto().sha256(&v2).into());
+        assert_eq!(client.get_puzzle_version(&4), 2);
+        assert!(!client.is_completed(&player, &4));
+        assert_eq!(client.solve_count(&4), 0);
+
+        // The leaked answer no longer works; the new one can be solved again
+        assert!(!client.verify_solution(&player, &4, &v1));
+        assert!(client.verify_solution(&player, &4, &v2));
+        assert!(client.is_completed(&player, &4));
+        assert_eq!(client.rewards_of(&player), 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized")]
+    fn test_non_admin_cannot_rotate_solution() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let attacker = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        let hash: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &[0u8; 4])).into();
+        client.set_puzzle(&admin, &1, &hash, &0, &10_000, &1, &10);
+        client.rotate_solution(&attacker, &1, &hash);
+    }
+
+    #[test]
+    fn test_rotation_resets_attempt_budget() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+        client.set_max_attempts(&admin, &1);
+
+        env.ledger().set_timestamp(1_000);
+
+        let v1 = Bytes::from_array(&env, &[1u8; 4]);
+        let v2 = Bytes::from_array(&env, &[2u8; 4]);
+        client.set_puzzle(&admin, &4, &env.crypto().sha256(&v1).into(), &900, &10_000, &1, &10);
+
+        // Locked out on v1
+        assert!(!client.verify_solution(&player, &4, &v2));
+        assert!(client.try_verify_solution(&player, &4, &v1).is_err());
+
+        // v2 comes with a fresh budget
+        client.rotate_solution(&admin, &4, &env.crypto().sha256(&v2).into());
+        assert_eq!(client.attempts_of(&player, &4), 0);
+        assert!(client.verify_solution(&player, &4, &v2));
+        assert!(client.is_completed(&player, &4));
+    }
+
+    #[test]
+    fn test_puzzle_window() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let early = Address::generate(&env);
+        let on_time = Address::generate(&env);
+        let late = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        let preimage = Bytes::from_array(&env, &[5u8; 4]);
+        client.set_puzzle(&admin, &6, &env.crypto().sha256(&preimage).into(), &2_000, &3_000, &1, &10);
+        assert!(!client.is_puzzle_open(&7));
+
+        // Before opening
+        env.ledger().set_timestamp(1_999);
+        assert!(!client.is_puzzle_open(&6));
+        assert_eq!(
+            client.try_verify_solution(&early, &6, &preimage),
+            Err(Ok(Error::PuzzleNotOpen))
+        );
+
+        // Inside the window, bounds included
+        env.ledger().set_timestamp(2_000);
+        assert!(client.is_puzzle_open(&6));
+        env.ledger().set_timestamp(3_000);
+        assert!(client.is_puzzle_open(&6));
+        assert!(client.verify_solution(&on_time, &6, &preimage));
+
+        // After closing
+        env.ledger().set_timestamp(3_001);
+        assert!(!client.is_puzzle_open(&6));
+        assert_eq!(
+            client.try_verify_solution(&late, &6, &preimage),
+            Err(Ok(Error::PuzzleNotOpen))
+        );
+        assert!(!client.is_completed(&early, &6));
+        assert!(!client.is_completed(&late, &6));
+
+        // Zero leaves an end of the window unbounded
+        client.set_puzzle(&admin, &8, &env.crypto().sha256(&preimage).into(), &0, &3_000, &1, &10);
+        client.set_puzzle(&admin, &9, &env.crypto().sha256(&preimage).into(), &2_000, &0, &1, &10);
+        client.set_puzzle(&admin, &10, &env.crypto().sha256(&preimage).into(), &0, &0, &1, &10);
+        env.ledger().set_timestamp(0);
+        assert!(client.is_puzzle_open(&8));
+        assert!(!client.is_puzzle_open(&9));
+        assert!(client.is_puzzle_open(&10));
+        env.ledger().set_timestamp(u64::MAX);
+        assert!(!client.is_puzzle_open(&8));
+        assert!(client.is_puzzle_open(&9));
+        assert!(client.is_puzzle_open(&10));
+    }
+
+    #[test]
+    fn test_bulk_puzzles_open_indefinitely() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        env.ledger().set_timestamp(1_000);
+        let mut ids = Vec::new(&env);
+        ids.push_back(1u32);
+        let mut hashes: Vec<BytesN<32>> = Vec::new(&env);
+        hashes.push_back(BytesN::from_array(&env, &[0u8; 32]));
+        client.set_puzzles(&admin, &ids, &hashes);
+
+        env.ledger().set_timestamp(u64::MAX);
+        assert!(client.is_puzzle_open(&1));
+    }
+
+    #[test]
+    fn test_attempt_limit_locks_out() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        let other = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+        client.set_max_attempts(&admin, &3);
+
+        env.ledger().set_timestamp(1_000);
+
+        let preimage = Bytes::from_array(&env, &[4u8; 4]);
+        client.set_puzzle(&admin, &2, &env.crypto().sha256(&preimage).into(), &900, &10_000, &1, &10);
+
+        for i in 0..3u8 {
+            let guess = Bytes::from_array(&env, &[i; 4]);
+            assert!(!client.verify_solution(&player, &2, &guess));
+        }
+        assert_eq!(client.attempts_of(&player, &2), 3);
+
+        // Even the right answer is refused once the budget is spent
+        assert_eq!(
+            client.try_verify_solution(&player, &2, &preimage),
+            Err(Ok(Error::TooManyAttempts))
+        );
+        assert!(!client.is_completed(&player, &2));
+
+        // Other players keep their own budget
+        assert!(client.verify_solution(&other, &2, &preimage));
+        assert_eq!(client.attempts_of(&other, &2), 0);
+    }
+
+    #[test]
+    fn test_correct_answer_within_budget() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+        client.set_max_attempts(&admin, &2);
+
+        env.ledger().set_timestamp(1_000);
+
+        let preimage = Bytes::from_array(&env, &[4u8; 4]);
+        client.set_puzzle(&admin, &2, &env.crypto().sha256(&preimage).into(), &900, &10_000, &1, &10);
+
+        let wrong = Bytes::from_array(&env, &[1u8; 4]);
+        assert!(!client.verify_solution(&player, &2, &wrong));
+        assert!(client.verify_solution(&player, &2, &preimage));
+        assert_eq!(client.attempts_of(&player, &2), 1);
+        assert!(client.is_completed(&player, &2));
+    }
+
+    #[test]
+    fn test_solve_grants_achievement() {
+        use achievement_nft::{AchievementNFT, AchievementNFTClient};
+        use soroban_sdk::String;
+
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+        let nft_id = env.register_contract(None, AchievementNFT);
+        let nft = AchievementNFTClient::new(&env, &nft_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+        // Verification is the NFT contract's verifier so it can mark solves
+        let nft_admin = Address::generate(&env);
+        nft.initialize(&nft_admin, &String::from_str(&env, "Quest Achievements"), &String::from_str(&env, "QACH"));
+        nft.set_verifier(&nft_admin, &contract_id);
+        client.set_achievement_contract(&admin, &Some(nft_id.clone()));
+        assert_eq!(client.get_achievement_contract(), Some(nft_id));
+
+        env.ledger().set_timestamp(1_000);
+
+        let preimage = Bytes::from_array(&env, &[8u8; 4]);
+        client.set_puzzle(&admin, &12, &env.crypto().sha256(&preimage).into(), &900, &10_000, &1, &10);
+
+        // Nothing to mint before solving
+        let metadata = String::from_str(&env, "Puzzle 12");
+        assert!(nft.try_mint(&player, &12, &metadata).is_err());
+
+        assert!(client.verify_solution(&player, &12, &preimage));
+        let token_id = nft.mint(&player, &12, &metadata);
+        assert_eq!(nft.owner_of(&token_id), player);
 
-        // Attempt verify should panic
-        let _ = client.verify_solution(&player, &42, &preimage);
+        // Unlinking leaves verification working on its own
+        client.set_achievement_contract(&admin, &None);
+        let other = Address::generate(&env);
+        assert!(client.verify_solution(&other, &12, &preimage));
+        assert!(nft.try_mint(&other, &12, &metadata).is_err());
     }
 }
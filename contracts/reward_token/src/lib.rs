@@ -1,17 +1,32 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env,
+    String, Vec,
+};
 
 #[contracttype]
 pub enum DataKey {
     Balance(Address),
     TotalSupply,
     Admin,
-    Allowance(Address, Address), // (owner, spender)
+    Allowance(Address, Address), // (owner, spender) -> AllowanceValue
     AuthorizedMinters(Address),
     Name,
     Symbol,
     Decimals,
+    MaxSupply,                            // i128, 0 = unlimited
+    Paused,                               // bool
+    Frozen(Address),                      // bool
+    AirdropRoot,                          // BytesN<32> merkle root of (address, amount) leaves
+    AirdropClaimed(BytesN<32>, Address), // (root, claimer)
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32, // Last ledger the allowance can be used in, 0 = never expires
 }
 
 #[contracttype]
@@ -25,19 +40,38 @@ pub enum RewardType {
 #[contract]
 pub struct RewardToken;
 
+// Balances, allowances, minter flags and airdrop claims live in persistent
+// storage so each holder's entry has its own TTL instead of growing the
+// instance entry.
+const LEDGER_THRESHOLD: u32 = 518_400; // ~30 days @ 5s/ledger
+const LEDGER_BUMP: u32 = 1_036_800; // ~60 days @ 5s/ledger
+
 #[contractimpl]
 impl RewardToken {
     /// Initialize the token contract with metadata
-    pub fn initialize(env: Env, admin: Address, name: String, symbol: String, decimals: u32) {
+    ///
+    /// `max_supply` caps the current total supply; 0 means unlimited.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        name: String,
+        symbol: String,
+        decimals: u32,
+        max_supply: i128,
+    ) {
         if env.storage().instance().has(&DataKey::Admin) {
             panic!("Already initialized");
         }
+        if max_supply < 0 {
+            panic!("Max supply cannot be negative");
+        }
 
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::TotalSupply, &0i128);
         env.storage().instance().set(&DataKey::Name, &name);
         env.storage().instance().set(&DataKey::Symbol, &symbol);
         env.storage().instance().set(&DataKey::Decimals, &decimals);
+        env.storage().instance().set(&DataKey::MaxSupply, &max_supply);
     }
 
     /// Get token name
@@ -64,14 +98,32 @@ impl RewardToken {
             .unwrap_or(6)
     }
 
+    /// Update token name and symbol (admin only). Decimals stay fixed.
+    pub fn set_metadata(env: Env, admin: Address, name: String, symbol: String) {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("Unauthorized");
+        }
+
+        env.storage().instance().set(&DataKey::Name, &name);
+        env.storage().instance().set(&DataKey::Symbol, &symbol);
+
+        env.events()
+            .publish((symbol_short!("metadata"), admin), (name, symbol));
+    }
+
     /// Authorize a minter address (admin only)
     pub fn authorize_minter(env: Env, minter: Address) {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
 
+        let key = DataKey::AuthorizedMinters(minter);
+        env.storage().persistent().set(&key, &true);
         env.storage()
-            .instance()
-            .set(&DataKey::AuthorizedMinters(minter), &true);
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
     }
 
     /// Revoke minter authorization (admin only)
@@ -80,7 +132,7 @@ impl RewardToken {
         admin.require_auth();
 
         env.storage()
-            .instance()
+            .persistent()
             .remove(&DataKey::AuthorizedMinters(minter));
     }
 
@@ -94,36 +146,30 @@ impl RewardToken {
         }
 
         env.storage()
-            .instance()
+            .persistent()
             .get(&DataKey::AuthorizedMinters(minter))
             .unwrap_or(false)
     }
 
     /// Mint new tokens (admin or authorized minter only)
-    pub fn mint(env: Env, to: Address, amount: i128) {
+    pub fn mint(env: Env, minter: Address, to: Address, amount: i128) {
+        minter.require_auth();
+
         if amount <= 0 {
             panic!("Amount must be positive");
         }
 
-        // Check if caller is authorized
-        if !Self::is_authorized_minter(env.clone(), to.clone()) {
-            let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-            admin.require_auth();
+        if !Self::is_authorized_minter(env.clone(), minter.clone()) {
+            panic!("Unauthorized minter");
         }
 
         let balance = Self::balance(env.clone(), to.clone());
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance(to), &(balance + amount));
+        write_balance(&env, &to, balance + amount);
 
-        let total_supply: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0);
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalSupply, &(total_supply + amount));
+        increase_supply(&env, amount);
+
+        env.events()
+            .publish((symbol_short!("mint"), minter, to), amount);
     }
 
     /// Distribute rewards to multiple addresses
@@ -141,25 +187,147 @@ impl RewardToken {
 
             if amount > 0 {
                 let balance = Self::balance(env.clone(), recipient.clone());
-                env.storage()
-                    .instance()
-                    .set(&DataKey::Balance(recipient), &(balance + amount));
-
-                let total_supply: i128 = env
-                    .storage()
-                    .instance()
-                    .get(&DataKey::TotalSupply)
-                    .unwrap_or(0);
-                env.storage()
-                    .instance()
-                    .set(&DataKey::TotalSupply, &(total_supply + amount));
+                write_balance(&env, &recipient, balance + amount);
+
+                increase_supply(&env, amount);
+
+                env.events()
+                    .publish((symbol_short!("mint"), admin.clone(), recipient), amount);
             }
         }
     }
 
+    /// Pause or resume transfers, approvals and spending (admin only)
+    ///
+    /// Minting and admin functions keep working while paused so the admin
+    /// can recover from an incident.
+    pub fn set_paused(env: Env, admin: Address, paused: bool) {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("Unauthorized");
+        }
+
+        env.storage().instance().set(&DataKey::Paused, &paused);
+
+        env.events()
+            .publish((symbol_short!("paused"), admin), paused);
+    }
+
+    /// Freeze or unfreeze an account, blocking transfers to and from it (admin only)
+    pub fn set_frozen(env: Env, admin: Address, account: Address, frozen: bool) {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("Unauthorized");
+        }
+
+        let key = DataKey::Frozen(account.clone());
+        if frozen {
+            env.storage().persistent().set(&key, &true);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        env.events()
+            .publish((symbol_short!("frozen"), account), frozen);
+    }
+
+    /// Check whether transfers are paused
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// Check whether an account is frozen
+    pub fn is_frozen(env: Env, account: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Frozen(account))
+            .unwrap_or(false)
+    }
+
+    /// Set the merkle root for an airdrop (admin only)
+    ///
+    /// Leaves are `sha256(address xdr || amount as 16 big-endian bytes)` and
+    /// pairs are hashed in sorted order. Claims are tracked per root, so a new
+    /// root starts a fresh airdrop.
+    pub fn set_airdrop_root(env: Env, admin: Address, root: BytesN<32>) {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("Unauthorized");
+        }
+
+        env.storage().instance().set(&DataKey::AirdropRoot, &root);
+    }
+
+    /// Claim an airdrop allocation by proving `(claimer, amount)` is in the merkle tree
+    pub fn claim_airdrop(env: Env, claimer: Address, amount: i128, proof: Vec<BytesN<32>>) {
+        claimer.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let root: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AirdropRoot)
+            .expect("No airdrop");
+
+        let claimed_key = DataKey::AirdropClaimed(root.clone(), claimer.clone());
+        if env.storage().persistent().has(&claimed_key) {
+            panic!("Already claimed");
+        }
+
+        let mut computed = airdrop_leaf(&env, &claimer, amount);
+        for sibling in proof.iter() {
+            computed = hash_pair(&env, &computed, &sibling);
+        }
+        if computed != root {
+            panic!("Invalid proof");
+        }
+
+        env.storage().persistent().set(&claimed_key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&claimed_key, LEDGER_THRESHOLD, LEDGER_BUMP);
+
+        let balance = Self::balance(env.clone(), claimer.clone());
+        write_balance(&env, &claimer, balance + amount);
+
+        increase_supply(&env, amount);
+
+        env.events()
+            .publish((symbol_short!("airdrop"), claimer), amount);
+    }
+
+    /// Check whether an address has claimed from the current airdrop
+    pub fn is_airdrop_claimed(env: Env, claimer: Address) -> bool {
+        match env.storage().instance().get::<_, BytesN<32>>(&DataKey::AirdropRoot) {
+            Some(root) => env
+                .storage()
+                .persistent()
+                .has(&DataKey::AirdropClaimed(root, claimer)),
+            None => false,
+        }
+    }
+
     /// Transfer tokens
     pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> bool {
         from.require_auth();
+        assert_not_paused(&env);
+        assert_not_frozen(&env, &from);
+        assert_not_frozen(&env, &to);
 
         if amount <= 0 {
             panic!("Amount must be positive");
@@ -172,31 +340,131 @@ impl RewardToken {
             panic!("Insufficient balance");
         }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance(from), &(from_balance - amount));
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance(to), &(to_balance + amount));
+        write_balance(&env, &from, from_balance - amount);
+        write_balance(&env, &to, to_balance + amount);
+
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), amount);
+
+        true
+    }
+
+    /// Transfer to several recipients at once, all or nothing
+    pub fn transfer_batch(env: Env, from: Address, recipients: Vec<Address>, amounts: Vec<i128>) -> bool {
+        from.require_auth();
+        assert_not_paused(&env);
+        assert_not_frozen(&env, &from);
+
+        if recipients.len() != amounts.len() {
+            panic!("Recipients and amounts length mismatch");
+        }
+
+        let mut total: i128 = 0;
+        for amount in amounts.iter() {
+            if amount <= 0 {
+                panic!("Amount must be positive");
+            }
+            total = total.checked_add(amount).expect("Amount overflow");
+        }
+
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < total {
+            panic!("Insufficient balance");
+        }
+
+        for i in 0..recipients.len() {
+            let to = recipients.get(i).unwrap();
+            let amount = amounts.get(i).unwrap();
+            assert_not_frozen(&env, &to);
+
+            // Re-read so a recipient equal to the sender stays consistent
+            let from_balance = Self::balance(env.clone(), from.clone());
+            write_balance(&env, &from, from_balance - amount);
+            let to_balance = Self::balance(env.clone(), to.clone());
+            write_balance(&env, &to, to_balance + amount);
+
+            env.events()
+                .publish((symbol_short!("transfer"), from.clone(), to), amount);
+        }
 
         true
     }
 
     /// Approve spender to spend tokens on behalf of owner
-    pub fn approve(env: Env, owner: Address, spender: Address, amount: i128) -> bool {
+    ///
+    /// With `expiration_ledger` set, the allowance can't be used after that
+    /// ledger sequence.
+    pub fn approve(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: Option<u32>,
+    ) -> bool {
         owner.require_auth();
+        assert_not_paused(&env);
 
         if amount < 0 {
             panic!("Amount cannot be negative");
         }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Allowance(owner, spender), &amount);
+        let expiration_ledger = expiration_ledger.unwrap_or(0);
+        if expiration_ledger != 0 && expiration_ledger < env.ledger().sequence() {
+            panic!("Expiration ledger is in the past");
+        }
+
+        write_allowance(&env, &owner, &spender, amount, expiration_ledger);
+
+        env.events()
+            .publish((symbol_short!("approve"), owner, spender), amount);
 
         true
     }
 
+    /// Raise the allowance by `delta` instead of overwriting it
+    pub fn increase_allowance(env: Env, owner: Address, spender: Address, delta: i128) -> i128 {
+        owner.require_auth();
+        assert_not_paused(&env);
+
+        if delta < 0 {
+            panic!("Amount cannot be negative");
+        }
+
+        let current = read_allowance(&env, &owner, &spender);
+        if is_expired(&env, &current) {
+            panic!("Allowance expired");
+        }
+        let allowance = current
+            .amount
+            .checked_add(delta)
+            .expect("Allowance overflow");
+        write_allowance(&env, &owner, &spender, allowance, current.expiration_ledger);
+
+        env.events()
+            .publish((symbol_short!("approve"), owner, spender), allowance);
+
+        allowance
+    }
+
+    /// Lower the allowance by `delta`, stopping at zero
+    pub fn decrease_allowance(env: Env, owner: Address, spender: Address, delta: i128) -> i128 {
+        owner.require_auth();
+        assert_not_paused(&env);
+
+        if delta < 0 {
+            panic!("Amount cannot be negative");
+        }
+
+        let current = read_allowance(&env, &owner, &spender);
+        let allowance = if delta >= current.amount { 0 } else { current.amount - delta };
+        write_allowance(&env, &owner, &spender, allowance, current.expiration_ledger);
+
+        env.events()
+            .publish((symbol_short!("approve"), owner, spender), allowance);
+
+        allowance
+    }
+
     /// Transfer tokens from one address to another using allowance
     pub fn transfer_from(
         env: Env,
@@ -206,12 +474,19 @@ impl RewardToken {
         amount: i128,
     ) -> bool {
         spender.require_auth();
+        assert_not_paused(&env);
+        assert_not_frozen(&env, &from);
+        assert_not_frozen(&env, &to);
 
         if amount <= 0 {
             panic!("Amount must be positive");
         }
 
-        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
+        let current = read_allowance(&env, &from, &spender);
+        if is_expired(&env, &current) {
+            panic!("Allowance expired");
+        }
+        let allowance = current.amount;
         if allowance < amount {
             panic!("Insufficient allowance");
         }
@@ -224,17 +499,14 @@ impl RewardToken {
         let to_balance = Self::balance(env.clone(), to.clone());
 
         // Update balances
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance(from.clone()), &(from_balance - amount));
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance(to), &(to_balance + amount));
+        write_balance(&env, &from, from_balance - amount);
+        write_balance(&env, &to, to_balance + amount);
 
         // Update allowance
-        env.storage()
-            .instance()
-            .set(&DataKey::Allowance(from, spender), &(allowance - amount));
+        write_allowance(&env, &from, &spender, allowance - amount, current.expiration_ledger);
+
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), amount);
 
         true
     }
@@ -247,6 +519,8 @@ impl RewardToken {
         _unlock_type: String,
     ) -> bool {
         spender.require_auth();
+        assert_not_paused(&env);
+        assert_not_frozen(&env, &spender);
 
         if amount <= 0 {
             panic!("Amount must be positive");
@@ -258,9 +532,7 @@ impl RewardToken {
         }
 
         // Deduct from balance (burn)
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance(spender), &(balance - amount));
+        write_balance(&env, &spender, balance - amount);
 
         // Reduce total supply
         let total_supply: i128 = env
@@ -272,6 +544,9 @@ impl RewardToken {
             .instance()
             .set(&DataKey::TotalSupply, &(total_supply - amount));
 
+        env.events()
+            .publish((symbol_short!("burn"), spender), amount);
+
         true
     }
 
@@ -288,9 +563,7 @@ impl RewardToken {
             panic!("Insufficient balance to burn");
         }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance(from), &(balance - amount));
+        write_balance(&env, &from, balance - amount);
 
         let total_supply: i128 = env
             .storage()
@@ -301,23 +574,40 @@ impl RewardToken {
             .instance()
             .set(&DataKey::TotalSupply, &(total_supply - amount));
 
+        env.events()
+            .publish((symbol_short!("burn"), from), amount);
+
         true
     }
 
     /// Get balance of an account
     pub fn balance(env: Env, account: Address) -> i128 {
-        env.storage()
-            .instance()
-            .get(&DataKey::Balance(account))
-            .unwrap_or(0)
+        let key = DataKey::Balance(account);
+        match env.storage().persistent().get(&key) {
+            Some(balance) => {
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+                balance
+            }
+            None => 0,
+        }
     }
 
-    /// Get allowance
+    /// Get allowance (0 once expired)
     pub fn allowance(env: Env, owner: Address, spender: Address) -> i128 {
-        env.storage()
-            .instance()
-            .get(&DataKey::Allowance(owner, spender))
-            .unwrap_or(0)
+        let allowance = read_allowance(&env, &owner, &spender);
+        if is_expired(&env, &allowance) {
+            0
+        } else {
+            allowance.amount
+        }
+    }
+
+    /// Get the stored allowance and its expiration ledger (0 = never expires)
+    pub fn allowance_with_expiry(env: Env, owner: Address, spender: Address) -> (i128, u32) {
+        let allowance = read_allowance(&env, &owner, &spender);
+        (allowance.amount, allowance.expiration_ledger)
     }
 
     /// Get total supply
@@ -328,16 +618,165 @@ impl RewardToken {
             .unwrap_or(0)
     }
 
+    /// Get the supply cap (0 = unlimited)
+    pub fn max_supply(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxSupply)
+            .unwrap_or(0)
+    }
+
     /// Get admin address
     pub fn admin(env: Env) -> Address {
         env.storage().instance().get(&DataKey::Admin).unwrap()
     }
+
+    /// Move entries written by older versions from instance to persistent storage (admin only)
+    ///
+    /// Instance storage can't be enumerated, so the caller lists the accounts
+    /// (balances and minter flags) and `(owner, spender)` allowance pairs to move.
+    /// Amounts are added to any persistent entry written since the upgrade, so
+    /// nothing is lost if an account was already used under the new layout;
+    /// a merged allowance keeps the persistent entry's expiration.
+    pub fn migrate(
+        env: Env,
+        admin: Address,
+        accounts: Vec<Address>,
+        allowances: Vec<(Address, Address)>,
+    ) -> u32 {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("Unauthorized");
+        }
+
+        let mut moved = 0u32;
+        for account in accounts.iter() {
+            let key = DataKey::Balance(account.clone());
+            if let Some(balance) = env.storage().instance().get::<_, i128>(&key) {
+                env.storage().instance().remove(&key);
+                let current = Self::balance(env.clone(), account.clone());
+                write_balance(&env, &account, current + balance);
+                moved += 1;
+            }
+
+            let key = DataKey::AuthorizedMinters(account);
+            if env.storage().instance().has(&key) {
+                env.storage().instance().remove(&key);
+                env.storage().persistent().set(&key, &true);
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+                moved += 1;
+            }
+        }
+
+        for (owner, spender) in allowances.iter() {
+            let key = DataKey::Allowance(owner.clone(), spender.clone());
+            if let Some(amount) = env.storage().instance().get::<_, i128>(&key) {
+                env.storage().instance().remove(&key);
+                let current = read_allowance(&env, &owner, &spender);
+                write_allowance(
+                    &env,
+                    &owner,
+                    &spender,
+                    current.amount + amount,
+                    current.expiration_ledger,
+                );
+                moved += 1;
+            }
+        }
+
+        moved
+    }
+}
+
+fn assert_not_paused(env: &Env) {
+    if RewardToken::is_paused(env.clone()) {
+        panic!("Contract is paused");
+    }
+}
+
+fn assert_not_frozen(env: &Env, account: &Address) {
+    if RewardToken::is_frozen(env.clone(), account.clone()) {
+        panic!("Account is frozen");
+    }
+}
+
+fn increase_supply(env: &Env, amount: i128) {
+    let total_supply: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::TotalSupply)
+        .unwrap_or(0);
+    let max_supply = RewardToken::max_supply(env.clone());
+    if max_supply > 0 && total_supply + amount > max_supply {
+        panic!("Max supply exceeded");
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::TotalSupply, &(total_supply + amount));
+}
+
+fn write_balance(env: &Env, account: &Address, amount: i128) {
+    let key = DataKey::Balance(account.clone());
+    env.storage().persistent().set(&key, &amount);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+fn read_allowance(env: &Env, owner: &Address, spender: &Address) -> AllowanceValue {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Allowance(owner.clone(), spender.clone()))
+        .unwrap_or(AllowanceValue {
+            amount: 0,
+            expiration_ledger: 0,
+        })
+}
+
+fn is_expired(env: &Env, allowance: &AllowanceValue) -> bool {
+    allowance.expiration_ledger != 0 && allowance.expiration_ledger < env.ledger().sequence()
+}
+
+fn write_allowance(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+) {
+    let key = DataKey::Allowance(owner.clone(), spender.clone());
+    let allowance = AllowanceValue {
+        amount,
+        expiration_ledger,
+    };
+    env.storage().persistent().set(&key, &allowance);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+fn airdrop_leaf(env: &Env, account: &Address, amount: i128) -> BytesN<32> {
+    let mut data: Bytes = account.clone().to_xdr(env);
+    data.extend_from_slice(&amount.to_be_bytes());
+    env.crypto().sha256(&data).into()
+}
+
+fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a.to_array() <= b.to_array() { (a, b) } else { (b, a) };
+    let mut data = Bytes::from_array(env, &first.to_array());
+    data.extend_from_slice(&second.to_array());
+    env.crypto().sha256(&data).into()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::testutils::{storage::Persistent as _, Address as _, Events, Ledger};
+    use soroban_sdk::{Symbol, TryFromVal};
 
     #[test]
     fn test_initialization() {
@@ -349,7 +788,7 @@ mod test {
         let name = String::from_str(&env, "Game Reward Token");
         let symbol = String::from_str(&env, "GRWD");
 
-        client.initialize(&admin, &name, &symbol, &6);
+        client.initialize(&admin, &name, &symbol, &6, &0);
 
         assert_eq!(client.name(), name);
         assert_eq!(client.symbol(), symbol);
@@ -371,11 +810,12 @@ mod test {
             &String::from_str(&env, "Reward"),
             &String::from_str(&env, "RWD"),
             &6,
+            &0,
         );
 
         env.mock_all_auths();
 
-        client.mint(&user, &1000);
+        client.mint(&admin, &user, &1000);
 
         assert_eq!(client.balance(&user), 1000);
         assert_eq!(client.total_supply(), 1000);
@@ -396,11 +836,12 @@ mod test {
             &String::from_str(&env, "Reward"),
             &String::from_str(&env, "RWD"),
             &6,
+            &0,
         );
 
         env.mock_all_auths();
 
-        client.mint(&user1, &1000);
+        client.mint(&admin, &user1, &1000);
         client.transfer(&user1, &user2, &300);
 
         assert_eq!(client.balance(&user1), 700);
@@ -423,12 +864,13 @@ mod test {
             &String::from_str(&env, "Reward"),
             &String::from_str(&env, "RWD"),
             &6,
+            &0,
         );
 
         env.mock_all_auths();
 
-        client.mint(&owner, &1000);
-        client.approve(&owner, &spender, &500);
+        client.mint(&admin, &owner, &1000);
+        client.approve(&owner, &spender, &500, &None);
 
         assert_eq!(client.allowance(&owner, &spender), 500);
 
@@ -453,11 +895,12 @@ mod test {
             &String::from_str(&env, "Reward"),
             &String::from_str(&env, "RWD"),
             &6,
+            &0,
         );
 
         env.mock_all_auths();
 
-        client.mint(&user, &1000);
+        client.mint(&admin, &user, &1000);
         client.burn(&user, &300);
 
         assert_eq!(client.balance(&user), 700);
@@ -478,11 +921,12 @@ mod test {
             &String::from_str(&env, "Reward"),
             &String::from_str(&env, "RWD"),
             &6,
+            &0,
         );
 
         env.mock_all_auths();
 
-        client.mint(&player, &1000);
+        client.mint(&admin, &player, &1000);
         client.spend_for_unlock(&player, &250, &String::from_str(&env, "level_unlock"));
 
         assert_eq!(client.balance(&player), 750);
@@ -505,6 +949,7 @@ mod test {
             &String::from_str(&env, "Reward"),
             &String::from_str(&env, "RWD"),
             &6,
+            &0,
         );
 
         env.mock_all_auths();
@@ -541,6 +986,7 @@ mod test {
             &String::from_str(&env, "Reward"),
             &String::from_str(&env, "RWD"),
             &6,
+            &0,
         );
 
         env.mock_all_auths();
@@ -570,11 +1016,784 @@ mod test {
             &String::from_str(&env, "Reward"),
             &String::from_str(&env, "RWD"),
             &6,
+            &0,
         );
 
         env.mock_all_auths();
 
-        client.mint(&user1, &100);
+        client.mint(&admin, &user1, &100);
         client.transfer(&user1, &user2, &200);
     }
+
+    #[test]
+    fn test_admin_can_update_metadata() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardToken);
+        let client = RewardTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Reward"),
+            &String::from_str(&env, "RWDD"),
+            &6,
+            &0,
+        );
+
+        env.mock_all_auths();
+
+        client.set_metadata(
+            &admin,
+            &String::from_str(&env, "Reward"),
+            &String::from_str(&env, "RWD"),
+        );
+
+        assert_eq!(client.name(), String::from_str(&env, "Reward"));
+        assert_eq!(client.symbol(), String::from_str(&env, "RWD"));
+        assert_eq!(client.decimals(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn test_non_admin_cannot_update_metadata() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardToken);
+        let client = RewardTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Reward"),
+            &String::from_str(&env, "RWD"),
+            &6,
+            &0,
+        );
+
+        env.mock_all_auths();
+
+        client.set_metadata(
+            &user,
+            &String::from_str(&env, "Fake"),
+            &String::from_str(&env, "FAKE"),
+        );
+    }
+
+    #[test]
+    fn test_claim_airdrop_with_merkle_proof() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardToken);
+        let client = RewardTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Reward"),
+            &String::from_str(&env, "RWD"),
+            &6,
+            &0,
+        );
+
+        env.mock_all_auths();
+
+        let leaf1 = airdrop_leaf(&env, &user1, 300);
+        let leaf2 = airdrop_leaf(&env, &user2, 700);
+        client.set_airdrop_root(&admin, &hash_pair(&env, &leaf1, &leaf2));
+
+        // Wrong amount doesn't match the leaf
+        let proof1 = Vec::from_array(&env, [leaf2.clone()]);
+        assert!(client.try_claim_airdrop(&user1, &500, &proof1).is_err());
+
+        client.claim_airdrop(&user1, &300, &proof1);
+        assert_eq!(client.balance(&user1), 300);
+        assert!(client.is_airdrop_claimed(&user1));
+        assert!(!client.is_airdrop_claimed(&user2));
+
+        // Claim flags get their own persistent entry rather than growing the instance
+        let claimed_key = DataKey::AirdropClaimed(hash_pair(&env, &leaf1, &leaf2), user1.clone());
+        env.as_contract(&contract_id, || {
+            assert!(!env.storage().instance().has(&claimed_key));
+            assert_eq!(env.storage().persistent().get_ttl(&claimed_key), LEDGER_BUMP);
+        });
+
+        let proof2 = Vec::from_array(&env, [leaf1]);
+        client.claim_airdrop(&user2, &700, &proof2);
+        assert_eq!(client.balance(&user2), 700);
+        assert_eq!(client.total_supply(), 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Already claimed")]
+    fn test_airdrop_double_claim() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardToken);
+        let client = RewardTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Reward"),
+            &String::from_str(&env, "RWD"),
+            &6,
+            &0,
+        );
+
+        env.mock_all_auths();
+
+        let leaf1 = airdrop_leaf(&env, &user1, 300);
+        let leaf2 = airdrop_leaf(&env, &user2, 700);
+        client.set_airdrop_root(&admin, &hash_pair(&env, &leaf1, &leaf2));
+
+        let proof = Vec::from_array(&env, [leaf2]);
+        client.claim_airdrop(&user1, &300, &proof);
+        client.claim_airdrop(&user1, &300, &proof);
+    }
+
+    #[test]
+    fn test_token_events() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardToken);
+        let client = RewardTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Reward"),
+            &String::from_str(&env, "RWD"),
+            &6,
+            &0,
+        );
+
+        env.mock_all_auths();
+
+        client.mint(&admin, &owner, &1000);
+        let event = env.events().all().last().unwrap();
+        assert_eq!(event.0, contract_id);
+        let topics = event.1;
+        assert_eq!(topics.len(), 3);
+        assert_eq!(Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(), symbol_short!("mint"));
+        assert_eq!(Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap(), admin);
+        assert_eq!(Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap(), owner);
+        assert_eq!(i128::try_from_val(&env, &event.2).unwrap(), 1000);
+
+        client.transfer(&owner, &recipient, &300);
+        let event = env.events().all().last().unwrap();
+        let topics = event.1;
+        assert_eq!(topics.len(), 3);
+        assert_eq!(Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(), symbol_short!("transfer"));
+        assert_eq!(Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap(), owner);
+        assert_eq!(Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap(), recipient);
+        assert_eq!(i128::try_from_val(&env, &event.2).unwrap(), 300);
+
+        client.approve(&owner, &spender, &200, &None);
+        let event = env.events().all().last().unwrap();
+        let topics = event.1;
+        assert_eq!(topics.len(), 3);
+        assert_eq!(Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(), symbol_short!("approve"));
+        assert_eq!(Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap(), owner);
+        assert_eq!(Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap(), spender);
+        assert_eq!(i128::try_from_val(&env, &event.2).unwrap(), 200);
+
+        // transfer_from is reported as a plain transfer from the owner
+        client.transfer_from(&spender, &owner, &recipient, &50);
+        let event = env.events().all().last().unwrap();
+        let topics = event.1;
+        assert_eq!(Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(), symbol_short!("transfer"));
+        assert_eq!(Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap(), owner);
+        assert_eq!(Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap(), recipient);
+        assert_eq!(i128::try_from_val(&env, &event.2).unwrap(), 50);
+
+        client.burn(&owner, &100);
+        let event = env.events().all().last().unwrap();
+        let topics = event.1;
+        assert_eq!(topics.len(), 2);
+        assert_eq!(Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(), symbol_short!("burn"));
+        assert_eq!(Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap(), owner);
+        assert_eq!(i128::try_from_val(&env, &event.2).unwrap(), 100);
+
+        // Spending for unlocks burns too
+        client.spend_for_unlock(&recipient, &25, &String::from_str(&env, "level_unlock"));
+        let event = env.events().all().last().unwrap();
+        let topics = event.1;
+        assert_eq!(Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(), symbol_short!("burn"));
+        assert_eq!(Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap(), recipient);
+        assert_eq!(i128::try_from_val(&env, &event.2).unwrap(), 25);
+    }
+
+    #[test]
+    fn test_distribute_rewards_emits_mint_events() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardToken);
+        let client = RewardTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Reward"),
+            &String::from_str(&env, "RWD"),
+            &6,
+            &0,
+        );
+
+        env.mock_all_auths();
+
+        let recipients = Vec::from_array(&env, [user1.clone(), user2.clone()]);
+        let amounts = Vec::from_array(&env, [100i128, 200i128]);
+        client.distribute_rewards(&recipients, &amounts);
+
+        let events = env.events().all();
+        assert_eq!(events.len(), 2);
+        for (i, (user, amount)) in [(user1, 100i128), (user2, 200i128)].into_iter().enumerate() {
+            let event = events.get(i as u32).unwrap();
+            let topics = event.1;
+            assert_eq!(Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(), symbol_short!("mint"));
+            assert_eq!(Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap(), admin);
+            assert_eq!(Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap(), user);
+            assert_eq!(i128::try_from_val(&env, &event.2).unwrap(), amount);
+        }
+    }
+
+    #[test]
+    fn test_authorized_minter_mints_to_any_recipient() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardToken);
+        let client = RewardTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let minter = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Reward"),
+            &String::from_str(&env, "RWD"),
+            &6,
+            &0,
+        );
+
+        env.mock_all_auths();
+
+        client.authorize_minter(&minter);
+        client.mint(&minter, &recipient, &400);
+
+        // The minter, not the admin, authorized the call
+        let auths = env.auths();
+        assert_eq!(auths.len(), 1);
+        assert_eq!(auths[0].0, minter);
+
+        assert_eq!(client.balance(&recipient), 400);
+        assert_eq!(client.balance(&minter), 0);
+        assert_eq!(client.total_supply(), 400);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized minter")]
+    fn test_unauthorized_caller_cannot_mint() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardToken);
+        let client = RewardTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let minter = Address::generate(&env);
+        let attacker = Address::generate(&env);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Reward"),
+            &String::from_str(&env, "RWD"),
+            &6,
+            &0,
+        );
+
+        env.mock_all_auths();
+
+        // Minting to an authorized address no longer lets anyone mint
+        client.authorize_minter(&minter);
+        client.mint(&attacker, &minter, &400);
+    }
+
+    #[test]
+    fn test_balances_survive_ttl_extension() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardToken);
+        let client = RewardTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Reward"),
+            &String::from_str(&env, "RWD"),
+            &6,
+            &0,
+        );
+
+        env.mock_all_auths();
+
+        client.mint(&admin, &user1, &1000);
+        client.transfer(&user1, &user2, &300);
+        client.approve(&user1, &user2, &100, &None);
+
+        env.as_contract(&contract_id, || {
+            let storage = env.storage().persistent();
+            assert_eq!(storage.get_ttl(&DataKey::Balance(user1.clone())), LEDGER_BUMP);
+            assert_eq!(storage.get_ttl(&DataKey::Balance(user2.clone())), LEDGER_BUMP);
+            assert_eq!(
+                storage.get_ttl(&DataKey::Allowance(user1.clone(), user2.clone())),
+                LEDGER_BUMP
+            );
+            assert!(!env.storage().instance().has(&DataKey::Balance(user1.clone())));
+        });
+
+        // Keep the contract itself alive, then jump well past the default
+        // entry TTL but within the bump
+        env.as_contract(&contract_id, || {
+            env.storage().instance().extend_ttl(LEDGER_BUMP, LEDGER_BUMP);
+        });
+        env.ledger().with_mut(|li| {
+            li.sequence_number += LEDGER_BUMP - 1;
+        });
+
+        assert_eq!(client.balance(&user1), 700);
+        assert_eq!(client.balance(&user2), 300);
+        assert_eq!(client.allowance(&user1, &user2), 100);
+        assert_eq!(client.total_supply(), 1000);
+
+        // Reading a balance bumps it again
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                env.storage().persistent().get_ttl(&DataKey::Balance(user1.clone())),
+                LEDGER_BUMP
+            );
+        });
+    }
+
+    #[test]
+    fn test_migrate_moves_instance_entries() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardToken);
+        let client = RewardTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let minter = Address::generate(&env);
+        let spender = Address::generate(&env);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Reward"),
+            &String::from_str(&env, "RWD"),
+            &6,
+            &0,
+        );
+
+        // Entries as an older version would have written them
+        env.as_contract(&contract_id, || {
+            let storage = env.storage().instance();
+            storage.set(&DataKey::Balance(user.clone()), &500i128);
+            storage.set(&DataKey::AuthorizedMinters(minter.clone()), &true);
+            storage.set(&DataKey::Allowance(user.clone(), spender.clone()), &50i128);
+            storage.set(&DataKey::TotalSupply, &500i128);
+        });
+        assert_eq!(client.balance(&user), 0);
+
+        env.mock_all_auths();
+
+        let moved = client.migrate(
+            &admin,
+            &Vec::from_array(&env, [user.clone(), minter.clone()]),
+            &Vec::from_array(&env, [(user.clone(), spender.clone())]),
+        );
+        assert_eq!(moved, 3);
+
+        assert_eq!(client.balance(&user), 500);
+        assert_eq!(client.allowance(&user, &spender), 50);
+        assert!(client.is_authorized_minter(&minter));
+
+        env.as_contract(&contract_id, || {
+            assert!(!env.storage().instance().has(&DataKey::Balance(user.clone())));
+        });
+
+        // Running it again is a no-op
+        let moved = client.migrate(
+            &admin,
+            &Vec::from_array(&env, [user.clone(), minter]),
+            &Vec::from_array(&env, [(user.clone(), spender)]),
+        );
+        assert_eq!(moved, 0);
+        assert_eq!(client.balance(&user), 500);
+    }
+
+    #[test]
+    fn test_migrate_merges_with_post_upgrade_entries() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardToken);
+        let client = RewardTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let spender = Address::generate(&env);
+
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Reward"),
+            &String::from_str(&env, "RWD"),
+            &6,
+            &0,
+        );
+
+        env.as_contract(&contract_id, || {
+            let storage = env.storage().instance();
+            storage.set(&DataKey::Balance(user.clone()), &500i128);
+            storage.set(&DataKey::Allowance(user.clone(), spender.clone()), &50i128);
+        });
+
+        env.mock_all_auths();
+
+        // The account is used under the new layout before anyone migrates it
+        client.mint(&admin, &sender, &200);
+        client.transfer(&sender, &user, &200);
+        client.approve(&user, &spender, &30, &None);
+        assert_eq!(client.balance(&user), 200);
+
+        let moved = client.migrate(
+            &admin,
+            &Vec::from_array(&env, [user.clone()]),
+            &Vec::from_array(&env, [(user.clone(), spender.clone())]),
+        );
+        assert_eq!(moved, 2);
+        assert_eq!(client.balance(&user), 700);
+        assert_eq!(client.allowance(&user, &spender), 80);
+    }
+
+    fn setup_capped(env: &Env, max_supply: i128) -> (RewardTokenClient<'_>, Address) {
+        let contract_id = env.register_contract(None, RewardToken);
+        let client = RewardTokenClient::new(env, &contract_id);
+        let admin = Address::generate(env);
+
+        client.initialize(
+            &admin,
+            &String::from_str(env, "Reward"),
+            &String::from_str(env, "RWD"),
+            &6,
+            &max_supply,
+        );
+        env.mock_all_auths();
+
+        (client, admin)
+    }
+
+    #[test]
+    fn test_mint_up_to_max_supply() {
+        let env = Env::default();
+        let (client, admin) = setup_capped(&env, 1000);
+        let user = Address::generate(&env);
+
+        assert_eq!(client.max_supply(), 1000);
+
+        client.mint(&admin, &user, &600);
+        client.mint(&admin, &user, &400);
+        assert_eq!(client.total_supply(), 1000);
+
+        // Burning frees room under the cap
+        client.burn(&user, &100);
+        client.mint(&admin, &user, &100);
+        assert_eq!(client.total_supply(), 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Max supply exceeded")]
+    fn test_mint_one_over_max_supply() {
+        let env = Env::default();
+        let (client, admin) = setup_capped(&env, 1000);
+        let user = Address::generate(&env);
+
+        client.mint(&admin, &user, &1000);
+        client.mint(&admin, &user, &1);
+    }
+
+    #[test]
+    fn test_distribute_rewards_respects_max_supply() {
+        let env = Env::default();
+        let (client, admin) = setup_capped(&env, 1000);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        client.mint(&admin, &user1, &500);
+
+        let recipients = Vec::from_array(&env, [user1.clone(), user2.clone()]);
+        let over = Vec::from_array(&env, [250i128, 251i128]);
+        assert!(client.try_distribute_rewards(&recipients, &over).is_err());
+        assert_eq!(client.total_supply(), 500);
+        assert_eq!(client.balance(&user2), 0);
+
+        let exact = Vec::from_array(&env, [250i128, 250i128]);
+        client.distribute_rewards(&recipients, &exact);
+        assert_eq!(client.total_supply(), 1000);
+    }
+
+    #[test]
+    fn test_zero_max_supply_is_unlimited() {
+        let env = Env::default();
+        let (client, admin) = setup_capped(&env, 0);
+        let user = Address::generate(&env);
+
+        client.mint(&admin, &user, &i128::MAX);
+        assert_eq!(client.total_supply(), i128::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account is frozen")]
+    fn test_frozen_sender_cannot_transfer() {
+        let env = Env::default();
+        let (client, admin) = setup_capped(&env, 0);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        client.mint(&admin, &user1, &1000);
+        client.set_frozen(&admin, &user1, &true);
+        assert!(client.is_frozen(&user1));
+
+        client.transfer(&user1, &user2, &100);
+    }
+
+    #[test]
+    fn test_frozen_recipient_blocks_transfers() {
+        let env = Env::default();
+        let (client, admin) = setup_capped(&env, 0);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+        let spender = Address::generate(&env);
+
+        client.mint(&admin, &user1, &1000);
+        client.approve(&user1, &spender, &500, &None);
+        client.set_frozen(&admin, &user2, &true);
+
+        assert!(client.try_transfer(&user1, &user2, &100).is_err());
+        assert!(client.try_transfer_from(&spender, &user1, &user2, &100).is_err());
+        assert_eq!(client.balance(&user2), 0);
+
+        client.set_frozen(&admin, &user2, &false);
+        assert!(!client.is_frozen(&user2));
+
+        client.transfer(&user1, &user2, &100);
+        client.transfer_from(&spender, &user1, &user2, &100);
+        assert_eq!(client.balance(&user2), 200);
+    }
+
+    #[test]
+    fn test_pause_blocks_transfers_but_not_minting() {
+        let env = Env::default();
+        let (client, admin) = setup_capped(&env, 0);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        client.mint(&admin, &user1, &1000);
+        client.approve(&user1, &user2, &500, &None);
+        client.set_paused(&admin, &true);
+        assert!(client.is_paused());
+
+        assert!(client.try_transfer(&user1, &user2, &100).is_err());
+        assert!(client.try_transfer_from(&user2, &user1, &user2, &100).is_err());
+        assert!(client.try_approve(&user1, &user2, &100, &None).is_err());
+        assert!(client
+            .try_spend_for_unlock(&user1, &100, &String::from_str(&env, "level_unlock"))
+            .is_err());
+
+        // Recovery paths stay open
+        client.mint(&admin, &user2, &50);
+        assert_eq!(client.balance(&user2), 50);
+
+        client.set_paused(&admin, &false);
+        client.transfer(&user1, &user2, &100);
+        assert_eq!(client.balance(&user1), 900);
+        assert_eq!(client.balance(&user2), 150);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn test_non_admin_cannot_pause() {
+        let env = Env::default();
+        let (client, _admin) = setup_capped(&env, 0);
+        let user = Address::generate(&env);
+
+        client.set_paused(&user, &true);
+    }
+
+    #[test]
+    fn test_increase_and_decrease_allowance() {
+        let env = Env::default();
+        let (client, admin) = setup_capped(&env, 0);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        client.mint(&admin, &owner, &1000);
+
+        assert_eq!(client.increase_allowance(&owner, &spender, &300), 300);
+        assert_eq!(client.increase_allowance(&owner, &spender, &200), 500);
+
+        // Reported as an approve with the new total
+        let event = env.events().all().last().unwrap();
+        assert_eq!(Symbol::try_from_val(&env, &event.1.get(0).unwrap()).unwrap(), symbol_short!("approve"));
+        assert_eq!(i128::try_from_val(&env, &event.2).unwrap(), 500);
+
+        assert_eq!(client.decrease_allowance(&owner, &spender, &350), 150);
+        assert!(client.try_transfer_from(&spender, &owner, &recipient, &151).is_err());
+        client.transfer_from(&spender, &owner, &recipient, &150);
+        assert_eq!(client.balance(&recipient), 150);
+        assert_eq!(client.allowance(&owner, &spender), 0);
+    }
+
+    #[test]
+    fn test_decrease_allowance_clamps_to_zero() {
+        let env = Env::default();
+        let (client, _admin) = setup_capped(&env, 0);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+
+        client.approve(&owner, &spender, &100, &None);
+        assert_eq!(client.decrease_allowance(&owner, &spender, &250), 0);
+        assert_eq!(client.allowance(&owner, &spender), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Allowance overflow")]
+    fn test_increase_allowance_overflow() {
+        let env = Env::default();
+        let (client, _admin) = setup_capped(&env, 0);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+
+        client.approve(&owner, &spender, &i128::MAX, &None);
+        client.increase_allowance(&owner, &spender, &1);
+    }
+
+    #[test]
+    fn test_allowance_expires() {
+        let env = Env::default();
+        let (client, admin) = setup_capped(&env, 0);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        env.ledger().set_sequence_number(100);
+        client.mint(&admin, &owner, &1000);
+        client.approve(&owner, &spender, &500, &Some(110));
+        assert_eq!(client.allowance_with_expiry(&owner, &spender), (500, 110));
+
+        // Usable through the expiration ledger itself
+        env.ledger().set_sequence_number(110);
+        client.transfer_from(&spender, &owner, &recipient, &200);
+        assert_eq!(client.allowance_with_expiry(&owner, &spender), (300, 110));
+
+        env.ledger().set_sequence_number(111);
+        assert_eq!(client.allowance(&owner, &spender), 0);
+        assert_eq!(client.allowance_with_expiry(&owner, &spender), (300, 110));
+        assert!(client.try_transfer_from(&spender, &owner, &recipient, &100).is_err());
+        assert_eq!(client.balance(&recipient), 200);
+
+        // A fresh approval without expiry works again
+        client.approve(&owner, &spender, &100, &None);
+        assert_eq!(client.allowance_with_expiry(&owner, &spender), (100, 0));
+        client.transfer_from(&spender, &owner, &recipient, &100);
+        assert_eq!(client.balance(&recipient), 300);
+    }
+
+    #[test]
+    #[should_panic(expected = "Allowance expired")]
+    fn test_transfer_from_rejects_expired_allowance() {
+        let env = Env::default();
+        let (client, admin) = setup_capped(&env, 0);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+
+        env.ledger().set_sequence_number(100);
+        client.mint(&admin, &owner, &1000);
+        client.approve(&owner, &spender, &500, &Some(105));
+
+        env.ledger().set_sequence_number(106);
+        client.transfer_from(&spender, &owner, &spender, &100);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expiration ledger is in the past")]
+    fn test_approve_rejects_past_expiration() {
+        let env = Env::default();
+        let (client, _admin) = setup_capped(&env, 0);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+
+        env.ledger().set_sequence_number(100);
+        client.approve(&owner, &spender, &500, &Some(99));
+    }
+
+    #[test]
+    fn test_transfer_batch() {
+        let env = Env::default();
+        let (client, admin) = setup_capped(&env, 0);
+        let from = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        client.mint(&admin, &from, &1000);
+
+        let recipients = Vec::from_array(&env, [user1.clone(), user2.clone()]);
+        let amounts = Vec::from_array(&env, [300i128, 450i128]);
+        client.transfer_batch(&from, &recipients, &amounts);
+
+        assert_eq!(client.balance(&from), 250);
+        assert_eq!(client.balance(&user1), 300);
+        assert_eq!(client.balance(&user2), 450);
+        assert_eq!(client.total_supply(), 1000);
+
+        // One transfer event per recipient, after the mint
+        let events = env.events().all();
+        assert_eq!(events.len(), 3);
+        for (i, (user, amount)) in [(user1, 300i128), (user2, 450i128)].into_iter().enumerate() {
+            let event = events.get(i as u32 + 1).unwrap();
+            let topics = event.1;
+            assert_eq!(Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(), symbol_short!("transfer"));
+            assert_eq!(Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap(), from);
+            assert_eq!(Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap(), user);
+            assert_eq!(i128::try_from_val(&env, &event.2).unwrap(), amount);
+        }
+    }
+
+    #[test]
+    fn test_transfer_batch_over_balance_changes_nothing() {
+        let env = Env::default();
+        let (client, admin) = setup_capped(&env, 0);
+        let from = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        client.mint(&admin, &from, &1000);
+
+        let recipients = Vec::from_array(&env, [user1.clone(), user2.clone()]);
+        let amounts = Vec::from_array(&env, [600i128, 401i128]);
+        assert!(client.try_transfer_batch(&from, &recipients, &amounts).is_err());
+
+        assert_eq!(client.balance(&from), 1000);
+        assert_eq!(client.balance(&user1), 0);
+        assert_eq!(client.balance(&user2), 0);
+    }
 }
@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Env, Vec};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Map, Vec};
 
 //
 // ──────────────────────────────────────────────────────────
@@ -17,6 +17,32 @@ pub enum StakingTier {
     Gold = 3,
 }
 
+//
+// ──────────────────────────────────────────────────────────
+// ERRORS
+// ──────────────────────────────────────────────────────────
+//
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    NotInitialized = 1,
+    Paused = 2,
+    InvalidAmount = 3,
+    NotStaked = 4,
+    InsufficientBalance = 5,
+    LockActive = 6,
+    NoRewards = 7,
+    InsufficientPool = 8,
+    Unauthorized = 9,
+    AlreadyInitialized = 10,
+    InsufficientEscrow = 11,
+    CompoundUnsupported = 12,
+    NoSlashTreasury = 13,
+    PoolInvariantViolated = 14,
+}
+
 //
 // ──────────────────────────────────────────────────────────
 // DATA KEYS
@@ -30,6 +56,14 @@ pub enum DataKey {
     StakersList,               // Vec<Address>
     TotalStaked,               // i128
     RewardPool,                // i128
+    StakePositions(Address),   // Vec<StakePosition>
+    StakerCount,               // u32
+    LockBonuses,               // Map<u64, u32> lock duration -> bonus APY
+    PendingAdmin,              // Address nominated by transfer_admin
+    SlashTreasury,             // Address receiving slashed stake (optional)
+    RewardIndex,               // RewardIndex
+    TierEpochs,                // Vec<TierEpoch> thresholds replaced by update_tier_thresholds
+    StakerIndex(Address),      // RewardIndex at the staker's last settlement
 }
 
 //
@@ -55,6 +89,8 @@ pub struct StakingConfig {
     pub early_unstake_penalty: u32, // Penalty in basis points (1000 = 10%)
     pub emergency_penalty: u32,     // Emergency withdrawal penalty
     pub paused: bool,               // Contract pause state
+    pub claim_when_paused: bool,    // Allow reward claims while paused
+    pub unstake_when_paused: bool,  // Allow unstaking unlocked positions while paused
 }
 
 #[contracttype]
@@ -67,6 +103,55 @@ pub struct StakerInfo {
     pub tier: StakingTier,
 }
 
+/// A single deposit with its own lock period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakePosition {
+    pub amount: i128,
+    pub stake_timestamp: u64,
+    pub lock_period: u64, // Chosen lock in seconds (0 = min_lock_period)
+    pub lock_bonus: u32,  // Additional APY for the chosen lock
+}
+
+/// APY accrued since deployment per rate component, in basis-point-seconds
+///
+/// A staker's pending rewards come from the difference between the current
+/// index and the snapshot taken at their last settlement, so changing rates
+/// only checkpoints the index instead of touching every staker.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardIndex {
+    pub base: i128,
+    pub bronze: i128,
+    pub silver: i128,
+    pub gold: i128,
+    pub updated_at: u64,
+}
+
+/// Tier thresholds that applied until `changed_at`, and the index at that time
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierEpoch {
+    pub changed_at: u64,
+    pub index: RewardIndex,
+    pub bronze_threshold: i128,
+    pub silver_threshold: i128,
+    pub gold_threshold: i128,
+}
+
+/// Everything a client needs about one staker in a single call
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakerSummary {
+    pub staked_amount: i128,
+    pub tier: StakingTier,
+    pub pending_rewards: i128,      // Accumulated plus not yet settled rewards
+    pub current_apy: u32,           // Base plus tier bonus, in basis points
+    pub lock_bonus: u32,            // Weighted lock bonus APY, in basis points
+    pub time_until_unlock: u64,     // Seconds until every position is unlocked
+    pub can_unstake_without_penalty: bool,
+}
+
 //
 // ──────────────────────────────────────────────────────────
 // CONSTANTS
@@ -75,6 +160,7 @@ pub struct StakerInfo {
 
 const SECONDS_PER_YEAR: u64 = 31_536_000;
 const BASIS_POINTS: u64 = 10_000;
+const MAX_PAGE_SIZE: u32 = 100;
 
 //
 // ──────────────────────────────────────────────────────────
@@ -104,11 +190,11 @@ impl StakingContract {
         reward_token: Address,
         base_apy: u32,
         min_lock_period: u64,
-    ) {
+    ) -> Result<(), Error> {
         admin.require_auth();
 
         if env.storage().persistent().has(&DataKey::Config) {
-            panic!("Already initialized");
+            return Err(Error::AlreadyInitialized);
         }
 
         let config = StakingConfig {
@@ -126,11 +212,22 @@ impl StakingContract {
             early_unstake_penalty: 1_000,  // 10% penalty
             emergency_penalty: 2_000,      // 20% penalty
             paused: false,
+            claim_when_paused: false,
+            unstake_when_paused: true,
         };
 
         env.storage().persistent().set(&DataKey::Config, &config);
         env.storage().persistent().set(&DataKey::TotalStaked, &0i128);
         env.storage().persistent().set(&DataKey::RewardPool, &0i128);
+        env.storage().persistent().set(&DataKey::RewardIndex, &RewardIndex {
+            base: 0,
+            bronze: 0,
+            silver: 0,
+            gold: 0,
+            updated_at: env.ledger().timestamp(),
+        });
+
+        Ok(())
     }
 
     // ───────────── ADMIN FUNCTIONS ─────────────
@@ -143,11 +240,14 @@ impl StakingContract {
         bronze_bonus: u32,
         silver_bonus: u32,
         gold_bonus: u32,
-    ) {
+    ) -> Result<(), Error> {
         admin.require_auth();
-        Self::assert_admin(&env, &admin);
+        Self::assert_admin(&env, &admin)?;
 
-        let mut config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let mut config = Self::load_config(&env)?;
+
+        // Rewards earned so far stay at the old rates
+        Self::checkpoint_index(&env, &config);
 
         config.base_apy = base_apy;
         config.bronze_bonus = bronze_bonus;
@@ -155,6 +255,8 @@ impl StakingContract {
         config.gold_bonus = gold_bonus;
 
         env.storage().persistent().set(&DataKey::Config, &config);
+
+        Ok(())
     }
 
     /// Update tier thresholds (admin only)
@@ -164,17 +266,34 @@ impl StakingContract {
         bronze_threshold: i128,
         silver_threshold: i128,
         gold_threshold: i128,
-    ) {
+    ) -> Result<(), Error> {
         admin.require_auth();
-        Self::assert_admin(&env, &admin);
-
-        let mut config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        Self::assert_admin(&env, &admin)?;
+
+        let mut config = Self::load_config(&env)?;
+
+        // Rewards earned so far stay at the old tiers; each staker is
+        // re-tiered from this epoch when they are next settled
+        let index = Self::checkpoint_index(&env, &config);
+        let mut epochs: Vec<TierEpoch> = env.storage().persistent()
+            .get(&DataKey::TierEpochs)
+            .unwrap_or(Vec::new(&env));
+        epochs.push_back(TierEpoch {
+            changed_at: env.ledger().timestamp(),
+            index,
+            bronze_threshold: config.bronze_threshold,
+            silver_threshold: config.silver_threshold,
+            gold_threshold: config.gold_threshold,
+        });
+        env.storage().persistent().set(&DataKey::TierEpochs, &epochs);
 
         config.bronze_threshold = bronze_threshold;
         config.silver_threshold = silver_threshold;
         config.gold_threshold = gold_threshold;
 
         env.storage().persistent().set(&DataKey::Config, &config);
+
+        Ok(())
     }
 
     /// Update staking parameters (admin only)
@@ -184,60 +303,250 @@ impl StakingContract {
         min_lock_period: u64,
         early_unstake_penalty: u32,
         emergency_penalty: u32,
-    ) {
+    ) -> Result<(), Error> {
         admin.require_auth();
-        Self::assert_admin(&env, &admin);
+        Self::assert_admin(&env, &admin)?;
 
-        let mut config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let mut config = Self::load_config(&env)?;
 
         config.min_lock_period = min_lock_period;
         config.early_unstake_penalty = early_unstake_penalty;
         config.emergency_penalty = emergency_penalty;
 
         env.storage().persistent().set(&DataKey::Config, &config);
+
+        Ok(())
+    }
+
+    /// Nominate a new admin (admin only). Takes effect once `accept_admin` is called.
+    pub fn transfer_admin(env: Env, current_admin: Address, new_admin: Address) -> Result<(), Error> {
+        current_admin.require_auth();
+        Self::assert_admin(&env, &current_admin)?;
+
+        env.storage().persistent().set(&DataKey::PendingAdmin, &new_admin);
+
+        Ok(())
+    }
+
+    /// Accept a pending admin nomination
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        new_admin.require_auth();
+
+        let pending: Address = env.storage().persistent()
+            .get(&DataKey::PendingAdmin)
+            .ok_or(Error::Unauthorized)?;
+        if pending != new_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut config = Self::load_config(&env)?;
+        let old_admin = config.admin.clone();
+        config.admin = new_admin.clone();
+        env.storage().persistent().set(&DataKey::Config, &config);
+        env.storage().persistent().remove(&DataKey::PendingAdmin);
+
+        env.events().publish((symbol_short!("ADMIN_CHG"), old_admin), new_admin);
+
+        Ok(())
+    }
+
+    /// Get the admin nominated by `transfer_admin`, if any
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::PendingAdmin)
     }
 
     /// Pause/unpause the contract (admin only)
-    pub fn set_paused(env: Env, admin: Address, paused: bool) {
+    pub fn set_paused(env: Env, admin: Address, paused: bool) -> Result<(), Error> {
         admin.require_auth();
-        Self::assert_admin(&env, &admin);
+        Self::assert_admin(&env, &admin)?;
 
-        let mut config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let mut config = Self::load_config(&env)?;
         config.paused = paused;
         env.storage().persistent().set(&DataKey::Config, &config);
+
+        Ok(())
+    }
+
+    /// Choose which operations stay available while paused (admin only)
+    ///
+    /// Staking is always blocked and emergency withdrawal always allowed.
+    pub fn set_pause_policy(
+        env: Env,
+        admin: Address,
+        claim_when_paused: bool,
+        unstake_when_paused: bool,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        let mut config = Self::load_config(&env)?;
+        config.claim_when_paused = claim_when_paused;
+        config.unstake_when_paused = unstake_when_paused;
+        env.storage().persistent().set(&DataKey::Config, &config);
+
+        Ok(())
+    }
+
+    /// Set the bonus APY for locking at least `lock_seconds` (admin only)
+    ///
+    /// A bonus of 0 removes the entry.
+    pub fn set_lock_bonus(env: Env, admin: Address, lock_seconds: u64, bonus: u32) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        let mut bonuses: Map<u64, u32> = env.storage().persistent()
+            .get(&DataKey::LockBonuses)
+            .unwrap_or(Map::new(&env));
+
+        if bonus == 0 {
+            bonuses.remove(lock_seconds);
+        } else {
+            bonuses.set(lock_seconds, bonus);
+        }
+
+        env.storage().persistent().set(&DataKey::LockBonuses, &bonuses);
+
+        Ok(())
     }
 
     /// Add rewards to the reward pool (admin only)
-    pub fn add_rewards(env: Env, admin: Address, amount: i128) {
+    pub fn add_rewards(env: Env, admin: Address, amount: i128) -> Result<(), Error> {
         admin.require_auth();
-        Self::assert_admin(&env, &admin);
+        Self::assert_admin(&env, &admin)?;
 
-        let config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let config = Self::load_config(&env)?;
         let reward_client = token::Client::new(&env, &config.reward_token);
 
         reward_client.transfer(&admin, &env.current_contract_address(), &amount);
 
         let current_pool: i128 = env.storage().persistent().get(&DataKey::RewardPool).unwrap_or(0);
         env.storage().persistent().set(&DataKey::RewardPool, &(current_pool + amount));
+
+        Ok(())
+    }
+
+    /// Set where slashed stake is sent (admin only)
+    pub fn set_slash_treasury(env: Env, admin: Address, treasury: Address) -> Result<(), Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        env.storage().persistent().set(&DataKey::SlashTreasury, &treasury);
+
+        Ok(())
+    }
+
+    /// Slash `bps` basis points of a staker's stake (admin only)
+    ///
+    /// Slashed tokens go to the slash treasury if one is set. Otherwise they are
+    /// added to the reward pool, which requires the staking and reward tokens to match.
+    pub fn slash(env: Env, admin: Address, staker: Address, bps: u32) -> Result<i128, Error> {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin)?;
+
+        if bps == 0 || bps as u64 > BASIS_POINTS {
+            return Err(Error::InvalidAmount);
+        }
+
+        let config = Self::load_config(&env)?;
+        let mut staker_info: StakerInfo = env.storage().persistent()
+            .get(&DataKey::StakerInfo(staker.clone()))
+            .ok_or(Error::NotStaked)?;
+
+        if staker_info.staked_amount <= 0 {
+            return Err(Error::NotStaked);
+        }
+
+        let treasury: Option<Address> = env.storage().persistent().get(&DataKey::SlashTreasury);
+        if treasury.is_none() && config.staking_token != config.reward_token {
+            return Err(Error::NoSlashTreasury);
+        }
+
+        // Settle rewards earned on the full stake before it shrinks
+        Self::settle_rewards(&env, &staker, &mut staker_info, &config);
+
+        let slashed = (staker_info.staked_amount * bps as i128) / BASIS_POINTS as i128;
+
+        let positions = Self::load_positions(&env, &staker);
+        let (remaining_positions, _) =
+            Self::withdraw_from_positions(&env, &positions, slashed, config.min_lock_period);
+
+        staker_info.staked_amount -= slashed;
+        staker_info.tier = Self::calculate_tier(staker_info.staked_amount, &config);
+        env.storage().persistent().set(&DataKey::StakerInfo(staker.clone()), &staker_info);
+
+        let total_staked: i128 = env.storage().persistent().get(&DataKey::TotalStaked).unwrap_or(0);
+        env.storage().persistent().set(&DataKey::TotalStaked, &(total_staked - slashed));
+
+        if staker_info.staked_amount == 0 {
+            env.storage().persistent().remove(&DataKey::StakePositions(staker.clone()));
+            Self::remove_from_stakers_list(&env, staker.clone());
+        } else {
+            env.storage().persistent().set(&DataKey::StakePositions(staker.clone()), &remaining_positions);
+        }
+
+        match treasury {
+            Some(treasury) => {
+                let staking_client = token::Client::new(&env, &config.staking_token);
+                staking_client.transfer(&env.current_contract_address(), &treasury, &slashed);
+            }
+            None => {
+                let reward_pool: i128 = env.storage().persistent().get(&DataKey::RewardPool).unwrap_or(0);
+                env.storage().persistent().set(&DataKey::RewardPool, &(reward_pool + slashed));
+            }
+        }
+
+        env.events().publish(
+            (symbol_short!("SLASH"), staker),
+            (slashed, bps, staker_info.tier),
+        );
+
+        Ok(slashed)
     }
 
     // ───────────── STAKING FUNCTIONS ─────────────
 
     /// Stake tokens
-    pub fn stake(env: Env, staker: Address, amount: i128) {
+    pub fn stake(env: Env, staker: Address, amount: i128) -> Result<(), Error> {
+        staker.require_auth();
+        Self::assert_not_paused(&env)?;
+
+        let config = Self::load_config(&env)?;
+        Self::add_stake(&env, &config, staker, amount, 0, 0)
+    }
+
+    /// Stake tokens with a lock longer than `min_lock_period` for a bonus APY
+    pub fn stake_with_lock(env: Env, staker: Address, amount: i128, lock_seconds: u64) -> Result<(), Error> {
         staker.require_auth();
-        Self::assert_not_paused(&env);
+        Self::assert_not_paused(&env)?;
+
+        let config = Self::load_config(&env)?;
+        if lock_seconds < config.min_lock_period {
+            return Err(Error::InvalidAmount);
+        }
 
+        let lock_bonus = Self::lock_bonus_for(&env, lock_seconds);
+        Self::add_stake(&env, &config, staker, amount, lock_seconds, lock_bonus)
+    }
+
+    fn add_stake(
+        env: &Env,
+        config: &StakingConfig,
+        staker: Address,
+        amount: i128,
+        lock_period: u64,
+        lock_bonus: u32,
+    ) -> Result<(), Error> {
         if amount <= 0 {
-            panic!("Amount must be positive");
+            return Err(Error::InvalidAmount);
         }
 
-        let config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
-        let staking_client = token::Client::new(&env, &config.staking_token);
+        let staking_client = token::Client::new(env, &config.staking_token);
 
         // Transfer tokens from staker to contract
         staking_client.transfer(&staker, &env.current_contract_address(), &amount);
 
+        let mut positions = Self::load_positions(env, &staker);
+
         // Get or create staker info
         let mut staker_info = Self::get_staker_info(env.clone(), staker.clone())
             .unwrap_or(StakerInfo {
@@ -248,63 +557,77 @@ impl StakingContract {
                 tier: StakingTier::None,
             });
 
-        // If existing stake, claim pending rewards first
-        if staker_info.staked_amount > 0 {
-            let pending = Self::calculate_pending_rewards(&env, &staker_info, &config);
-            staker_info.accumulated_rewards += pending;
-        }
+        // Settle pending rewards at the current tier before it changes
+        Self::settle_rewards(env, &staker, &mut staker_info, config);
 
         // Update staker info
         staker_info.staked_amount += amount;
         staker_info.stake_timestamp = env.ledger().timestamp();
-        staker_info.last_reward_claim = env.ledger().timestamp();
-        staker_info.tier = Self::calculate_tier(staker_info.staked_amount, &config);
+        staker_info.tier = Self::calculate_tier(staker_info.staked_amount, config);
 
         env.storage().persistent().set(&DataKey::StakerInfo(staker.clone()), &staker_info);
 
+        // Each deposit gets its own position so earlier deposits keep their unlock progress
+        positions.push_back(StakePosition {
+            amount,
+            stake_timestamp: env.ledger().timestamp(),
+            lock_period,
+            lock_bonus,
+        });
+        env.storage().persistent().set(&DataKey::StakePositions(staker.clone()), &positions);
+
         // Update stakers list
-        Self::add_to_stakers_list(&env, staker);
+        Self::add_to_stakers_list(env, staker.clone());
 
         // Update total staked
         let total_staked: i128 = env.storage().persistent().get(&DataKey::TotalStaked).unwrap_or(0);
         env.storage().persistent().set(&DataKey::TotalStaked, &(total_staked + amount));
+
+        env.events().publish(
+            (symbol_short!("STAKE"), staker),
+            (amount, staker_info.tier),
+        );
+
+        Ok(())
     }
 
     /// Unstake tokens (with early unstake penalty if applicable)
-    pub fn unstake(env: Env, staker: Address, amount: i128) {
+    ///
+    /// While paused, only unlocked amounts can be unstaked, and only if the
+    /// pause policy allows it.
+    pub fn unstake(env: Env, staker: Address, amount: i128) -> Result<(), Error> {
         staker.require_auth();
-        Self::assert_not_paused(&env);
 
         if amount <= 0 {
-            panic!("Amount must be positive");
+            return Err(Error::InvalidAmount);
         }
 
-        let config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let config = Self::load_config(&env)?;
         let mut staker_info: StakerInfo = env.storage().persistent()
             .get(&DataKey::StakerInfo(staker.clone()))
-            .expect("Not staked");
+            .ok_or(Error::NotStaked)?;
 
         if staker_info.staked_amount < amount {
-            panic!("Insufficient staked balance");
+            return Err(Error::InsufficientBalance);
         }
 
-        // Calculate pending rewards before unstaking
-        let pending = Self::calculate_pending_rewards(&env, &staker_info, &config);
-        staker_info.accumulated_rewards += pending;
-
-        // Check if early unstake (before lock period ends)
-        let time_staked = env.ledger().timestamp() - staker_info.stake_timestamp;
-        let mut penalty_amount: i128 = 0;
+        // Settle pending rewards at the current tier before it changes
+        Self::settle_rewards(&env, &staker, &mut staker_info, &config);
 
-        if time_staked < config.min_lock_period {
-            penalty_amount = (amount * config.early_unstake_penalty as i128) / BASIS_POINTS as i128;
+        // Draw from unlocked positions first, oldest first; only locked amounts are penalized
+        let positions = Self::load_positions(&env, &staker);
+        let (remaining_positions, locked_amount) =
+            Self::withdraw_from_positions(&env, &positions, amount, config.min_lock_period);
+        if config.paused && (!config.unstake_when_paused || locked_amount > 0) {
+            return Err(Error::Paused);
         }
+        env.storage().persistent().set(&DataKey::StakePositions(staker.clone()), &remaining_positions);
 
+        let penalty_amount = (locked_amount * config.early_unstake_penalty as i128) / BASIS_POINTS as i128;
         let amount_to_return = amount - penalty_amount;
 
         // Update staker info
         staker_info.staked_amount -= amount;
-        staker_info.last_reward_claim = env.ledger().timestamp();
         staker_info.tier = Self::calculate_tier(staker_info.staked_amount, &config);
 
         env.storage().persistent().set(&DataKey::StakerInfo(staker.clone()), &staker_info);
@@ -319,37 +642,45 @@ impl StakingContract {
 
         // Remove from stakers list if fully unstaked
         if staker_info.staked_amount == 0 {
-            Self::remove_from_stakers_list(&env, staker);
+            Self::remove_from_stakers_list(&env, staker.clone());
         }
+
+        env.events().publish(
+            (symbol_short!("UNSTAKE"), staker),
+            (amount, penalty_amount, staker_info.tier),
+        );
+
+        Ok(())
     }
 
     /// Claim accumulated rewards
-    pub fn claim_rewards(env: Env, staker: Address) -> i128 {
+    pub fn claim_rewards(env: Env, staker: Address) -> Result<i128, Error> {
         staker.require_auth();
-        Self::assert_not_paused(&env);
 
-        let config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let config = Self::load_config(&env)?;
+        if config.paused && !config.claim_when_paused {
+            return Err(Error::Paused);
+        }
         let mut staker_info: StakerInfo = env.storage().persistent()
             .get(&DataKey::StakerInfo(staker.clone()))
-            .expect("Not staked");
+            .ok_or(Error::NotStaked)?;
 
         // Calculate total rewards
-        let pending = Self::calculate_pending_rewards(&env, &staker_info, &config);
-        let total_rewards = staker_info.accumulated_rewards + pending;
+        Self::settle_rewards(&env, &staker, &mut staker_info, &config);
+        let total_rewards = staker_info.accumulated_rewards;
 
         if total_rewards <= 0 {
-            panic!("No rewards to claim");
+            return Err(Error::NoRewards);
         }
 
         // Check reward pool has enough
         let reward_pool: i128 = env.storage().persistent().get(&DataKey::RewardPool).unwrap_or(0);
         if reward_pool < total_rewards {
-            panic!("Insufficient reward pool");
+            return Err(Error::InsufficientPool);
         }
 
         // Update staker info
         staker_info.accumulated_rewards = 0;
-        staker_info.last_reward_claim = env.ledger().timestamp();
         env.storage().persistent().set(&DataKey::StakerInfo(staker.clone()), &staker_info);
 
         // Update reward pool
@@ -359,20 +690,126 @@ impl StakingContract {
         let reward_client = token::Client::new(&env, &config.reward_token);
         reward_client.transfer(&env.current_contract_address(), &staker, &total_rewards);
 
-        total_rewards
+        Self::assert_escrow_covered(&env, &config)?;
+
+        env.events().publish((symbol_short!("CLAIM"), staker), total_rewards);
+
+        Ok(total_rewards)
+    }
+
+    /// Claim as much of the accumulated rewards as the reward pool can cover
+    ///
+    /// Any unpaid remainder stays in `accumulated_rewards` for a later claim.
+    pub fn claim_available(env: Env, staker: Address) -> Result<i128, Error> {
+        staker.require_auth();
+
+        let config = Self::load_config(&env)?;
+        if config.paused && !config.claim_when_paused {
+            return Err(Error::Paused);
+        }
+        let mut staker_info: StakerInfo = env.storage().persistent()
+            .get(&DataKey::StakerInfo(staker.clone()))
+            .ok_or(Error::NotStaked)?;
+
+        Self::settle_rewards(&env, &staker, &mut staker_info, &config);
+        let total_rewards = staker_info.accumulated_rewards;
+
+        if total_rewards <= 0 {
+            return Err(Error::NoRewards);
+        }
+
+        let reward_pool: i128 = env.storage().persistent().get(&DataKey::RewardPool).unwrap_or(0);
+        if reward_pool <= 0 {
+            return Err(Error::InsufficientPool);
+        }
+
+        let payout = if reward_pool < total_rewards { reward_pool } else { total_rewards };
+
+        // Keep the unpaid remainder for later
+        staker_info.accumulated_rewards = total_rewards - payout;
+        env.storage().persistent().set(&DataKey::StakerInfo(staker.clone()), &staker_info);
+
+        env.storage().persistent().set(&DataKey::RewardPool, &(reward_pool - payout));
+
+        let reward_client = token::Client::new(&env, &config.reward_token);
+        reward_client.transfer(&env.current_contract_address(), &staker, &payout);
+
+        Self::assert_escrow_covered(&env, &config)?;
+
+        env.events().publish((symbol_short!("CLAIM"), staker), payout);
+
+        Ok(payout)
+    }
+
+    /// Compound accumulated rewards into staked principal
+    ///
+    /// Only available when the reward token is the staking token. Rewards move
+    /// from the reward pool into the staker's principal without a transfer.
+    pub fn compound_rewards(env: Env, staker: Address) -> Result<i128, Error> {
+        staker.require_auth();
+        Self::assert_not_paused(&env)?;
+
+        let config = Self::load_config(&env)?;
+        if config.staking_token != config.reward_token {
+            return Err(Error::CompoundUnsupported);
+        }
+
+        let mut staker_info: StakerInfo = env.storage().persistent()
+            .get(&DataKey::StakerInfo(staker.clone()))
+            .ok_or(Error::NotStaked)?;
+
+        Self::settle_rewards(&env, &staker, &mut staker_info, &config);
+        let total_rewards = staker_info.accumulated_rewards;
+
+        if total_rewards <= 0 {
+            return Err(Error::NoRewards);
+        }
+
+        let reward_pool: i128 = env.storage().persistent().get(&DataKey::RewardPool).unwrap_or(0);
+        if reward_pool < total_rewards {
+            return Err(Error::InsufficientPool);
+        }
+
+        let mut positions = Self::load_positions(&env, &staker);
+
+        // Move rewards into principal
+        staker_info.staked_amount += total_rewards;
+        staker_info.accumulated_rewards = 0;
+        staker_info.tier = Self::calculate_tier(staker_info.staked_amount, &config);
+        env.storage().persistent().set(&DataKey::StakerInfo(staker.clone()), &staker_info);
+
+        positions.push_back(StakePosition {
+            amount: total_rewards,
+            stake_timestamp: env.ledger().timestamp(),
+            lock_period: 0,
+            lock_bonus: 0,
+        });
+        env.storage().persistent().set(&DataKey::StakePositions(staker.clone()), &positions);
+
+        env.storage().persistent().set(&DataKey::RewardPool, &(reward_pool - total_rewards));
+
+        let total_staked: i128 = env.storage().persistent().get(&DataKey::TotalStaked).unwrap_or(0);
+        env.storage().persistent().set(&DataKey::TotalStaked, &(total_staked + total_rewards));
+
+        env.events().publish(
+            (symbol_short!("COMPOUND"), staker),
+            (total_rewards, staker_info.tier),
+        );
+
+        Ok(total_rewards)
     }
 
     /// Emergency withdrawal - withdraw all staked tokens with higher penalty
-    pub fn emergency_withdraw(env: Env, staker: Address) -> i128 {
+    pub fn emergency_withdraw(env: Env, staker: Address) -> Result<i128, Error> {
         staker.require_auth();
 
-        let config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let config = Self::load_config(&env)?;
         let staker_info: StakerInfo = env.storage().persistent()
             .get(&DataKey::StakerInfo(staker.clone()))
-            .expect("Not staked");
+            .ok_or(Error::NotStaked)?;
 
         if staker_info.staked_amount <= 0 {
-            panic!("Nothing to withdraw");
+            return Err(Error::NotStaked);
         }
 
         let penalty_amount = (staker_info.staked_amount * config.emergency_penalty as i128) / BASIS_POINTS as i128;
@@ -387,6 +824,8 @@ impl StakingContract {
             tier: StakingTier::None,
         };
         env.storage().persistent().set(&DataKey::StakerInfo(staker.clone()), &empty_info);
+        env.storage().persistent().remove(&DataKey::StakePositions(staker.clone()));
+        env.storage().persistent().remove(&DataKey::StakerIndex(staker.clone()));
 
         // Transfer tokens back to staker
         let staking_client = token::Client::new(&env, &config.staking_token);
@@ -397,30 +836,55 @@ impl StakingContract {
         env.storage().persistent().set(&DataKey::TotalStaked, &(total_staked - staker_info.staked_amount));
 
         // Remove from stakers list
-        Self::remove_from_stakers_list(&env, staker);
+        Self::remove_from_stakers_list(&env, staker.clone());
 
-        amount_to_return
+        env.events().publish(
+            (symbol_short!("EMERG"), staker),
+            (amount_to_return, penalty_amount),
+        );
+
+        Ok(amount_to_return)
     }
 
     // ───────────── VIEW FUNCTIONS ─────────────
 
-    /// Get staker information
+    /// Get staker information, with the tier under the current thresholds
     pub fn get_staker_info(env: Env, staker: Address) -> Option<StakerInfo> {
-        env.storage().persistent().get(&DataKey::StakerInfo(staker))
+        let mut info: StakerInfo = env.storage().persistent().get(&DataKey::StakerInfo(staker))?;
+        if let Ok(config) = Self::load_config(&env) {
+            info.tier = Self::calculate_tier(info.staked_amount, &config);
+        }
+        Some(info)
     }
 
     /// Get pending rewards for a staker
     pub fn get_pending_rewards(env: Env, staker: Address) -> i128 {
         let config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
 
-        if let Some(staker_info) = Self::get_staker_info(env.clone(), staker) {
-            let pending = Self::calculate_pending_rewards(&env, &staker_info, &config);
+        if let Some(staker_info) = Self::get_staker_info(env.clone(), staker.clone()) {
+            let pending = Self::calculate_pending_rewards(&env, &staker, &staker_info, &config);
             staker_info.accumulated_rewards + pending
         } else {
             0
         }
     }
 
+    /// Get a staker's amount, tier, rewards, APY and unlock status together
+    pub fn get_staker_summary(env: Env, staker: Address) -> StakerSummary {
+        let info = Self::get_staker_info(env.clone(), staker.clone());
+        let time_until_unlock = Self::get_time_until_unlock(env.clone(), staker.clone());
+
+        StakerSummary {
+            staked_amount: info.as_ref().map(|i| i.staked_amount).unwrap_or(0),
+            tier: info.map(|i| i.tier).unwrap_or(StakingTier::None),
+            pending_rewards: Self::get_pending_rewards(env.clone(), staker.clone()),
+            current_apy: Self::get_current_apy(env.clone(), staker.clone()),
+            lock_bonus: Self::get_lock_bonus(env, staker),
+            time_until_unlock,
+            can_unstake_without_penalty: time_until_unlock == 0,
+        }
+    }
+
     /// Get total staked amount
     pub fn get_total_staked(env: Env) -> i128 {
         env.storage().persistent().get(&DataKey::TotalStaked).unwrap_or(0)
@@ -447,22 +911,54 @@ impl StakingContract {
         }
     }
 
-    /// Get time remaining until lock period ends (0 if already unlocked)
-    pub fn get_time_until_unlock(env: Env, staker: Address) -> u64 {
+    /// Get the APY for a staker split into (base, tier bonus, total), in basis points
+    pub fn get_apy_breakdown(env: Env, staker: Address) -> (u32, u32, u32) {
         let config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
 
-        if let Some(staker_info) = Self::get_staker_info(env.clone(), staker) {
-            let unlock_time = staker_info.stake_timestamp + config.min_lock_period;
-            let current_time = env.ledger().timestamp();
+        let tier = Self::get_staker_info(env.clone(), staker)
+            .map(|info| info.tier)
+            .unwrap_or(StakingTier::None);
+        let bonus = Self::get_bonus_for_tier(tier, &config);
+
+        (config.base_apy, bonus, config.base_apy + bonus)
+    }
+
+    /// Get a staker's open positions, oldest first
+    pub fn get_positions(env: Env, staker: Address) -> Vec<StakePosition> {
+        Self::load_positions(&env, &staker)
+    }
+
+    /// Get a staker's lock bonus APY in basis points, weighted across positions
+    ///
+    /// Positions whose lock has ended no longer earn a bonus.
+    pub fn get_lock_bonus(env: Env, staker: Address) -> u32 {
+        let config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let mut total: i128 = 0;
+        for position in Self::load_positions(&env, &staker).iter() {
+            total += position.amount;
+        }
+
+        if total <= 0 {
+            return 0;
+        }
+
+        (Self::lock_bonus_weight(&env, &staker, &config) / total) as u32
+    }
 
-            if current_time >= unlock_time {
-                0
-            } else {
-                unlock_time - current_time
+    /// Get time remaining until every position is unlocked (0 if already unlocked)
+    pub fn get_time_until_unlock(env: Env, staker: Address) -> u64 {
+        let config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let current_time = env.ledger().timestamp();
+
+        let mut remaining = 0;
+        for position in Self::load_positions(&env, &staker).iter() {
+            let unlock_time = position.stake_timestamp + Self::position_lock(&position, config.min_lock_period);
+            if unlock_time > current_time && unlock_time - current_time > remaining {
+                remaining = unlock_time - current_time;
             }
-        } else {
-            0
         }
+
+        remaining
     }
 
     /// Check if staker can unstake without penalty
@@ -471,42 +967,278 @@ impl StakingContract {
     }
 
     /// Get all stakers
+    ///
+    /// Unbounded: the result grows with the number of stakers. Prefer
+    /// `get_stakers_paginated` for off-chain enumeration.
     pub fn get_all_stakers(env: Env) -> Vec<Address> {
         env.storage().persistent().get(&DataKey::StakersList).unwrap_or(Vec::new(&env))
     }
 
+    /// Get up to `limit` stakers starting at index `start` (limit capped at 100)
+    pub fn get_stakers_paginated(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        let stakers = Self::get_all_stakers(env.clone());
+        let limit = if limit > MAX_PAGE_SIZE { MAX_PAGE_SIZE } else { limit };
+        let end = start.saturating_add(limit).min(stakers.len());
+
+        let mut page = Vec::new(&env);
+        for i in start..end {
+            page.push_back(stakers.get(i).unwrap());
+        }
+        page
+    }
+
+    /// Get number of active stakers
+    pub fn get_staker_count(env: Env) -> u32 {
+        env.storage().persistent().get(&DataKey::StakerCount).unwrap_or(0)
+    }
+
+    /// Get (contract staking token balance, total staked)
+    pub fn get_solvency(env: Env) -> (i128, i128) {
+        let config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let balance = token::Client::new(&env, &config.staking_token).balance(&env.current_contract_address());
+        (balance, Self::get_total_staked(env))
+    }
+
     // ───────────── INTERNAL HELPERS ─────────────
 
-    fn calculate_pending_rewards(env: &Env, staker_info: &StakerInfo, config: &StakingConfig) -> i128 {
+    fn calculate_pending_rewards(env: &Env, staker: &Address, staker_info: &StakerInfo, config: &StakingConfig) -> i128 {
         if staker_info.staked_amount <= 0 {
             return 0;
         }
 
-        let time_elapsed = env.ledger().timestamp() - staker_info.last_reward_claim;
-        let apy = Self::get_apy_for_tier(staker_info.tier, config) as i128;
-
-        // Use i128 arithmetic to avoid overflow
-        // rewards = staked_amount * apy * time_elapsed / (SECONDS_PER_YEAR * BASIS_POINTS)
-        // Split the calculation to avoid overflow: first divide by SECONDS_PER_YEAR, then multiply
         let staked = staker_info.staked_amount;
-        let time = time_elapsed as i128;
-        let seconds_per_year = SECONDS_PER_YEAR as i128;
-        let basis_points = BASIS_POINTS as i128;
+        let current = Self::current_index(env, config);
+        let divisor = (SECONDS_PER_YEAR * BASIS_POINTS) as i128;
+
+        let snapshot: Option<RewardIndex> = env.storage().persistent().get(&DataKey::StakerIndex(staker.clone()));
+        let rewards = match snapshot {
+            Some(mut from) => {
+                // Walk threshold changes since the last settlement at the tier each one implied
+                let mut rate_seconds: i128 = 0;
+                let epochs: Vec<TierEpoch> = env.storage().persistent()
+                    .get(&DataKey::TierEpochs)
+                    .unwrap_or(Vec::new(env));
+                for epoch in epochs.iter() {
+                    if epoch.changed_at <= staker_info.last_reward_claim {
+                        continue;
+                    }
+                    let tier = Self::tier_for(
+                        staked,
+                        epoch.bronze_threshold,
+                        epoch.silver_threshold,
+                        epoch.gold_threshold,
+                    );
+                    rate_seconds += Self::index_delta(&from, &epoch.index, tier);
+                    from = epoch.index;
+                }
+
+                let tier = Self::calculate_tier(staked, config);
+                rate_seconds += Self::index_delta(&from, &current, tier);
+
+                (staked * rate_seconds) / divisor
+            }
+            None => {
+                // Settled before the reward index existed: accrue at the current rate
+                let time_elapsed = env.ledger().timestamp() - staker_info.last_reward_claim;
+                let apy = Self::get_apy_for_tier(Self::calculate_tier(staked, config), config) as i128;
+                (staked * apy * time_elapsed as i128) / divisor
+            }
+        };
 
-        // Calculate: (staked * apy / BASIS_POINTS) * time / SECONDS_PER_YEAR
-        // This avoids overflow by doing division earlier
-        let annual_reward = (staked * apy) / basis_points;
-        let rewards = (annual_reward * time) / seconds_per_year;
+        // Lock bonuses apply per position on top of the tier APY, only while locked
+        rewards + Self::lock_bonus_rewards(env, staker, staker_info.last_reward_claim, config)
+    }
+
+    /// The reward index advanced to now at the configured rates
+    fn current_index(env: &Env, config: &StakingConfig) -> RewardIndex {
+        let now = env.ledger().timestamp();
+        let mut index: RewardIndex = env.storage().persistent()
+            .get(&DataKey::RewardIndex)
+            .unwrap_or(RewardIndex {
+                base: 0,
+                bronze: 0,
+                silver: 0,
+                gold: 0,
+                updated_at: now,
+            });
+
+        let elapsed = now.saturating_sub(index.updated_at) as i128;
+        index.base += config.base_apy as i128 * elapsed;
+        index.bronze += config.bronze_bonus as i128 * elapsed;
+        index.silver += config.silver_bonus as i128 * elapsed;
+        index.gold += config.gold_bonus as i128 * elapsed;
+        index.updated_at = now;
+        index
+    }
 
+    /// Store the reward index as of now, before a rate change
+    fn checkpoint_index(env: &Env, config: &StakingConfig) -> RewardIndex {
+        let index = Self::current_index(env, config);
+        env.storage().persistent().set(&DataKey::RewardIndex, &index);
+        index
+    }
+
+    /// Basis-point-seconds accrued between two index values at `tier`
+    fn index_delta(from: &RewardIndex, to: &RewardIndex, tier: StakingTier) -> i128 {
+        let bonus = match tier {
+            StakingTier::None => 0,
+            StakingTier::Bronze => to.bronze - from.bronze,
+            StakingTier::Silver => to.silver - from.silver,
+            StakingTier::Gold => to.gold - from.gold,
+        };
+        (to.base - from.base) + bonus
+    }
+
+    fn load_positions(env: &Env, staker: &Address) -> Vec<StakePosition> {
+        if let Some(positions) = env.storage().persistent().get(&DataKey::StakePositions(staker.clone())) {
+            return positions;
+        }
+
+        // Stakes made before positions existed are treated as a single position
+        let mut positions = Vec::new(env);
+        if let Some(info) = Self::get_staker_info(env.clone(), staker.clone()) {
+            if info.staked_amount > 0 {
+                positions.push_back(StakePosition {
+                    amount: info.staked_amount,
+                    stake_timestamp: info.stake_timestamp,
+                    lock_period: 0,
+                    lock_bonus: 0,
+                });
+            }
+        }
+        positions
+    }
+
+    /// Sum of `amount * lock_bonus` over a staker's still-locked positions
+    fn lock_bonus_weight(env: &Env, staker: &Address, config: &StakingConfig) -> i128 {
+        let now = env.ledger().timestamp();
+        let mut weight: i128 = 0;
+        for position in Self::load_positions(env, staker).iter() {
+            if now < position.stake_timestamp + Self::position_lock(&position, config.min_lock_period) {
+                weight += position.amount * position.lock_bonus as i128;
+            }
+        }
+        weight
+    }
+
+    /// Lock bonus earned since `since`, counting each position only until its lock ends
+    fn lock_bonus_rewards(env: &Env, staker: &Address, since: u64, config: &StakingConfig) -> i128 {
+        let now = env.ledger().timestamp();
+        let mut rewards: i128 = 0;
+        for position in Self::load_positions(env, staker).iter() {
+            if position.lock_bonus == 0 {
+                continue;
+            }
+            let lock_end = position.stake_timestamp + Self::position_lock(&position, config.min_lock_period);
+            let end = if now < lock_end { now } else { lock_end };
+            if end <= since {
+                continue;
+            }
+            let annual_bonus = (position.amount * position.lock_bonus as i128) / BASIS_POINTS as i128;
+            rewards += (annual_bonus * (end - since) as i128) / SECONDS_PER_YEAR as i128;
+        }
         rewards
     }
 
+    /// Bonus APY for the longest configured lock that `lock_seconds` satisfies
+    fn lock_bonus_for(env: &Env, lock_seconds: u64) -> u32 {
+        let bonuses: Map<u64, u32> = env.storage().persistent()
+            .get(&DataKey::LockBonuses)
+            .unwrap_or(Map::new(env));
+
+        let mut best_duration = 0;
+        let mut bonus = 0;
+        for (duration, duration_bonus) in bonuses.iter() {
+            if duration <= lock_seconds && duration >= best_duration {
+                best_duration = duration;
+                bonus = duration_bonus;
+            }
+        }
+        bonus
+    }
+
+    fn position_lock(position: &StakePosition, min_lock_period: u64) -> u64 {
+        if position.lock_period > min_lock_period {
+            position.lock_period
+        } else {
+            min_lock_period
+        }
+    }
+
+    /// Remove `amount` from the positions, taking unlocked positions (oldest first)
+    /// before locked ones. Returns the remaining positions and how much came from
+    /// still-locked positions.
+    fn withdraw_from_positions(
+        env: &Env,
+        positions: &Vec<StakePosition>,
+        amount: i128,
+        min_lock_period: u64,
+    ) -> (Vec<StakePosition>, i128) {
+        let now = env.ledger().timestamp();
+        let mut remaining: Vec<StakePosition> = positions.clone();
+        let mut to_take = amount;
+        let mut locked_amount: i128 = 0;
+
+        // First pass takes unlocked positions, second pass takes locked ones
+        for take_locked in [false, true] {
+            for i in 0..remaining.len() {
+                if to_take == 0 {
+                    break;
+                }
+
+                let mut position = remaining.get(i).unwrap();
+                let locked = now < position.stake_timestamp + Self::position_lock(&position, min_lock_period);
+                if locked != take_locked || position.amount == 0 {
+                    continue;
+                }
+
+                let taken = if position.amount < to_take { position.amount } else { to_take };
+                position.amount -= taken;
+                to_take -= taken;
+                if locked {
+                    locked_amount += taken;
+                }
+                remaining.set(i, position);
+            }
+        }
+
+        let mut open_positions = Vec::new(env);
+        for position in remaining.iter() {
+            if position.amount > 0 {
+                open_positions.push_back(position);
+            }
+        }
+
+        (open_positions, locked_amount)
+    }
+
+    /// Move pending rewards into `accumulated_rewards` and snapshot the reward index
+    fn settle_rewards(env: &Env, staker: &Address, staker_info: &mut StakerInfo, config: &StakingConfig) {
+        let pending = Self::calculate_pending_rewards(env, staker, staker_info, config);
+        staker_info.accumulated_rewards += pending;
+        staker_info.last_reward_claim = env.ledger().timestamp();
+        staker_info.tier = Self::calculate_tier(staker_info.staked_amount, config);
+        env.storage().persistent().set(
+            &DataKey::StakerIndex(staker.clone()),
+            &Self::current_index(env, config),
+        );
+    }
+
     fn calculate_tier(staked_amount: i128, config: &StakingConfig) -> StakingTier {
-        if staked_amount >= config.gold_threshold {
+        Self::tier_for(
+            staked_amount,
+            config.bronze_threshold,
+            config.silver_threshold,
+            config.gold_threshold,
+        )
+    }
+
+    fn tier_for(staked_amount: i128, bronze_threshold: i128, silver_threshold: i128, gold_threshold: i128) -> StakingTier {
+        if staked_amount >= gold_threshold {
             StakingTier::Gold
-        } else if staked_amount >= config.silver_threshold {
+        } else if staked_amount >= silver_threshold {
             StakingTier::Silver
-        } else if staked_amount >= config.bronze_threshold {
+        } else if staked_amount >= bronze_threshold {
             StakingTier::Bronze
         } else {
             StakingTier::None
@@ -514,11 +1246,15 @@ impl StakingContract {
     }
 
     fn get_apy_for_tier(tier: StakingTier, config: &StakingConfig) -> u32 {
+        config.base_apy + Self::get_bonus_for_tier(tier, config)
+    }
+
+    fn get_bonus_for_tier(tier: StakingTier, config: &StakingConfig) -> u32 {
         match tier {
-            StakingTier::None => config.base_apy,
-            StakingTier::Bronze => config.base_apy + config.bronze_bonus,
-            StakingTier::Silver => config.base_apy + config.silver_bonus,
-            StakingTier::Gold => config.base_apy + config.gold_bonus,
+            StakingTier::None => 0,
+            StakingTier::Bronze => config.bronze_bonus,
+            StakingTier::Silver => config.silver_bonus,
+            StakingTier::Gold => config.gold_bonus,
         }
     }
 
@@ -530,6 +1266,9 @@ impl StakingContract {
         if !stakers.contains(&staker) {
             stakers.push_back(staker);
             env.storage().persistent().set(&DataKey::StakersList, &stakers);
+
+            let count: u32 = env.storage().persistent().get(&DataKey::StakerCount).unwrap_or(0);
+            env.storage().persistent().set(&DataKey::StakerCount, &(count + 1));
         }
     }
 
@@ -545,21 +1284,61 @@ impl StakingContract {
             }
         }
 
+        if new_stakers.len() < stakers.len() {
+            let count: u32 = env.storage().persistent().get(&DataKey::StakerCount).unwrap_or(0);
+            env.storage().persistent().set(&DataKey::StakerCount, &count.saturating_sub(1));
+        }
+
         env.storage().persistent().set(&DataKey::StakersList, &new_stakers);
     }
 
-    fn assert_admin(env: &Env, user: &Address) {
-        let config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+    fn load_config(env: &Env) -> Result<StakingConfig, Error> {
+        env.storage().persistent().get(&DataKey::Config).ok_or(Error::NotInitialized)
+    }
+
+    fn assert_admin(env: &Env, user: &Address) -> Result<(), Error> {
+        let config = Self::load_config(env)?;
         if config.admin != *user {
-            panic!("Admin only");
+            return Err(Error::Unauthorized);
         }
+        Ok(())
     }
 
-    fn assert_not_paused(env: &Env) {
-        let config: StakingConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+    /// Ensure the contract still holds enough tokens to cover staked principal
+    /// and the reward pool. When both use the same token, one balance must cover both.
+    ///
+    /// Falling below staked principal is `PoolInvariantViolated`; a reward pool
+    /// that isn't fully backed is `InsufficientEscrow`.
+    fn assert_escrow_covered(env: &Env, config: &StakingConfig) -> Result<(), Error> {
+        let total_staked: i128 = env.storage().persistent().get(&DataKey::TotalStaked).unwrap_or(0);
+        let reward_pool: i128 = env.storage().persistent().get(&DataKey::RewardPool).unwrap_or(0);
+        let contract = env.current_contract_address();
+
+        let staking_balance = token::Client::new(env, &config.staking_token).balance(&contract);
+        if staking_balance < total_staked {
+            return Err(Error::PoolInvariantViolated);
+        }
+
+        if config.staking_token == config.reward_token {
+            if staking_balance < total_staked + reward_pool {
+                return Err(Error::InsufficientEscrow);
+            }
+        } else {
+            let reward_balance = token::Client::new(env, &config.reward_token).balance(&contract);
+            if reward_balance < reward_pool {
+                return Err(Error::InsufficientEscrow);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn assert_not_paused(env: &Env) -> Result<(), Error> {
+        let config = Self::load_config(env)?;
         if config.paused {
-            panic!("Contract is paused");
+            return Err(Error::Paused);
         }
+        Ok(())
     }
 }
 
@@ -2,10 +2,10 @@
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
+    testutils::{Address as _, Events, Ledger},
     token::Client as TokenClient,
     token::StellarAssetClient,
-    Address, Env,
+    Address, Env, Symbol, TryFromVal, Val,
 };
 
 fn create_token_contract<'a>(env: &Env, admin: &Address) -> (Address, TokenClient<'a>) {
@@ -14,6 +14,19 @@ fn create_token_contract<'a>(env: &Env, admin: &Address) -> (Address, TokenClien
     (address.clone(), TokenClient::new(env, &address))
 }
 
+fn find_event(env: &Env, name: &str) -> Option<(Address, Val)> {
+    let name = Symbol::new(env, name);
+    let mut found = None;
+    for (_, topics, data) in env.events().all().iter() {
+        let first: Val = topics.get(0).unwrap();
+        if Symbol::try_from_val(env, &first) == Ok(name.clone()) {
+            let staker = Address::try_from_val(env, &topics.get(1).unwrap()).unwrap();
+            found = Some((staker, data));
+        }
+    }
+    found
+}
+
 fn setup_staking_contract(env: &Env) -> (
     StakingContractClient,
     Address,
@@ -80,7 +93,6 @@ fn test_initialization() {
 }
 
 #[test]
-#[should_panic(expected = "Already initialized")]
 fn test_double_initialization() {
     let env = Env::default();
     env.mock_all_auths();
@@ -89,13 +101,14 @@ fn test_double_initialization() {
         setup_staking_contract(&env);
 
     // Try to initialize again
-    client.initialize(
+    let result = client.try_initialize(
         &admin,
         &staking_token_client.address,
         &reward_token_client.address,
         &500u32,
         &604800u64,
     );
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
 }
 
 #[test]
@@ -187,6 +200,27 @@ fn test_tier_system() {
     assert_eq!(client.get_current_apy(&staker), 1000); // 500 + 500
 }
 
+#[test]
+fn test_apy_breakdown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let (client, _, staker, _, _, _, staking_admin_client, _) = setup_staking_contract(&env);
+
+    // Non-staker only gets the base APY
+    assert_eq!(client.get_apy_breakdown(&staker), (500, 0, 500));
+
+    staking_admin_client.mint(&staker, &100_000_000_000);
+    client.stake(&staker, &100_000_000_000); // Gold tier
+
+    let (base, bonus, total) = client.get_apy_breakdown(&staker);
+    assert_eq!(base, 500);
+    assert_eq!(bonus, 500);
+    assert_eq!(total, 1000);
+    assert_eq!(total, client.get_current_apy(&staker));
+}
+
 #[test]
 fn test_unstake_after_lock_period() {
     let env = Env::default();
@@ -336,7 +370,6 @@ fn test_pause_functionality() {
 }
 
 #[test]
-#[should_panic(expected = "Contract is paused")]
 fn test_stake_when_paused() {
     let env = Env::default();
     env.mock_all_auths();
@@ -351,7 +384,8 @@ fn test_stake_when_paused() {
     client.set_paused(&admin, &true);
 
     // Try to stake (should fail)
-    client.stake(&staker, &1_000_000_000);
+    let result = client.try_stake(&staker, &1_000_000_000);
+    assert_eq!(result, Err(Ok(Error::Paused)));
 }
 
 #[test]
@@ -442,7 +476,6 @@ fn test_update_staking_params() {
 }
 
 #[test]
-#[should_panic(expected = "Admin only")]
 fn test_update_config_non_admin() {
     let env = Env::default();
     env.mock_all_auths();
@@ -450,28 +483,28 @@ fn test_update_config_non_admin() {
     let (client, _, staker, _, _, _, _, _) = setup_staking_contract(&env);
 
     // Try to update APY config as non-admin
-    client.update_apy_config(
+    let result = client.try_update_apy_config(
         &staker,
         &1000u32,
         &200u32,
         &400u32,
         &800u32,
     );
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
 }
 
 #[test]
-#[should_panic(expected = "Amount must be positive")]
 fn test_stake_zero_amount() {
     let env = Env::default();
     env.mock_all_auths();
 
     let (client, _, staker, _, _, _, _, _) = setup_staking_contract(&env);
 
-    client.stake(&staker, &0);
+    let result = client.try_stake(&staker, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
 }
 
 #[test]
-#[should_panic(expected = "Insufficient staked balance")]
 fn test_unstake_more_than_staked() {
     let env = Env::default();
     env.mock_all_auths();
@@ -483,11 +516,11 @@ fn test_unstake_more_than_staked() {
     client.stake(&staker, &5_000_000_000);
 
     // Try to unstake more than staked
-    client.unstake(&staker, &6_000_000_000);
+    let result = client.try_unstake(&staker, &6_000_000_000);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
 }
 
 #[test]
-#[should_panic(expected = "No rewards to claim")]
 fn test_claim_zero_rewards() {
     let env = Env::default();
     env.mock_all_auths();
@@ -499,11 +532,11 @@ fn test_claim_zero_rewards() {
     client.stake(&staker, &5_000_000_000);
 
     // Try to claim immediately (no time passed, no rewards)
-    client.claim_rewards(&staker);
+    let result = client.try_claim_rewards(&staker);
+    assert_eq!(result, Err(Ok(Error::NoRewards)));
 }
 
 #[test]
-#[should_panic(expected = "Insufficient reward pool")]
 fn test_claim_rewards_insufficient_pool() {
     let env = Env::default();
     env.mock_all_auths();
@@ -521,7 +554,8 @@ fn test_claim_rewards_insufficient_pool() {
     env.ledger().set_timestamp(30 * 24 * 60 * 60);
 
     // Try to claim (should fail - no rewards in pool)
-    client.claim_rewards(&staker);
+    let result = client.try_claim_rewards(&staker);
+    assert_eq!(result, Err(Ok(Error::InsufficientPool)));
 }
 
 #[test]
@@ -669,3 +703,751 @@ fn test_full_staking_lifecycle() {
     assert_eq!(staker_info.staked_amount, 0);
     assert_eq!(client.get_total_staked(), 0);
 }
+
+#[test]
+fn test_claim_with_shared_token_keeps_principal_covered() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let admin = Address::generate(&env);
+    let staker = Address::generate(&env);
+    let other_staker = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    // Staking and reward token are the same asset
+    let (token_addr, token_client) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    let contract_id = env.register_contract(None, StakingContract);
+    let client = StakingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &token_addr, &token_addr, &500u32, &0u64);
+
+    token_admin_client.mint(&staker, &10_000_000_000);
+    token_admin_client.mint(&other_staker, &10_000_000_000);
+    token_admin_client.mint(&admin, &1_000_000_000);
+
+    client.stake(&staker, &10_000_000_000);
+    client.stake(&other_staker, &10_000_000_000);
+    client.add_rewards(&admin, &1_000_000_000);
+
+    env.ledger().set_timestamp(30 * 24 * 60 * 60);
+
+    let claimed = client.claim_rewards(&staker);
+    assert!(claimed > 0);
+
+    // The remaining balance still covers all principal plus the reward pool
+    let balance = token_client.balance(&contract_id);
+    assert!(balance >= client.get_total_staked() + client.get_reward_pool());
+    assert!(balance - client.get_reward_pool() >= 20_000_000_000);
+
+    // The other staker can still withdraw their full principal
+    client.unstake(&other_staker, &10_000_000_000);
+    assert!(token_client.balance(&other_staker) >= 10_000_000_000);
+}
+
+#[test]
+fn test_staking_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin, staker, _, _, _, staking_admin_client, reward_admin_client) =
+        setup_staking_contract(&env);
+
+    staking_admin_client.mint(&staker, &20_000_000_000);
+    reward_admin_client.mint(&admin, &1_000_000_000);
+    client.add_rewards(&admin, &1_000_000_000);
+
+    client.stake(&staker, &10_000_000_000);
+    let (who, data) = find_event(&env, "STAKE").unwrap();
+    assert_eq!(who, staker);
+    let (amount, tier): (i128, StakingTier) =
+        <(i128, StakingTier)>::try_from_val(&env, &data).unwrap();
+    assert_eq!(amount, 10_000_000_000);
+    assert_eq!(tier, StakingTier::Silver);
+
+    env.ledger().set_timestamp(24 * 60 * 60);
+    let claimed = client.claim_rewards(&staker);
+    let (who, data) = find_event(&env, "CLAIM").unwrap();
+    assert_eq!(who, staker);
+    assert_eq!(i128::try_from_val(&env, &data).unwrap(), claimed);
+
+    // Early unstake drops to Bronze and pays a 10% penalty
+    client.unstake(&staker, &5_000_000_000);
+    let (who, data) = find_event(&env, "UNSTAKE").unwrap();
+    assert_eq!(who, staker);
+    let (amount, penalty, tier): (i128, i128, StakingTier) =
+        <(i128, i128, StakingTier)>::try_from_val(&env, &data).unwrap();
+    assert_eq!(amount, 5_000_000_000);
+    assert_eq!(penalty, 500_000_000);
+    assert_eq!(tier, StakingTier::Bronze);
+
+    let returned = client.emergency_withdraw(&staker);
+    let (who, data) = find_event(&env, "EMERG").unwrap();
+    assert_eq!(who, staker);
+    let (amount_returned, penalty): (i128, i128) =
+        <(i128, i128)>::try_from_val(&env, &data).unwrap();
+    assert_eq!(amount_returned, returned);
+    assert_eq!(penalty, 1_000_000_000);
+}
+
+#[test]
+fn test_compound_rewards_crosses_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let admin = Address::generate(&env);
+    let staker = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_addr, _) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    let contract_id = env.register_contract(None, StakingContract);
+    let client = StakingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &token_addr, &token_addr, &500u32, &0u64);
+
+    token_admin_client.mint(&staker, &9_900_000_000);
+    token_admin_client.mint(&admin, &1_000_000_000);
+    client.add_rewards(&admin, &1_000_000_000);
+
+    // Just below the Silver threshold
+    client.stake(&staker, &9_900_000_000);
+    assert_eq!(client.get_staker_info(&staker).unwrap().tier, StakingTier::Bronze);
+
+    // One year at 6% (5% base + 1% Bronze) = 594 tokens
+    env.ledger().set_timestamp(31_536_000);
+
+    let compounded = client.compound_rewards(&staker);
+    assert_eq!(compounded, 594_000_000);
+
+    let info = client.get_staker_info(&staker).unwrap();
+    assert_eq!(info.staked_amount, 10_494_000_000);
+    assert_eq!(info.tier, StakingTier::Silver);
+    assert_eq!(info.accumulated_rewards, 0);
+    assert_eq!(client.get_total_staked(), 10_494_000_000);
+    assert_eq!(client.get_reward_pool(), 406_000_000);
+
+    // Nothing left to compound
+    let result = client.try_compound_rewards(&staker);
+    assert_eq!(result, Err(Ok(Error::NoRewards)));
+}
+
+#[test]
+fn test_compound_rewards_requires_same_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin, staker, _, _, _, staking_admin_client, reward_admin_client) =
+        setup_staking_contract(&env);
+
+    staking_admin_client.mint(&staker, &1_000_000_000);
+    reward_admin_client.mint(&admin, &1_000_000_000);
+    client.add_rewards(&admin, &1_000_000_000);
+    client.stake(&staker, &1_000_000_000);
+
+    env.ledger().set_timestamp(30 * 24 * 60 * 60);
+
+    let result = client.try_compound_rewards(&staker);
+    assert_eq!(result, Err(Ok(Error::CompoundUnsupported)));
+}
+
+#[test]
+fn test_second_deposit_does_not_relock_first() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let (client, _, staker, _, staking_token_client, _, staking_admin_client, _) =
+        setup_staking_contract(&env);
+
+    staking_admin_client.mint(&staker, &10_000_000_000);
+    client.stake(&staker, &5_000_000_000);
+
+    // Second deposit made once the first has unlocked
+    let lock = 7 * 24 * 60 * 60;
+    env.ledger().set_timestamp(1000 + lock);
+    client.stake(&staker, &2_000_000_000);
+
+    let positions = client.get_positions(&staker);
+    assert_eq!(positions.len(), 2);
+    assert_eq!(positions.get(0).unwrap().stake_timestamp, 1000);
+    assert_eq!(positions.get(1).unwrap().stake_timestamp, 1000 + lock);
+
+    // The whole balance isn't unlocked yet, but the first deposit is
+    assert!(!client.can_unstake_without_penalty(&staker));
+
+    // Unstaking the first deposit's amount carries no penalty
+    client.unstake(&staker, &5_000_000_000);
+    assert_eq!(staking_token_client.balance(&staker), 8_000_000_000);
+
+    let positions = client.get_positions(&staker);
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions.get(0).unwrap().amount, 2_000_000_000);
+}
+
+#[test]
+fn test_unstake_penalizes_only_locked_portion() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let (client, _, staker, _, staking_token_client, _, staking_admin_client, _) =
+        setup_staking_contract(&env);
+
+    staking_admin_client.mint(&staker, &10_000_000_000);
+    client.stake(&staker, &4_000_000_000);
+
+    let lock = 7 * 24 * 60 * 60;
+    env.ledger().set_timestamp(1000 + lock);
+    client.stake(&staker, &4_000_000_000);
+
+    // 4,000 unlocked + 2,000 locked: 10% penalty applies to the 2,000 only
+    client.unstake(&staker, &6_000_000_000);
+    assert_eq!(
+        staking_token_client.balance(&staker),
+        2_000_000_000 + 6_000_000_000 - 200_000_000
+    );
+
+    let positions = client.get_positions(&staker);
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions.get(0).unwrap().amount, 2_000_000_000);
+    assert_eq!(client.get_staker_info(&staker).unwrap().staked_amount, 2_000_000_000);
+}
+
+#[test]
+fn test_rewards_split_by_tier_after_upgrade() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, _, staker, _, _, _, staking_admin_client, _) = setup_staking_contract(&env);
+
+    staking_admin_client.mint(&staker, &100_000_000_000);
+
+    // Bronze: 5% base + 1% bonus
+    client.stake(&staker, &1_000_000_000);
+    assert_eq!(client.get_staker_info(&staker).unwrap().tier, StakingTier::Bronze);
+
+    let half_year = 31_536_000 / 2;
+    env.ledger().set_timestamp(half_year);
+
+    // Gold: 5% base + 5% bonus
+    client.stake(&staker, &99_000_000_000);
+    assert_eq!(client.get_staker_info(&staker).unwrap().tier, StakingTier::Gold);
+
+    env.ledger().set_timestamp(2 * half_year);
+
+    // Half a year at 6% on 1,000 tokens + half a year at 10% on 100,000 tokens
+    let bronze_part = 30_000_000;
+    let gold_part = 5_000_000_000;
+    assert_eq!(client.get_pending_rewards(&staker), bronze_part + gold_part);
+}
+
+#[test]
+fn test_apy_change_settles_rewards_at_old_rate() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin, staker, _, _, _, staking_admin_client, _) = setup_staking_contract(&env);
+
+    staking_admin_client.mint(&staker, &10_000_000_000);
+    client.stake(&staker, &10_000_000_000); // Silver: 5% + 2.5%
+
+    let half_year = 31_536_000 / 2;
+    env.ledger().set_timestamp(half_year);
+
+    // Double every rate halfway through the year
+    client.update_apy_config(&admin, &1000u32, &200u32, &500u32, &1000u32);
+
+    env.ledger().set_timestamp(2 * half_year);
+
+    // 7.5% for the first half, 15% for the second
+    assert_eq!(client.get_pending_rewards(&staker), 375_000_000 + 750_000_000);
+}
+
+#[test]
+fn test_threshold_change_retiers_from_change_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin, staker, _, _, _, staking_admin_client, _) = setup_staking_contract(&env);
+    let other = Address::generate(&env);
+
+    staking_admin_client.mint(&staker, &10_000_000_000);
+    staking_admin_client.mint(&other, &1_000_000_000);
+    client.stake(&staker, &10_000_000_000); // Silver: 5% + 2.5%
+    client.stake(&other, &1_000_000_000); // Bronze: 5% + 1%
+
+    let half_year = 31_536_000 / 2;
+    env.ledger().set_timestamp(half_year);
+
+    // Raise every threshold tenfold: Silver drops to Bronze, Bronze to None
+    client.update_tier_thresholds(&admin, &10_000_000_000, &100_000_000_000, &1_000_000_000_000);
+    assert_eq!(client.get_staker_info(&staker).unwrap().tier, StakingTier::Bronze);
+    assert_eq!(client.get_staker_info(&other).unwrap().tier, StakingTier::None);
+    assert_eq!(client.get_current_apy(&staker), 600);
+
+    // A later rate change is picked up without settling anyone either
+    env.ledger().set_timestamp(3 * half_year / 2);
+    client.update_apy_config(&admin, &1000u32, &200u32, &500u32, &1000u32);
+
+    env.ledger().set_timestamp(2 * half_year);
+
+    // Silver 7.5% for half a year, Bronze 6% for a quarter, then Bronze 12% for a quarter
+    assert_eq!(
+        client.get_pending_rewards(&staker),
+        375_000_000 + 150_000_000 + 300_000_000
+    );
+    // Bronze 6% for half a year, None 5% for a quarter, then None 10% for a quarter
+    assert_eq!(
+        client.get_pending_rewards(&other),
+        30_000_000 + 12_500_000 + 25_000_000
+    );
+}
+
+#[test]
+fn test_stakers_pagination() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let (client, _, _, _, _, _, staking_admin_client, _) = setup_staking_contract(&env);
+
+    let mut stakers: Vec<Address> = Vec::new(&env);
+    for _ in 0..5 {
+        let staker = Address::generate(&env);
+        staking_admin_client.mint(&staker, &1_000_000_000);
+        client.stake(&staker, &1_000_000_000);
+        stakers.push_back(staker);
+    }
+
+    assert_eq!(client.get_staker_count(), 5);
+
+    let mut paged: Vec<Address> = Vec::new(&env);
+    let mut start = 0;
+    loop {
+        let page = client.get_stakers_paginated(&start, &2);
+        if page.is_empty() {
+            break;
+        }
+        assert!(page.len() <= 2);
+        paged.append(&page);
+        start += 2;
+    }
+    assert_eq!(paged, stakers);
+
+    // Fully unstaking drops the staker from the count and the pages
+    env.ledger().set_timestamp(1000 + 7 * 24 * 60 * 60);
+    client.unstake(&stakers.get(0).unwrap(), &1_000_000_000);
+    assert_eq!(client.get_staker_count(), 4);
+    assert_eq!(client.get_stakers_paginated(&0, &2).get(0).unwrap(), stakers.get(1).unwrap());
+    assert_eq!(client.get_stakers_paginated(&4, &2).len(), 0);
+}
+
+#[test]
+fn test_claim_available_with_short_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin, staker, _, _, reward_token_client, staking_admin_client, reward_admin_client) =
+        setup_staking_contract(&env);
+
+    staking_admin_client.mint(&staker, &10_000_000_000);
+    client.stake(&staker, &10_000_000_000); // Silver: 7.5% APY
+
+    // Pool only holds 100 tokens
+    reward_admin_client.mint(&admin, &100_000_000);
+    client.add_rewards(&admin, &100_000_000);
+
+    // One year of rewards = 750 tokens owed
+    env.ledger().set_timestamp(31_536_000);
+
+    let result = client.try_claim_rewards(&staker);
+    assert_eq!(result, Err(Ok(Error::InsufficientPool)));
+
+    let paid = client.claim_available(&staker);
+    assert_eq!(paid, 100_000_000);
+    assert_eq!(reward_token_client.balance(&staker), 100_000_000);
+    assert_eq!(client.get_reward_pool(), 0);
+
+    let info = client.get_staker_info(&staker).unwrap();
+    assert_eq!(info.accumulated_rewards, 650_000_000);
+    assert_eq!(client.get_pending_rewards(&staker), 650_000_000);
+
+    // Nothing more can be paid until the pool is topped up
+    let result = client.try_claim_available(&staker);
+    assert_eq!(result, Err(Ok(Error::InsufficientPool)));
+}
+
+#[test]
+fn test_paused_user_experience() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin, staker, _, staking_token_client, _, staking_admin_client, reward_admin_client) =
+        setup_staking_contract(&env);
+
+    staking_admin_client.mint(&staker, &10_000_000_000);
+    reward_admin_client.mint(&admin, &1_000_000_000);
+    client.add_rewards(&admin, &1_000_000_000);
+
+    client.stake(&staker, &4_000_000_000);
+
+    // Second deposit is still locked when the contract is paused
+    let lock = 7 * 24 * 60 * 60;
+    env.ledger().set_timestamp(lock);
+    client.stake(&staker, &4_000_000_000);
+
+    env.ledger().set_timestamp(lock + 24 * 60 * 60);
+    client.set_paused(&admin, &true);
+
+    // Staking is blocked
+    assert_eq!(client.try_stake(&staker, &1_000_000_000), Err(Ok(Error::Paused)));
+
+    // Claims are blocked unless the policy allows them
+    assert_eq!(client.try_claim_rewards(&staker), Err(Ok(Error::Paused)));
+    assert_eq!(client.try_claim_available(&staker), Err(Ok(Error::Paused)));
+
+    // Unlocked amounts can be unstaked, locked amounts cannot
+    assert_eq!(client.try_unstake(&staker, &5_000_000_000), Err(Ok(Error::Paused)));
+    client.unstake(&staker, &4_000_000_000);
+    assert_eq!(staking_token_client.balance(&staker), 6_000_000_000);
+
+    // Enabling paused claims lets rewards be withdrawn
+    client.set_pause_policy(&admin, &true, &true);
+    assert!(client.claim_rewards(&staker) > 0);
+
+    // Disabling paused unstakes blocks even unlocked amounts
+    client.set_pause_policy(&admin, &true, &false);
+    env.ledger().set_timestamp(2 * lock);
+    assert_eq!(client.try_unstake(&staker, &1_000_000_000), Err(Ok(Error::Paused)));
+
+    // Emergency withdrawal always works
+    let returned = client.emergency_withdraw(&staker);
+    assert_eq!(returned, 3_200_000_000);
+}
+
+#[test]
+fn test_long_lock_earns_bonus() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin, short_staker, _, _, _, staking_admin_client, _) =
+        setup_staking_contract(&env);
+    let long_staker = Address::generate(&env);
+
+    // +2% APY for locking 90 days or more
+    let ninety_days = 90 * 24 * 60 * 60u64;
+    client.set_lock_bonus(&admin, &ninety_days, &200u32);
+
+    staking_admin_client.mint(&short_staker, &1_000_000_000);
+    staking_admin_client.mint(&long_staker, &1_000_000_000);
+
+    client.stake(&short_staker, &1_000_000_000);
+    client.stake_with_lock(&long_staker, &1_000_000_000, &ninety_days);
+
+    assert_eq!(client.get_lock_bonus(&short_staker), 0);
+    assert_eq!(client.get_lock_bonus(&long_staker), 200);
+
+    env.ledger().set_timestamp(73 * 24 * 60 * 60);
+
+    // Bronze 6% vs Bronze 6% + 2% lock bonus, over a fifth of a year
+    assert_eq!(client.get_pending_rewards(&short_staker), 12_000_000);
+    assert_eq!(client.get_pending_rewards(&long_staker), 16_000_000);
+
+    // Locks shorter than the minimum are rejected
+    let result = client.try_stake_with_lock(&long_staker, &1, &60u64);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+}
+
+#[test]
+fn test_lock_bonus_stops_when_lock_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin, staker, _, _, reward_token_client, staking_admin_client, reward_admin_client) =
+        setup_staking_contract(&env);
+
+    // +2% APY for locking 73 days (a fifth of a year) or more
+    let lock = 73 * 24 * 60 * 60u64;
+    client.set_lock_bonus(&admin, &lock, &200u32);
+
+    staking_admin_client.mint(&staker, &1_000_000_000);
+    reward_admin_client.mint(&admin, &1_000_000_000);
+    client.add_rewards(&admin, &1_000_000_000);
+    client.stake_with_lock(&staker, &1_000_000_000, &lock);
+
+    env.ledger().set_timestamp(31_536_000);
+    assert_eq!(client.get_lock_bonus(&staker), 0);
+
+    // A full year at Bronze 6%, plus the 2% bonus for the 73 locked days only
+    let claimed = client.claim_rewards(&staker);
+    assert_eq!(claimed, 60_000_000 + 4_000_000);
+    assert_eq!(reward_token_client.balance(&staker), claimed);
+
+    // No further bonus accrues after the lock
+    env.ledger().set_timestamp(2 * 31_536_000);
+    assert_eq!(client.get_pending_rewards(&staker), 60_000_000);
+}
+
+#[test]
+fn test_long_lock_enforced_on_unstake() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, _, staker, _, staking_token_client, _, staking_admin_client, _) =
+        setup_staking_contract(&env);
+
+    staking_admin_client.mint(&staker, &1_000_000_000);
+
+    let thirty_days = 30 * 24 * 60 * 60u64;
+    client.stake_with_lock(&staker, &1_000_000_000, &thirty_days);
+
+    // Past the 7 day minimum but inside the chosen lock: still penalized
+    env.ledger().set_timestamp(10 * 24 * 60 * 60);
+    assert!(!client.can_unstake_without_penalty(&staker));
+    client.unstake(&staker, &500_000_000);
+    assert_eq!(staking_token_client.balance(&staker), 450_000_000);
+
+    env.ledger().set_timestamp(thirty_days);
+    assert!(client.can_unstake_without_penalty(&staker));
+    client.unstake(&staker, &500_000_000);
+    assert_eq!(staking_token_client.balance(&staker), 950_000_000);
+}
+
+#[test]
+fn test_admin_transfer_two_step() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _, _, _, _, _) = setup_staking_contract(&env);
+    let new_admin = Address::generate(&env);
+
+    client.transfer_admin(&admin, &new_admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+
+    // Nothing changes until the nominee accepts
+    assert_eq!(client.get_config().admin, admin);
+
+    client.accept_admin(&new_admin);
+    assert_eq!(client.get_config().admin, new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+
+    // The old admin has lost its rights
+    let result = client.try_set_paused(&admin, &true);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    client.set_paused(&new_admin, &true);
+}
+
+#[test]
+fn test_admin_transfer_rejections() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, staker, _, _, _, _, _) = setup_staking_contract(&env);
+    let new_admin = Address::generate(&env);
+
+    // Non-admin cannot nominate
+    let result = client.try_transfer_admin(&staker, &new_admin);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    // Only the nominee can accept
+    client.transfer_admin(&admin, &new_admin);
+    let result = client.try_accept_admin(&staker);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    assert_eq!(client.get_config().admin, admin);
+}
+
+#[test]
+fn test_slash_partial_to_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin, staker, _, staking_token_client, _, staking_admin_client, _) =
+        setup_staking_contract(&env);
+    let treasury = Address::generate(&env);
+
+    staking_admin_client.mint(&staker, &12_000_000_000);
+    client.stake(&staker, &12_000_000_000);
+    assert_eq!(client.get_staker_info(&staker).unwrap().tier, StakingTier::Silver);
+
+    // Without a treasury, different staking and reward tokens can't absorb slashed stake
+    let result = client.try_slash(&admin, &staker, &2_500);
+    assert_eq!(result, Err(Ok(Error::NoSlashTreasury)));
+
+    client.set_slash_treasury(&admin, &treasury);
+
+    // 25% slash drops the staker below the Silver threshold
+    let slashed = client.slash(&admin, &staker, &2_500);
+    assert_eq!(slashed, 3_000_000_000);
+
+    let info = client.get_staker_info(&staker).unwrap();
+    assert_eq!(info.staked_amount, 9_000_000_000);
+    assert_eq!(info.tier, StakingTier::Bronze);
+    assert_eq!(client.get_total_staked(), 9_000_000_000);
+    assert_eq!(client.get_positions(&staker).get(0).unwrap().amount, 9_000_000_000);
+    assert_eq!(staking_token_client.balance(&treasury), 3_000_000_000);
+    assert_eq!(client.get_all_stakers().len(), 1);
+
+    let (event_staker, data) = find_event(&env, "SLASH").unwrap();
+    assert_eq!(event_staker, staker);
+    let (amount, bps, tier) = <(i128, u32, StakingTier)>::try_from_val(&env, &data).unwrap();
+    assert_eq!((amount, bps, tier), (3_000_000_000, 2_500, StakingTier::Bronze));
+
+    // More than 100% is rejected
+    let result = client.try_slash(&admin, &staker, &10_001);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+}
+
+#[test]
+fn test_slash_full_moves_stake_to_reward_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let admin = Address::generate(&env);
+    let staker = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_addr, _) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    let contract_id = env.register_contract(None, StakingContract);
+    let client = StakingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &token_addr, &token_addr, &500u32, &0u64);
+
+    token_admin_client.mint(&staker, &2_000_000_000);
+    client.stake(&staker, &2_000_000_000);
+
+    let slashed = client.slash(&admin, &staker, &10_000);
+    assert_eq!(slashed, 2_000_000_000);
+
+    let info = client.get_staker_info(&staker).unwrap();
+    assert_eq!(info.staked_amount, 0);
+    assert_eq!(info.tier, StakingTier::None);
+    assert_eq!(client.get_total_staked(), 0);
+    assert_eq!(client.get_reward_pool(), 2_000_000_000);
+    assert_eq!(client.get_positions(&staker).len(), 0);
+    assert_eq!(client.get_all_stakers().len(), 0);
+    assert_eq!(client.get_staker_count(), 0);
+
+    // Nothing left to slash
+    let result = client.try_slash(&admin, &staker, &5_000);
+    assert_eq!(result, Err(Ok(Error::NotStaked)));
+}
+
+#[test]
+fn test_slash_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, staker, _, _, _, staking_admin_client, _) = setup_staking_contract(&env);
+    let attacker = Address::generate(&env);
+
+    staking_admin_client.mint(&staker, &1_000_000_000);
+    client.stake(&staker, &1_000_000_000);
+
+    let result = client.try_slash(&attacker, &staker, &5_000);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    assert_eq!(client.get_staker_info(&staker).unwrap().staked_amount, 1_000_000_000);
+}
+
+#[test]
+fn test_claim_cannot_draw_down_principal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let admin = Address::generate(&env);
+    let staker = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let sink = Address::generate(&env);
+
+    // Staking and reward token are the same asset
+    let (token_addr, token_client) = create_token_contract(&env, &token_admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_addr);
+
+    let contract_id = env.register_contract(None, StakingContract);
+    let client = StakingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &token_addr, &token_addr, &500u32, &0u64);
+
+    token_admin_client.mint(&staker, &10_000_000_000);
+    token_admin_client.mint(&admin, &1_000_000_000);
+    client.stake(&staker, &10_000_000_000);
+    client.add_rewards(&admin, &1_000_000_000);
+    assert_eq!(client.get_solvency(), (11_000_000_000, 10_000_000_000));
+
+    env.ledger().set_timestamp(30 * 24 * 60 * 60);
+
+    // A normal claim keeps the balance above total staked
+    let claimed = client.claim_rewards(&staker);
+    let (balance, total_staked) = client.get_solvency();
+    assert_eq!(balance, 11_000_000_000 - claimed);
+    assert!(balance >= total_staked);
+
+    // Simulate a deployment where the reward pool is no longer backed by tokens
+    token_client.transfer(&contract_id, &sink, &(balance - total_staked));
+    assert_eq!(client.get_solvency(), (10_000_000_000, 10_000_000_000));
+
+    env.ledger().set_timestamp(60 * 24 * 60 * 60);
+
+    let result = client.try_claim_rewards(&staker);
+    assert_eq!(result, Err(Ok(Error::PoolInvariantViolated)));
+    let result = client.try_claim_available(&staker);
+    assert_eq!(result, Err(Ok(Error::PoolInvariantViolated)));
+    assert_eq!(client.get_solvency(), (10_000_000_000, 10_000_000_000));
+}
+
+#[test]
+fn test_staker_summary_matches_getters() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin, staker, _, _, _, staking_admin_client, reward_admin_client) =
+        setup_staking_contract(&env);
+
+    let thirty_days = 30 * 24 * 60 * 60u64;
+    client.set_lock_bonus(&admin, &thirty_days, &200);
+
+    staking_admin_client.mint(&staker, &12_000_000_000);
+    reward_admin_client.mint(&admin, &1_000_000_000);
+    client.add_rewards(&admin, &1_000_000_000);
+    client.stake(&staker, &10_000_000_000);
+    client.stake_with_lock(&staker, &2_000_000_000, &thirty_days);
+
+    env.ledger().set_timestamp(10 * 24 * 60 * 60);
+
+    let summary = client.get_staker_summary(&staker);
+    let info = client.get_staker_info(&staker).unwrap();
+    assert_eq!(summary.staked_amount, info.staked_amount);
+    assert_eq!(summary.tier, info.tier);
+    assert_eq!(summary.tier, StakingTier::Silver);
+    assert_eq!(summary.pending_rewards, client.get_pending_rewards(&staker));
+    assert!(summary.pending_rewards > 0);
+    assert_eq!(summary.current_apy, client.get_current_apy(&staker));
+    assert_eq!(summary.lock_bonus, client.get_lock_bonus(&staker));
+    assert_eq!(summary.time_until_unlock, client.get_time_until_unlock(&staker));
+    assert_eq!(summary.time_until_unlock, 20 * 24 * 60 * 60);
+    assert_eq!(summary.can_unstake_without_penalty, client.can_unstake_without_penalty(&staker));
+    assert!(!summary.can_unstake_without_penalty);
+
+    // Accounts that never staked get an empty summary
+    let empty = client.get_staker_summary(&Address::generate(&env));
+    assert_eq!(empty.staked_amount, 0);
+    assert_eq!(empty.tier, StakingTier::None);
+    assert_eq!(empty.pending_rewards, 0);
+    assert!(empty.can_unstake_without_penalty);
+}
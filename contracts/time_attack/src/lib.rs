@@ -1,7 +1,8 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, Address,
+    BytesN, Env, Vec,
 };
 
 #[cfg(test)]
@@ -22,6 +23,7 @@ pub enum Scope {
 pub enum TimePeriod {
     Daily,
     Weekly,
+    Monthly,
     AllTime,
 }
 
@@ -33,6 +35,18 @@ pub enum DataKey {
     Best(Scope, TimePeriod),
     Board(Scope, TimePeriod),
     LastReset(Scope, TimePeriod),
+    GlobalBest, // (puzzle_id, TimeRecord) fastest across every puzzle
+    Leaderboard, // (leaderboard contract, score base in ms) fed on every submission
+    PuzzleBounds(u32), // (min_ms, max_ms) accepted completion times for a puzzle
+    Puzzle(u32), // registered puzzle ids accepting submissions
+    PlayerBest(Address, Scope), // a player's fastest TimeRecord in a scope
+    SubmitInterval, // minimum seconds between a player's submissions
+}
+
+/// The part of the Leaderboard contract used to report scores
+#[contractclient(name = "LeaderboardClient")]
+pub trait LeaderboardInterface {
+    fn submit_score(env: Env, submitter: Address, player: Address, score: i128);
 }
 
 /// Custom error codes for the contract
@@ -46,7 +60,10 @@ pub enum Error {
     TooFrequent = 4,
     DuplicateReplay = 5,
     ContractNotInitialized = 6,
-    // NOTE(MVP): `InvalidPuzzleId` intentionally omitted until puzzle-id validation rules are defined.
+    /// Puzzle id is neither 0 (global) nor registered by the admin
+    InvalidPuzzleId = 7,
+    /// Submit interval is longer than the network's maximum entry TTL
+    InvalidInterval = 8,
 }
 
 /// A single player completion record for a puzzle run submission.
@@ -75,6 +92,12 @@ pub struct TimeAttack;
 const LEDGER_THRESHOLD_SHARED: u32 = 518_400; // ~30 days @ 5s/ledger
 const LEDGER_BUMP_SHARED: u32 = 1_036_800; // ~60 days @ 5s/ledger
 
+const DEFAULT_SUBMIT_INTERVAL_S: u64 = 5;
+
+// Accepted completion times for puzzles without their own bounds
+const MIN_REASONABLE_TIME_MS: u64 = 1_000; // 1s
+const MAX_REASONABLE_TIME_MS: u64 = 60 * 60 * 1000; // 1h
+
 #[contractimpl]
 impl TimeAttack {
     fn bump_persistent_ttl(env: &Env, key: &DataKey) {
@@ -95,10 +118,138 @@ impl TimeAttack {
 
         // Store the admin address in contract storage
         storage.set(&DataKey::Admin, &admin);
+        storage.set(&DataKey::SubmitInterval, &DEFAULT_SUBMIT_INTERVAL_S);
+
+        Ok(())
+    }
+
+    /// Report every submission to a Leaderboard contract, or stop reporting
+    ///
+    /// # Arguments
+    /// * `admin` - The stored admin
+    /// * `leaderboard` - Leaderboard contract, or None to run standalone.
+    ///   This contract must be an authorized verifier there; reports it
+    ///   refuses are skipped with an `LB_FAIL` event.
+    /// * `score_base_ms` - A run scores `score_base_ms - completion_time_ms`,
+    ///   floored at zero
+    pub fn set_leaderboard(
+        env: Env,
+        admin: Address,
+        leaderboard: Option<Address>,
+        score_base_ms: u64,
+    ) -> Result<(), Error> {
+        Self::assert_admin(&env, &admin)?;
+
+        match leaderboard {
+            Some(leaderboard) => env
+                .storage()
+                .instance()
+                .set(&DataKey::Leaderboard, &(leaderboard, score_base_ms)),
+            None => env.storage().instance().remove(&DataKey::Leaderboard),
+        }
+
+        Ok(())
+    }
+
+    /// Set the minimum number of seconds between a player's submissions
+    ///
+    /// The rate-limit marker lives for one ledger per second of the interval,
+    /// so the interval can't exceed the network's maximum TTL.
+    pub fn set_submit_interval(env: Env, admin: Address, seconds: u64) -> Result<(), Error> {
+        Self::assert_admin(&env, &admin)?;
+        if seconds > env.storage().max_ttl() as u64 {
+            return Err(Error::InvalidInterval);
+        }
+        env.storage().instance().set(&DataKey::SubmitInterval, &seconds);
+        Ok(())
+    }
+
+    /// Get the minimum number of seconds between a player's submissions
+    pub fn get_submit_interval(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SubmitInterval)
+            .unwrap_or(DEFAULT_SUBMIT_INTERVAL_S)
+    }
+
+    /// Register a puzzle id so times can be submitted for it
+    ///
+    /// Puzzle 0 is the global scope and needs no registration.
+    pub fn register_puzzle(env: Env, admin: Address, puzzle_id: u32) -> Result<(), Error> {
+        Self::assert_admin(&env, &admin)?;
+
+        if puzzle_id == 0 {
+            return Err(Error::InvalidPuzzleId);
+        }
+
+        let key = DataKey::Puzzle(puzzle_id);
+        env.storage().persistent().set(&key, &true);
+        Self::bump_persistent_ttl(&env, &key);
+
+        Ok(())
+    }
+
+    /// Whether times can be submitted for a puzzle id (always true for 0)
+    pub fn is_puzzle_registered(env: Env, puzzle_id: u32) -> bool {
+        if puzzle_id == 0 {
+            return true;
+        }
+
+        let key = DataKey::Puzzle(puzzle_id);
+        let registered = env.storage().persistent().has(&key);
+        if registered {
+            Self::bump_persistent_ttl(&env, &key);
+        }
+        registered
+    }
+
+    /// Set the accepted completion time range for one puzzle, overriding the
+    /// global 1s..=1h bounds
+    ///
+    /// # Errors
+    /// - `NotAuthorized`: Caller is not the admin
+    /// - `InvalidTime`: `min_ms` is 0 or greater than `max_ms`
+    pub fn set_puzzle_bounds(
+        env: Env,
+        admin: Address,
+        puzzle_id: u32,
+        min_ms: u64,
+        max_ms: u64,
+    ) -> Result<(), Error> {
+        Self::assert_admin(&env, &admin)?;
+
+        if min_ms == 0 || min_ms > max_ms {
+            return Err(Error::InvalidTime);
+        }
+
+        let key = DataKey::PuzzleBounds(puzzle_id);
+        env.storage().persistent().set(&key, &(min_ms, max_ms));
+        Self::bump_persistent_ttl(&env, &key);
+
+        Ok(())
+    }
 
+    /// Get the accepted `(min_ms, max_ms)` completion times for a puzzle
+    pub fn get_puzzle_bounds(env: Env, puzzle_id: u32) -> (u64, u64) {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PuzzleBounds(puzzle_id))
+            .unwrap_or((MIN_REASONABLE_TIME_MS, MAX_REASONABLE_TIME_MS))
+    }
+
+    fn assert_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+        if *admin != Self::get_admin(env.clone()) {
+            return Err(Error::NotAuthorized);
+        }
         Ok(())
     }
 
+    /// Get the Leaderboard contract and score base, if reporting is enabled
+    pub fn get_leaderboard_contract(env: Env) -> Option<(Address, u64)> {
+        env.storage().instance().get(&DataKey::Leaderboard)
+    }
+
     /// Submit a puzzle completion time
     ///
     /// # Arguments
@@ -113,6 +264,7 @@ impl TimeAttack {
     /// * `Err(Error)` - Submission failed validation
     ///
     /// # Errors
+    /// - `InvalidPuzzleId`: Puzzle id is not 0 and has not been registered
     /// - `InvalidTime`: Completion time is 0 or unreasonably high
     /// - `TooFrequent`: Player submitted too recently (rate limiting)
     /// - `DuplicateReplay`: Replay hash has been used before
@@ -130,11 +282,22 @@ impl TimeAttack {
         // Require authentication from the player
         player.require_auth();
 
+        if puzzle_id != 0 && !Self::is_puzzle_registered(env.clone(), puzzle_id) {
+            return Err(Error::InvalidPuzzleId);
+        }
+
         // Get current ledger timestamp (seconds)
         let timestamp = env.ledger().timestamp();
 
         // Validate the submission
-        Self::verify_submission(&env, &player, completion_time_ms, &replay_hash, timestamp)?;
+        Self::verify_submission(
+            &env,
+            &player,
+            puzzle_id,
+            completion_time_ms,
+            &replay_hash,
+            timestamp,
+        )?;
 
         // Create the time record
         let record = TimeRecord {
@@ -151,36 +314,59 @@ impl TimeAttack {
             Scope::Puzzle(puzzle_id)
         };
 
-        // Check and reset leaderboards if needed (daily/weekly)
+        // Check and reset leaderboards if needed (daily/weekly/monthly)
         Self::check_and_reset_leaderboards(&env, scope, timestamp);
 
         // Update leaderboards for all time periods
         Self::update_leaderboard(&env, scope, TimePeriod::AllTime, &record)?;
         Self::update_leaderboard(&env, scope, TimePeriod::Daily, &record)?;
         Self::update_leaderboard(&env, scope, TimePeriod::Weekly, &record)?;
+        Self::update_leaderboard(&env, scope, TimePeriod::Monthly, &record)?;
 
         // Update all-time best for this scope (global or per-puzzle)
         Self::update_alltime_best(&env, scope, &record);
 
-        // Mark this submission timestamp for rate limiting (temporary storage)
+        // Update the fastest time across all puzzles
+        Self::update_overall_best(&env, puzzle_id, &record);
+
+        // Update this player's personal best for the scope
+        Self::update_player_best(&env, scope, &record);
+
+        // Mark this submission timestamp for rate limiting (temporary storage).
+        // Ledgers close no faster than one per second, so a TTL of `interval`
+        // ledgers keeps the marker for at least the whole interval.
+        let max_ttl = env.storage().max_ttl();
+        let interval = Self::get_submit_interval(env.clone()).min(max_ttl as u64) as u32;
         env.storage()
             .temporary()
             .set(&DataKey::LastSubmit(player.clone()), &timestamp);
         env.storage().temporary().extend_ttl(
             &DataKey::LastSubmit(player.clone()),
-            300, // threshold (seconds)
-            300, // extend_to (seconds)
+            interval, // threshold (ledgers)
+            interval, // extend_to (ledgers)
         );
 
-        // Mark replay hash as used (temporary storage, expires after 24 hours)
-        env.storage()
-            .temporary()
-            .set(&DataKey::ReplayUsed(replay_hash.clone()), &true);
-        env.storage().temporary().extend_ttl(
-            &DataKey::ReplayUsed(replay_hash),
-            86400, // threshold (seconds)
-            86400, // extend_to (seconds)
-        );
+        // Burn the replay hash for good (persistent storage)
+        let replay_key = DataKey::ReplayUsed(replay_hash);
+        env.storage().persistent().set(&replay_key, &true);
+        Self::bump_persistent_ttl(&env, &replay_key);
+
+        // Award leaderboard points: the faster the run, the higher the score.
+        // A failing Leaderboard doesn't block the submission itself.
+        if let Some((leaderboard, score_base_ms)) = Self::get_leaderboard_contract(env.clone()) {
+            let score = score_base_ms.saturating_sub(completion_time_ms) as i128;
+            let reported = LeaderboardClient::new(&env, &leaderboard).try_submit_score(
+                &env.current_contract_address(),
+                &player,
+                &score,
+            );
+            if reported.is_err() {
+                env.events().publish(
+                    (symbol_short!("LB_FAIL"), player.clone()),
+                    (leaderboard, score),
+                );
+            }
+        }
 
         // Emit event for off-chain indexing (optional but recommended)
         env.events().publish(
@@ -194,15 +380,13 @@ impl TimeAttack {
     fn verify_submission(
         env: &Env,
         player: &Address,
+        puzzle_id: u32,
         completion_time_ms: u64,
         replay_hash: &BytesN<32>,
         timestamp: u64,
     ) -> Result<(), Error> {
-        const MIN_REASONABLE_TIME_MS: u64 = 1_000; // 1s
-        const MAX_REASONABLE_TIME_MS: u64 = 60 * 60 * 1000; // 1h
-        const MIN_SUBMIT_INTERVAL_S: u64 = 5;
-
-        if !(MIN_REASONABLE_TIME_MS..=MAX_REASONABLE_TIME_MS).contains(&completion_time_ms) {
+        let (min_ms, max_ms) = Self::get_puzzle_bounds(env.clone(), puzzle_id);
+        if !(min_ms..=max_ms).contains(&completion_time_ms) {
             return Err(Error::InvalidTime);
         }
 
@@ -211,16 +395,12 @@ impl TimeAttack {
             .temporary()
             .get::<_, u64>(&DataKey::LastSubmit(player.clone()))
         {
-            if timestamp.saturating_sub(last) < MIN_SUBMIT_INTERVAL_S {
+            if timestamp.saturating_sub(last) < Self::get_submit_interval(env.clone()) {
                 return Err(Error::TooFrequent);
             }
         }
 
-        if let Some(true) = env
-            .storage()
-            .temporary()
-            .get::<_, bool>(&DataKey::ReplayUsed(replay_hash.clone()))
-        {
+        if Self::was_replay_used(env.clone(), replay_hash.clone()) {
             return Err(Error::DuplicateReplay);
         }
 
@@ -230,6 +410,7 @@ impl TimeAttack {
     fn check_and_reset_leaderboards(env: &Env, scope: Scope, current_timestamp: u64) {
         Self::maybe_reset_period(env, scope, TimePeriod::Daily, 86_400, current_timestamp);
         Self::maybe_reset_period(env, scope, TimePeriod::Weekly, 604_800, current_timestamp);
+        Self::maybe_reset_period(env, scope, TimePeriod::Monthly, 2_592_000, current_timestamp);
     }
 
     fn maybe_reset_period(
@@ -256,8 +437,14 @@ impl TimeAttack {
 
         // Use saturating_sub to avoid underflow in weird timestamp scenarios.
         if current_timestamp.saturating_sub(last_reset) >= duration_seconds {
-            // Clear the leaderboard.
+            // Clear the leaderboard, noting how many entries it held.
             let board_key = DataKey::Board(scope, period);
+            let prior_len = env
+                .storage()
+                .persistent()
+                .get::<_, Vec<TimeRecord>>(&board_key)
+                .map(|board| board.len())
+                .unwrap_or(0);
             env.storage()
                 .persistent()
                 .set(&board_key, &Vec::<TimeRecord>::new(env));
@@ -273,8 +460,10 @@ impl TimeAttack {
                 .set(&last_reset_key, &current_timestamp);
             Self::bump_persistent_ttl(env, &last_reset_key);
 
-            env.events()
-                .publish((symbol_short!("LB_RESET"), scope, period), current_timestamp);
+            env.events().publish(
+                (symbol_short!("LB_RESET"), scope, period),
+                (current_timestamp, prior_len),
+            );
         }
     }
 
@@ -345,6 +534,76 @@ impl TimeAttack {
         }
     }
 
+    fn update_overall_best(env: &Env, puzzle_id: u32, record: &TimeRecord) {
+        let key = DataKey::GlobalBest;
+
+        let current: Option<(u32, TimeRecord)> = env.storage().persistent().get(&key);
+
+        let should_update = match current {
+            None => true,
+            Some((_, best)) => record.completion_time_ms < best.completion_time_ms,
+        };
+
+        if should_update {
+            env.storage().persistent().set(&key, &(puzzle_id, record.clone()));
+            Self::bump_persistent_ttl(env, &key);
+        }
+    }
+
+    fn update_player_best(env: &Env, scope: Scope, record: &TimeRecord) {
+        let key = DataKey::PlayerBest(record.player.clone(), scope);
+
+        let current: Option<TimeRecord> = env.storage().persistent().get(&key);
+
+        let should_update = match current {
+            None => true,
+            Some(best) => record.completion_time_ms < best.completion_time_ms,
+        };
+
+        if should_update {
+            env.storage().persistent().set(&key, record);
+        }
+        Self::bump_persistent_ttl(env, &key);
+    }
+
+    /// Get a player's fastest time for a scope
+    ///
+    /// # Arguments
+    /// * `player` - The player to look up
+    /// * `puzzle_id` - Puzzle ID (0 for global)
+    ///
+    /// # Returns
+    /// The player's best time record, or None if they have not submitted
+    pub fn get_player_best(env: Env, player: Address, puzzle_id: u32) -> Option<TimeRecord> {
+        let scope = if puzzle_id == 0 {
+            Scope::Global
+        } else {
+            Scope::Puzzle(puzzle_id)
+        };
+
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlayerBest(player, scope))
+    }
+
+    /// Whether a replay hash has already been submitted
+    pub fn was_replay_used(env: Env, replay_hash: BytesN<32>) -> bool {
+        let key = DataKey::ReplayUsed(replay_hash);
+        let used = env.storage().persistent().has(&key);
+        if used {
+            Self::bump_persistent_ttl(&env, &key);
+        }
+        used
+    }
+
+    /// Get the fastest time submitted for any puzzle
+    ///
+    /// # Returns
+    /// `(puzzle_id, record)` of the overall best, or None if no records exist
+    pub fn get_overall_best(env: Env) -> Option<(u32, TimeRecord)> {
+        env.storage().persistent().get(&DataKey::GlobalBest)
+    }
+
     /// Get the best time for a scope
     ///
     /// # Arguments
@@ -364,12 +623,34 @@ impl TimeAttack {
         env.storage().persistent().get(&best_key)
     }
 
+    /// Get when a scope's board for a period was last reset
+    ///
+    /// # Arguments
+    /// * `puzzle_id` - Puzzle ID (0 for global)
+    /// * `period` - Time period (Daily/Weekly/Monthly)
+    ///
+    /// # Returns
+    /// Ledger timestamp of the last reset (the first submission starts the
+    /// first period), or 0 if the board has never been used
+    pub fn get_last_reset(env: Env, puzzle_id: u32, period: TimePeriod) -> u64 {
+        let scope = if puzzle_id == 0 {
+            Scope::Global
+        } else {
+            Scope::Puzzle(puzzle_id)
+        };
+
+        env.storage()
+            .persistent()
+            .get(&DataKey::LastReset(scope, period))
+            .unwrap_or(0)
+    }
+
     /// Get leaderboard for a specific scope and period
     ///
     /// # Arguments
     /// * `env` - Contract environment
     /// * `puzzle_id` - Puzzle ID (0 for global)
-    /// * `period` - Time period (Daily/Weekly/AllTime)
+    /// * `period` - Time period (Daily/Weekly/Monthly/AllTime)
     ///
     /// # Returns
     /// Vector of time records, ordered by fastest time
@@ -395,6 +676,28 @@ impl TimeAttack {
         board
     }
 
+    /// Get a player's position on a board
+    ///
+    /// # Arguments
+    /// * `player` - The player to look up
+    /// * `puzzle_id` - Puzzle ID (0 for global)
+    /// * `period` - Time period (Daily/Weekly/Monthly/AllTime)
+    ///
+    /// # Returns
+    /// 1-indexed rank of the player's fastest entry, or 0 if they are not on the board
+    pub fn get_player_rank(env: Env, player: Address, puzzle_id: u32, period: TimePeriod) -> u32 {
+        let board = Self::get_leaderboard(env, puzzle_id, period);
+
+        // Boards are sorted fastest first, so the first match is the player's best
+        for (i, record) in board.iter().enumerate() {
+            if record.player == player {
+                return i as u32 + 1;
+            }
+        }
+
+        0
+    }
+
     /// Pure mapping: completion time (ms) -> bracket (no storage).
     pub fn get_time_bracket(_env: Env, completion_time_ms: u64) -> TimeBracket {
         Self::time_to_bracket(completion_time_ms)
@@ -430,8 +733,8 @@ mod test {
     // - cargo clippy --all-targets -p time_attack -- -D warnings
     use super::*;
     use soroban_sdk::{
-        testutils::{Address as _, Ledger},
-        Address, BytesN, Env,
+        testutils::{Address as _, Events, Ledger},
+        Address, BytesN, Env, Symbol, TryFromVal,
     };
 
     #[test]
@@ -463,6 +766,7 @@ mod test {
         // Initialize
         let admin = Address::generate(&env);
         client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
 
         // Submit a time (will panic if it fails)
         let player = Address::generate(&env);
@@ -488,6 +792,7 @@ mod test {
 
         let admin = Address::generate(&env);
         client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
 
         let player = Address::generate(&env);
         let replay_hash = BytesN::from_array(&env, &[1u8; 32]);
@@ -512,6 +817,7 @@ mod test {
 
         let admin = Address::generate(&env);
         client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
 
         let player = Address::generate(&env);
         let completion_time = 120_000u64;
@@ -536,6 +842,7 @@ mod test {
 
         let admin = Address::generate(&env);
         client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
 
         let player1 = Address::generate(&env);
         let player2 = Address::generate(&env);
@@ -560,6 +867,7 @@ mod test {
 
         let admin = Address::generate(&env);
         client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
 
         // Submit multiple times with different speeds
         let player1 = Address::generate(&env);
@@ -610,6 +918,7 @@ mod test {
 
         let admin = Address::generate(&env);
         client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
 
         let player1 = Address::generate(&env);
         let player2 = Address::generate(&env);
@@ -648,4 +957,472 @@ mod test {
         let alltime_board = client.get_leaderboard(&1u32, &TimePeriod::AllTime);
         assert_eq!(alltime_board.len(), 2);
     }
+
+    #[test]
+    fn test_overall_best_across_puzzles() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
+        client.register_puzzle(&admin, &2u32);
+
+        assert_eq!(client.get_overall_best(), None);
+
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+
+        client.submit_time(
+            &player1,
+            &1u32,
+            &90_000u64,
+            &BytesN::from_array(&env, &[1u8; 32]),
+        );
+        client.submit_time(
+            &player2,
+            &2u32,
+            &45_000u64,
+            &BytesN::from_array(&env, &[2u8; 32]),
+        );
+
+        env.ledger().with_mut(|li| li.timestamp += 61);
+
+        // Slower than the overall record, but a personal best on puzzle 1
+        client.submit_time(
+            &player1,
+            &1u32,
+            &60_000u64,
+            &BytesN::from_array(&env, &[3u8; 32]),
+        );
+
+        let (puzzle_id, record) = client.get_overall_best().unwrap();
+        assert_eq!(puzzle_id, 2);
+        assert_eq!(record.player, player2);
+        assert_eq!(record.completion_time_ms, 45_000);
+        assert_eq!(client.get_best_time(&1u32).unwrap().completion_time_ms, 60_000);
+    }
+
+    #[test]
+    fn test_submission_feeds_leaderboard() {
+        use leaderboard::{LeaderboardContract, LeaderboardContractClient};
+
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+        let leaderboard_id = env.register_contract(None, LeaderboardContract);
+        let leaderboard = LeaderboardContractClient::new(&env, &leaderboard_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
+        leaderboard.initialize(&admin, &10);
+        leaderboard.add_verifier(&admin, &contract_id);
+
+        client.set_leaderboard(&admin, &Some(leaderboard_id.clone()), &600_000);
+        assert_eq!(client.get_leaderboard_contract(), Some((leaderboard_id, 600_000)));
+
+        let player = Address::generate(&env);
+        client.submit_time(&player, &1, &120_000, &BytesN::from_array(&env, &[1u8; 32]));
+        assert_eq!(leaderboard.get_player_all_time_total(&player), 480_000);
+
+        // Runs slower than the base still count, for zero points
+        let slow = Address::generate(&env);
+        client.submit_time(&slow, &1, &900_000, &BytesN::from_array(&env, &[2u8; 32]));
+        assert_eq!(leaderboard.get_player_all_time_total(&slow), 0);
+
+        // Unlinked, submissions no longer reach the leaderboard
+        client.set_leaderboard(&admin, &None, &0);
+        let other = Address::generate(&env);
+        client.submit_time(&other, &1, &60_000, &BytesN::from_array(&env, &[3u8; 32]));
+        assert_eq!(leaderboard.get_player_all_time_total(&other), 0);
+        assert!(client.get_best_time(&1).is_some());
+    }
+
+    #[test]
+    fn test_submission_survives_leaderboard_failure() {
+        use leaderboard::{LeaderboardContract, LeaderboardContractClient};
+        use soroban_sdk::{testutils::Events, IntoVal};
+
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+        let leaderboard_id = env.register_contract(None, LeaderboardContract);
+        let leaderboard = LeaderboardContractClient::new(&env, &leaderboard_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
+        leaderboard.initialize(&admin, &10);
+
+        // Never made a verifier, so every report is refused
+        client.set_leaderboard(&admin, &Some(leaderboard_id.clone()), &600_000);
+
+        let player = Address::generate(&env);
+        client.submit_time(&player, &1, &120_000, &BytesN::from_array(&env, &[1u8; 32]));
+        assert_eq!(client.get_best_time(&1).unwrap().player, player);
+        assert_eq!(leaderboard.get_player_all_time_total(&player), 0);
+
+        let failed = env.events().all().iter().any(|(_, topics, _)| {
+            topics == (symbol_short!("LB_FAIL"), player.clone()).into_val(&env)
+        });
+        assert!(failed);
+    }
+
+    #[test]
+    fn test_set_leaderboard_requires_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        let other = Address::generate(&env);
+        let result = client.try_set_leaderboard(&other, &Some(Address::generate(&env)), &1_000);
+        assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+        assert_eq!(client.get_leaderboard_contract(), None);
+    }
+
+    #[test]
+    fn test_puzzle_bounds_override_defaults() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.register_puzzle(&admin, &3u32);
+        client.register_puzzle(&admin, &4u32);
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+        let player = Address::generate(&env);
+
+        // A sub-second run is rejected by the global bounds
+        let result = client.try_submit_time(&player, &3, &200, &BytesN::from_array(&env, &[1u8; 32]));
+        assert_eq!(result, Err(Ok(Error::InvalidTime)));
+        assert_eq!(client.get_puzzle_bounds(&3), (1_000, 3_600_000));
+
+        // A trivial puzzle accepts it once given its own bounds
+        client.set_puzzle_bounds(&admin, &3, &100, &10_000);
+        assert_eq!(client.get_puzzle_bounds(&3), (100, 10_000));
+        client.submit_time(&player, &3, &200, &BytesN::from_array(&env, &[2u8; 32]));
+        assert_eq!(client.get_best_time(&3).unwrap().completion_time_ms, 200);
+
+        // ...and rejects times beyond its maximum
+        env.ledger().with_mut(|li| li.timestamp = 1_010);
+        let result = client.try_submit_time(&player, &3, &20_000, &BytesN::from_array(&env, &[3u8; 32]));
+        assert_eq!(result, Err(Ok(Error::InvalidTime)));
+
+        // Other puzzles keep the global bounds
+        let result = client.try_submit_time(&player, &4, &200, &BytesN::from_array(&env, &[4u8; 32]));
+        assert_eq!(result, Err(Ok(Error::InvalidTime)));
+    }
+
+    #[test]
+    fn test_set_puzzle_bounds_validation() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        let other = Address::generate(&env);
+        assert_eq!(client.try_set_puzzle_bounds(&other, &1, &100, &1_000), Err(Ok(Error::NotAuthorized)));
+        assert_eq!(client.try_set_puzzle_bounds(&admin, &1, &0, &1_000), Err(Ok(Error::InvalidTime)));
+        assert_eq!(client.try_set_puzzle_bounds(&admin, &1, &2_000, &1_000), Err(Ok(Error::InvalidTime)));
+    }
+
+    #[test]
+    fn test_submit_requires_registered_puzzle() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.register_puzzle(&admin, &5u32);
+        assert!(client.is_puzzle_registered(&5u32));
+        assert!(!client.is_puzzle_registered(&6u32));
+
+        let player = Address::generate(&env);
+        client.submit_time(&player, &5u32, &60_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        assert!(client.get_best_time(&5u32).is_some());
+
+        env.ledger().with_mut(|li| li.timestamp += 61);
+        let result = client.try_submit_time(&player, &6u32, &60_000u64, &BytesN::from_array(&env, &[2u8; 32]));
+        assert_eq!(result, Err(Ok(Error::InvalidPuzzleId)));
+        assert!(client.get_best_time(&6u32).is_none());
+
+        // The global scope is always open
+        client.submit_time(&player, &0u32, &60_000u64, &BytesN::from_array(&env, &[3u8; 32]));
+        assert!(client.get_best_time(&0u32).is_some());
+    }
+
+    #[test]
+    fn test_register_puzzle_requires_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        let other = Address::generate(&env);
+        assert_eq!(client.try_register_puzzle(&other, &5u32), Err(Ok(Error::NotAuthorized)));
+        assert_eq!(client.try_register_puzzle(&admin, &0u32), Err(Ok(Error::InvalidPuzzleId)));
+        assert!(!client.is_puzzle_registered(&5u32));
+    }
+
+    #[test]
+    fn test_player_best_per_player() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
+        client.register_puzzle(&admin, &2u32);
+
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+        assert_eq!(client.get_player_best(&player1, &1u32), None);
+
+        client.submit_time(&player1, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        client.submit_time(&player2, &1u32, &45_000u64, &BytesN::from_array(&env, &[2u8; 32]));
+
+        env.ledger().with_mut(|li| li.timestamp += 61);
+
+        // A slower run does not replace a personal best, a faster one does
+        client.submit_time(&player1, &1u32, &120_000u64, &BytesN::from_array(&env, &[3u8; 32]));
+        assert_eq!(client.get_player_best(&player1, &1u32).unwrap().completion_time_ms, 90_000);
+
+        env.ledger().with_mut(|li| li.timestamp += 61);
+        client.submit_time(&player1, &1u32, &70_000u64, &BytesN::from_array(&env, &[4u8; 32]));
+
+        let best1 = client.get_player_best(&player1, &1u32).unwrap();
+        assert_eq!(best1.player, player1);
+        assert_eq!(best1.completion_time_ms, 70_000);
+        let best2 = client.get_player_best(&player2, &1u32).unwrap();
+        assert_eq!(best2.player, player2);
+        assert_eq!(best2.completion_time_ms, 45_000);
+
+        // Bests are kept per puzzle
+        assert_eq!(client.get_player_best(&player1, &2u32), None);
+    }
+
+    #[test]
+    fn test_configurable_submit_interval() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
+        assert_eq!(client.get_submit_interval(), 5);
+
+        client.set_submit_interval(&admin, &60);
+        assert_eq!(client.get_submit_interval(), 60);
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        let player = Address::generate(&env);
+        client.submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+
+        // 30 seconds later is within the interval
+        env.ledger().with_mut(|li| {
+            li.timestamp += 30;
+            li.sequence_number += 30;
+        });
+        let result = client.try_submit_time(&player, &1u32, &80_000u64, &BytesN::from_array(&env, &[2u8; 32]));
+        assert_eq!(result, Err(Ok(Error::TooFrequent)));
+
+        // 60 seconds after the first submission is allowed
+        env.ledger().with_mut(|li| {
+            li.timestamp += 30;
+            li.sequence_number += 30;
+        });
+        client.submit_time(&player, &1u32, &80_000u64, &BytesN::from_array(&env, &[3u8; 32]));
+
+        let other = Address::generate(&env);
+        assert_eq!(client.try_set_submit_interval(&other, &1), Err(Ok(Error::NotAuthorized)));
+
+        // An interval the marker's TTL can't cover is rejected
+        assert_eq!(
+            client.try_set_submit_interval(&admin, &(u32::MAX as u64 + 1)),
+            Err(Ok(Error::InvalidInterval))
+        );
+        assert_eq!(client.get_submit_interval(), 60);
+    }
+
+    #[test]
+    fn test_monthly_board_resets_after_30_days() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
+
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+
+        client.submit_time(&player1, &1u32, &100_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+
+        // Still within the month after a week and a day
+        env.ledger().with_mut(|li| li.timestamp += 691_200);
+        client.submit_time(&player2, &1u32, &110_000u64, &BytesN::from_array(&env, &[2u8; 32]));
+        assert_eq!(client.get_leaderboard(&1u32, &TimePeriod::Monthly).len(), 2);
+        assert_eq!(client.get_leaderboard(&1u32, &TimePeriod::Weekly).len(), 1);
+
+        // Past the 30-day boundary
+        env.ledger().with_mut(|li| li.timestamp += 2_592_000);
+        client.submit_time(&player1, &1u32, &130_000u64, &BytesN::from_array(&env, &[3u8; 32]));
+
+        let monthly = client.get_leaderboard(&1u32, &TimePeriod::Monthly);
+        assert_eq!(monthly.len(), 1);
+        assert_eq!(monthly.get(0).unwrap().completion_time_ms, 130_000);
+        assert_eq!(client.get_leaderboard(&1u32, &TimePeriod::AllTime).len(), 3);
+        assert_eq!(client.get_best_time(&1u32).unwrap().completion_time_ms, 100_000);
+    }
+
+    #[test]
+    fn test_replay_hash_stays_burned() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
+
+        let player = Address::generate(&env);
+        let replay_hash = BytesN::from_array(&env, &[1u8; 32]);
+        assert!(!client.was_replay_used(&replay_hash));
+
+        client.submit_time(&player, &1u32, &90_000u64, &replay_hash);
+        assert!(client.was_replay_used(&replay_hash));
+
+        // Well past the old 24h window, in both time and ledgers
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .instance()
+                .extend_ttl(LEDGER_BUMP_SHARED, LEDGER_BUMP_SHARED);
+        });
+        env.ledger().with_mut(|li| {
+            li.timestamp += 7 * 86_400;
+            li.sequence_number += 7 * 17_280;
+        });
+
+        let result = client.try_submit_time(&player, &1u32, &90_000u64, &replay_hash);
+        assert_eq!(result, Err(Ok(Error::DuplicateReplay)));
+        assert!(client.was_replay_used(&replay_hash));
+    }
+
+    #[test]
+    fn test_last_reset_advances() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
+        assert_eq!(client.get_last_reset(&1u32, &TimePeriod::Daily), 0);
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+        client.submit_time(&player1, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        client.submit_time(&player2, &1u32, &80_000u64, &BytesN::from_array(&env, &[2u8; 32]));
+        assert_eq!(client.get_last_reset(&1u32, &TimePeriod::Daily), 1_000);
+
+        // First reset, clearing two entries
+        env.ledger().with_mut(|li| li.timestamp = 1_000 + 86_400);
+        client.submit_time(&player1, &1u32, &70_000u64, &BytesN::from_array(&env, &[3u8; 32]));
+        assert_eq!(client.get_last_reset(&1u32, &TimePeriod::Daily), 87_400);
+
+        // Second reset a day and a half later, clearing one entry
+        env.ledger().with_mut(|li| li.timestamp = 87_400 + 129_600);
+        client.submit_time(&player2, &1u32, &60_000u64, &BytesN::from_array(&env, &[4u8; 32]));
+        assert_eq!(client.get_last_reset(&1u32, &TimePeriod::Daily), 217_000);
+        assert_eq!(client.get_last_reset(&1u32, &TimePeriod::Weekly), 1_000);
+
+        let mut daily_resets = std::vec::Vec::new();
+        for (_, topics, data) in env.events().all().iter() {
+            if topics.len() == 3
+                && Symbol::try_from_val(&env, &topics.get(0).unwrap()) == Ok(symbol_short!("LB_RESET"))
+                && TimePeriod::try_from_val(&env, &topics.get(2).unwrap()) == Ok(TimePeriod::Daily)
+            {
+                daily_resets.push(<(u64, u32)>::try_from_val(&env, &data).unwrap());
+            }
+        }
+        assert_eq!(daily_resets, std::vec![(87_400, 2), (217_000, 1)]);
+    }
+
+    #[test]
+    fn test_player_rank() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.register_puzzle(&admin, &1u32);
+
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+        let player3 = Address::generate(&env);
+        let unranked = Address::generate(&env);
+
+        client.submit_time(&player1, &1u32, &150_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        client.submit_time(&player2, &1u32, &100_000u64, &BytesN::from_array(&env, &[2u8; 32]));
+        client.submit_time(&player3, &1u32, &125_000u64, &BytesN::from_array(&env, &[3u8; 32]));
+
+        assert_eq!(client.get_player_rank(&player2, &1u32, &TimePeriod::Daily), 1);
+        assert_eq!(client.get_player_rank(&player3, &1u32, &TimePeriod::Daily), 2);
+        assert_eq!(client.get_player_rank(&player1, &1u32, &TimePeriod::Daily), 3);
+        assert_eq!(client.get_player_rank(&unranked, &1u32, &TimePeriod::Daily), 0);
+
+        // A second, faster run moves player1 up by their best entry
+        env.ledger().with_mut(|li| li.timestamp += 61);
+        client.submit_time(&player1, &1u32, &110_000u64, &BytesN::from_array(&env, &[4u8; 32]));
+        assert_eq!(client.get_player_rank(&player1, &1u32, &TimePeriod::AllTime), 2);
+        assert_eq!(client.get_player_rank(&player3, &1u32, &TimePeriod::AllTime), 3);
+
+        // Other scopes are ranked separately
+        assert_eq!(client.get_player_rank(&player2, &0u32, &TimePeriod::AllTime), 0);
+    }
 }
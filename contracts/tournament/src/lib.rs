@@ -1,169 +1,735 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, token, Address, Env, Vec};
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractimpl, symbol_short, token, vec, Address, Env, IntoVal, Map, TryFromVal, Val,
+    Vec,
+};
 
 mod types;
 mod test;
-use types::{DataKey, TournamentConfig, TournamentState};
+use types::{BracketFormat, DataKey, Match, TournamentConfig, TournamentState};
+
+/// A match id and whether the player takes its first slot.
+type Slot = (u32, bool);
 
 #[contract]
 pub struct TournamentContract;
 
 #[contractimpl]
 impl TournamentContract {
-    pub fn initialize(e: Env, admin: Address, token: Address, entry_fee: i128) {
-        if e.storage().instance().has(&DataKey::Config) {
-            panic!("Already initialized");
-        }
+    /// Open a new tournament and return its id. Any number can run side by side.
+    pub fn create_tournament(e: Env, admin: Address, token: Address, entry_fee: i128) -> u32 {
+        admin.require_auth();
+
+        let tournament_id: u32 = e
+            .storage()
+            .instance()
+            .get(&DataKey::TournamentCounter)
+            .unwrap_or(0)
+            + 1;
+        e.storage().instance().set(&DataKey::TournamentCounter, &tournament_id);
+
         let config = TournamentConfig {
             admin,
             token,
             entry_fee,
+            max_participants: 0,
+            registration_deadline: 0,
         };
-        e.storage().instance().set(&DataKey::Config, &config);
-        e.storage().instance().set(&DataKey::State, &TournamentState::Open);
-        e.storage().instance().set(&DataKey::TotalPrize, &0i128);
+        write_persistent(&e, &DataKey::Config(tournament_id), &config);
+        write_persistent(&e, &DataKey::State(tournament_id), &TournamentState::Open);
+        write_persistent(&e, &DataKey::TotalPrize(tournament_id), &0i128);
         
         // Initialize empty participants list
         let participants: Vec<Address> = Vec::new(&e);
-        e.storage().instance().set(&DataKey::Participants, &participants);
+        write_persistent(&e, &DataKey::Participants(tournament_id), &participants);
+
+        tournament_id
     }
 
-    pub fn register(e: Env, player: Address) {
+    pub fn register(e: Env, tournament_id: u32, player: Address) {
         player.require_auth();
 
-        let state: TournamentState = e.storage().instance().get(&DataKey::State).unwrap();
+        let state: TournamentState = read_persistent(&e, &DataKey::State(tournament_id)).expect("Tournament not found");
         if state != TournamentState::Open {
             panic!("Tournament not open for registration");
         }
 
-        let config: TournamentConfig = e.storage().instance().get(&DataKey::Config).unwrap();
+        let config: TournamentConfig = read_persistent(&e, &DataKey::Config(tournament_id)).expect("Tournament not found");
         
-        let mut participants: Vec<Address> = e.storage().instance().get(&DataKey::Participants).unwrap();
+        if config.registration_deadline != 0 && e.ledger().timestamp() > config.registration_deadline {
+            panic!("Registration closed");
+        }
+
+        let mut participants: Vec<Address> = read_persistent(&e, &DataKey::Participants(tournament_id)).unwrap();
         if participants.contains(&player) {
             panic!("Already registered");
         }
+        if config.max_participants != 0 && participants.len() >= config.max_participants {
+            panic!("Tournament full");
+        }
 
         // Transfer entry fee
-        let client = token::Client::new(&e, &config.token);
-        client.transfer(&player, &e.current_contract_address(), &config.entry_fee);
+        let received = Self::receive(&e, &config.token, &player, config.entry_fee);
+        Self::add_escrow(&e, tournament_id, &config.token, received);
 
         // Update prize pool
-        let mut total_prize: i128 = e.storage().instance().get(&DataKey::TotalPrize).unwrap();
+        let mut total_prize: i128 = read_persistent(&e, &DataKey::TotalPrize(tournament_id)).unwrap();
         total_prize += config.entry_fee;
-        e.storage().instance().set(&DataKey::TotalPrize, &total_prize);
+        write_persistent(&e, &DataKey::TotalPrize(tournament_id), &total_prize);
 
         // Add to participants
-        participants.push_back(player);
-        e.storage().instance().set(&DataKey::Participants, &participants);
+        participants.push_back(player.clone());
+        write_persistent(&e, &DataKey::Participants(tournament_id), &participants);
+
+        e.events().publish((symbol_short!("register"), player), config.entry_fee);
+    }
+
+    /// Cap the field size and close registration after `registration_deadline`
+    /// (a ledger timestamp). Zero disables either limit.
+    pub fn set_registration_limits(e: Env, tournament_id: u32, max_participants: u32, registration_deadline: u64) {
+        let mut config: TournamentConfig = read_persistent(&e, &DataKey::Config(tournament_id)).expect("Tournament not found");
+        config.admin.require_auth();
+
+        let state: TournamentState = read_persistent(&e, &DataKey::State(tournament_id)).expect("Tournament not found");
+        if state != TournamentState::Open {
+            panic!("Tournament already started or ended");
+        }
+
+        let participants: Vec<Address> = read_persistent(&e, &DataKey::Participants(tournament_id)).unwrap();
+        if max_participants != 0 && max_participants < participants.len() {
+            panic!("Cap below current participants");
+        }
+
+        config.max_participants = max_participants;
+        config.registration_deadline = registration_deadline;
+        write_persistent(&e, &DataKey::Config(tournament_id), &config);
+    }
+
+    /// Add funds to the prize pool, e.g. a sponsor or a rollover from another tournament.
+    pub fn add_prize(e: Env, tournament_id: u32, from: Address, amount: i128) {
+        from.require_auth();
+
+        if amount <= 0 {
+            panic!("Invalid amount");
+        }
+
+        let config: TournamentConfig = read_persistent(&e, &DataKey::Config(tournament_id)).expect("Tournament not found");
+        Self::credit_prize(&e, tournament_id, amount);
+
+        let received = Self::receive(&e, &config.token, &from, amount);
+        Self::add_escrow(&e, tournament_id, &config.token, received);
+    }
+
+    /// Move the prize pool into another tournament instead of leaving it here,
+    /// either in this contract or in another tournament contract.
+    /// Only allowed once every participant has been refunded their entry fee.
+    pub fn rollover_prize(
+        e: Env,
+        admin: Address,
+        tournament_id: u32,
+        next_contract: Address,
+        next_tournament_id: u32,
+    ) {
+        admin.require_auth();
+        let config: TournamentConfig = read_persistent(&e, &DataKey::Config(tournament_id)).expect("Tournament not found");
+        if admin != config.admin {
+            panic!("Unauthorized");
+        }
+
+        let state: TournamentState = read_persistent(&e, &DataKey::State(tournament_id)).expect("Tournament not found");
+        if state != TournamentState::Cancelled && state != TournamentState::Open {
+            panic!("Tournament must be open or cancelled to roll over");
+        }
+
+        let this = e.current_contract_address();
+        if next_contract == this && next_tournament_id == tournament_id {
+            panic!("Cannot roll over into the same tournament");
+        }
+
+        let participants: Vec<Address> = read_persistent(&e, &DataKey::Participants(tournament_id)).unwrap();
+        if participants.iter().any(|p| !Self::is_refunded(e.clone(), tournament_id, p)) {
+            panic!("Participants must be refunded first");
+        }
+
+        let total_prize: i128 = read_persistent(&e, &DataKey::TotalPrize(tournament_id)).unwrap();
+        if total_prize <= 0 {
+            panic!("No prize to roll over");
+        }
+
+        write_persistent(&e, &DataKey::TotalPrize(tournament_id), &0i128);
+
+        // Funds already sit in this contract; only the accounting moves
+        if next_contract == this {
+            let next_config: TournamentConfig = read_persistent(&e, &DataKey::Config(next_tournament_id))
+                .expect("Tournament not found");
+            if next_config.token != config.token {
+                panic!("Token mismatch");
+            }
+            Self::credit_prize(&e, next_tournament_id, total_prize);
+            let held = Self::get_escrow(e.clone(), tournament_id);
+            Self::add_escrow(&e, tournament_id, &config.token, -held);
+            Self::add_escrow(&e, next_tournament_id, &config.token, held);
+            return;
+        }
+
+        // Only what this tournament actually holds leaves the contract
+        let amount = total_prize.min(Self::get_escrow(e.clone(), tournament_id));
+        Self::add_escrow(&e, tournament_id, &config.token, -amount);
+
+        // The next tournament pulls the funds from us, so authorize that transfer.
+        e.authorize_as_current_contract(vec![
+            &e,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: config.token.clone(),
+                    fn_name: symbol_short!("transfer"),
+                    args: (this.clone(), next_contract.clone(), amount).into_val(&e),
+                },
+                sub_invocations: vec![&e],
+            }),
+        ]);
+
+        TournamentContractClient::new(&e, &next_contract).add_prize(&next_tournament_id, &this, &amount);
     }
 
-    pub fn start_tournament(e: Env) {
-        let config: TournamentConfig = e.storage().instance().get(&DataKey::Config).unwrap();
+    pub fn start_tournament(e: Env, tournament_id: u32) {
+        let config: TournamentConfig = read_persistent(&e, &DataKey::Config(tournament_id)).expect("Tournament not found");
         config.admin.require_auth();
 
-        let state: TournamentState = e.storage().instance().get(&DataKey::State).unwrap();
+        let state: TournamentState = read_persistent(&e, &DataKey::State(tournament_id)).expect("Tournament not found");
         if state != TournamentState::Open {
             panic!("Tournament already started or ended");
         }
 
-        let participants: Vec<Address> = e.storage().instance().get(&DataKey::Participants).unwrap();
+        let participants: Vec<Address> = read_persistent(&e, &DataKey::Participants(tournament_id)).unwrap();
         if participants.len() < 2 {
             panic!("Not enough participants");
         }
 
-        e.storage().instance().set(&DataKey::State, &TournamentState::Started);
-        // Bracket generation logic would go here. 
-        // For simplicity in this iteration, we assume off-chain bracket management 
-        // or a simple linear matching handled by the admin via `record_result`.
+        write_persistent(&e, &DataKey::State(tournament_id), &TournamentState::Started);
+        e.events().publish((symbol_short!("start"),), participants.len());
+
+        Self::generate_bracket(&e, tournament_id, participants);
     }
 
-    pub fn record_result(e: Env, winner: Address) { 
-        // Note: This is a simplified version where admin declares winners of matches/tournament directly
-        // In a full version, we'd pass match_id and validate against the bracket.
-        let config: TournamentConfig = e.storage().instance().get(&DataKey::Config).unwrap();
+    /// Record the winner of a bracket match and send both players on to
+    /// their next match. Resolving the final ends the tournament and pays
+    /// out; in double elimination that is the grand final when the unbeaten
+    /// finalist wins it, and the deciding rematch otherwise.
+    pub fn record_result(e: Env, tournament_id: u32, match_id: u32, winner: Address) {
+        let config: TournamentConfig = read_persistent(&e, &DataKey::Config(tournament_id)).expect("Tournament not found");
         config.admin.require_auth();
 
-        let state: TournamentState = e.storage().instance().get(&DataKey::State).unwrap();
+        let state: TournamentState = read_persistent(&e, &DataKey::State(tournament_id)).expect("Tournament not found");
         if state != TournamentState::Started {
             panic!("Tournament not in progress");
         }
-        
-        // Check if winner is a valid participant - simplified check
-        let participants: Vec<Address> = e.storage().instance().get(&DataKey::Participants).unwrap();
-        if !participants.contains(&winner) {
-             panic!("Winner is not a participant");
+
+        let mut m: Match = read_persistent(&e, &DataKey::Match(tournament_id, match_id))
+            .expect("Match not found");
+        if m.winner.is_some() {
+            panic!("Match already decided");
         }
-        
-        // For this MVP, let's assume `record_result` declares the FINAL tournament winner for simplicity
-        // or effectively distributes the prize.
-        
-        // We will move to Ended state and distribute prize
-        e.storage().instance().set(&DataKey::State, &TournamentState::Ended);
-        
-        let total_prize: i128 = e.storage().instance().get(&DataKey::TotalPrize).unwrap();
-        if total_prize > 0 {
-             let client = token::Client::new(&e, &config.token);
-             client.transfer(&e.current_contract_address(), &winner, &total_prize);
+
+        let (p1, p2) = match (m.p1.clone(), m.p2.clone()) {
+            (Some(p1), Some(p2)) => (p1, p2),
+            _ => panic!("Match not ready"),
+        };
+        let loser = if winner == p1 {
+            p2
+        } else if winner == p2 {
+            p1.clone()
+        } else {
+            panic!("Winner is not in this match");
+        };
+
+        m.winner = Some(winner.clone());
+        write_persistent(&e, &DataKey::Match(tournament_id, match_id), &m);
+
+        let mut losses: Map<Address, u32> = read_persistent(&e, &DataKey::Losses(tournament_id))
+            .unwrap_or(Map::new(&e));
+        losses.set(loser.clone(), losses.get(loser.clone()).unwrap_or(0) + 1);
+        write_persistent(&e, &DataKey::Losses(tournament_id), &losses);
+
+        e.events().publish(
+            (symbol_short!("result"), winner.clone()),
+            (match_id, loser.clone()),
+        );
+
+        let size = Self::get_bracket_size(e.clone(), tournament_id);
+        let decided = match Self::get_bracket_format(e.clone(), tournament_id) {
+            BracketFormat::Single => match_id == 1,
+            BracketFormat::Double => {
+                match_id == 2 * size - 1 || (match_id == 2 * size - 2 && winner == p1)
+            }
+        };
+        if decided {
+            Self::finish(&e, tournament_id, &config, &winner);
+        } else {
+            Self::send_on(&e, tournament_id, match_id, Some(winner), Some(loser));
         }
     }
 
-    pub fn cancel_tournament(e: Env) {
-        let config: TournamentConfig = e.storage().instance().get(&DataKey::Config).unwrap();
+    /// Seed a registered player; 1 is the strongest. Top seeds are spread
+    /// across the bracket so they meet as late as possible.
+    pub fn set_seed(e: Env, admin: Address, tournament_id: u32, player: Address, seed: u32) {
+        admin.require_auth();
+        let config: TournamentConfig = read_persistent(&e, &DataKey::Config(tournament_id)).expect("Tournament not found");
+        if admin != config.admin {
+            panic!("Unauthorized");
+        }
+
+        let state: TournamentState = read_persistent(&e, &DataKey::State(tournament_id)).expect("Tournament not found");
+        if state != TournamentState::Open {
+            panic!("Tournament already started or ended");
+        }
+
+        if seed == 0 {
+            panic!("Seed must be positive");
+        }
+
+        let participants: Vec<Address> = read_persistent(&e, &DataKey::Participants(tournament_id)).unwrap();
+        if !participants.contains(&player) {
+            panic!("Player is not a participant");
+        }
+
+        let mut seeds: Map<Address, u32> = read_persistent(&e, &DataKey::Seeds(tournament_id))
+            .unwrap_or(Map::new(&e));
+        for (other, other_seed) in seeds.iter() {
+            if other_seed == seed && other != player {
+                panic!("Seed already taken");
+            }
+        }
+        seeds.set(player, seed);
+        write_persistent(&e, &DataKey::Seeds(tournament_id), &seeds);
+    }
+
+    pub fn get_seed(e: Env, tournament_id: u32, player: Address) -> Option<u32> {
+        let seeds: Map<Address, u32> = read_persistent(&e, &DataKey::Seeds(tournament_id))
+            .unwrap_or(Map::new(&e));
+        seeds.get(player)
+    }
+
+    /// Choose single or double elimination before the tournament starts.
+    pub fn set_bracket_format(e: Env, tournament_id: u32, format: BracketFormat) {
+        let config: TournamentConfig = read_persistent(&e, &DataKey::Config(tournament_id)).expect("Tournament not found");
         config.admin.require_auth();
 
-        let state: TournamentState = e.storage().instance().get(&DataKey::State).unwrap();
+        let state: TournamentState = read_persistent(&e, &DataKey::State(tournament_id)).expect("Tournament not found");
+        if state != TournamentState::Open {
+            panic!("Tournament already started or ended");
+        }
+
+        write_persistent(&e, &DataKey::Format(tournament_id), &format);
+    }
+
+    pub fn cancel_tournament(e: Env, tournament_id: u32) {
+        let config: TournamentConfig = read_persistent(&e, &DataKey::Config(tournament_id)).expect("Tournament not found");
+        config.admin.require_auth();
+
+        let state: TournamentState = read_persistent(&e, &DataKey::State(tournament_id)).expect("Tournament not found");
         if state == TournamentState::Ended {
             panic!("Cannot cancel ended tournament");
         }
 
-        e.storage().instance().set(&DataKey::State, &TournamentState::Cancelled);
+        write_persistent(&e, &DataKey::State(tournament_id), &TournamentState::Cancelled);
+
+        let total_prize: i128 = read_persistent(&e, &DataKey::TotalPrize(tournament_id)).unwrap();
+        e.events().publish((symbol_short!("cancel"),), total_prize);
         
         // Allow refunds - in this model, we can iterate and refund or let users pull.
         // For gas efficiency, usually pull pattern is better, but loop is okay for small numbers.
         // Let's implement a 'withdraw_refund' function for users to call instead of auto-refunding loop to be safe.
     }
 
-    pub fn withdraw_refund(e: Env, player: Address) {
+    pub fn withdraw_refund(e: Env, tournament_id: u32, player: Address) {
         player.require_auth();
-        let state: TournamentState = e.storage().instance().get(&DataKey::State).unwrap();
+        let state: TournamentState = read_persistent(&e, &DataKey::State(tournament_id)).expect("Tournament not found");
         if state != TournamentState::Cancelled {
             panic!("Tournament not cancelled");
         }
 
-        let participants: Vec<Address> = e.storage().instance().get(&DataKey::Participants).unwrap();
+        let participants: Vec<Address> = read_persistent(&e, &DataKey::Participants(tournament_id)).unwrap();
         if !participants.contains(&player) {
             panic!("Not a participant");
         }
 
-        // Ideally we track if they already withdrew. 
-        // Quick fix: Remove them from participants list after refund to prevent double refund.
-        // Note: Vector removal by value is O(N), might be expensive for large lists.
-        // Valid for MVP.
-        
-        let config: TournamentConfig = e.storage().instance().get(&DataKey::Config).unwrap();
+        // Participants stay listed for history; the flag blocks a second refund
+        let refunded_key = DataKey::Refunded(tournament_id, player.clone());
+        if e.storage().persistent().has(&refunded_key) {
+            panic!("Already refunded");
+        }
+        write_persistent(&e, &refunded_key, &true);
+
+        let config: TournamentConfig = read_persistent(&e, &DataKey::Config(tournament_id)).expect("Tournament not found");
+        let refund = config.entry_fee.min(Self::get_escrow(e.clone(), tournament_id));
+        Self::add_escrow(&e, tournament_id, &config.token, -refund);
         let client = token::Client::new(&e, &config.token);
-        client.transfer(&e.current_contract_address(), &player, &config.entry_fee);
+        client.transfer(&e.current_contract_address(), &player, &refund);
 
-        // Remove from list
-        let mut new_participants = Vec::new(&e);
-        for p in participants.iter() {
-            if p != player {
-                new_participants.push_back(p);
-            }
-        }
-        e.storage().instance().set(&DataKey::Participants, &new_participants);
+        let mut total_prize: i128 = read_persistent(&e, &DataKey::TotalPrize(tournament_id)).unwrap();
+        total_prize -= config.entry_fee;
+        write_persistent(&e, &DataKey::TotalPrize(tournament_id), &total_prize);
+
+        e.events().publish((symbol_short!("refund"), player), refund);
+    }
+
+    pub fn is_refunded(e: Env, tournament_id: u32, player: Address) -> bool {
+        e.storage().persistent().has(&DataKey::Refunded(tournament_id, player))
     }
     
     // View functions
-    pub fn get_state(e: Env) -> TournamentState {
-        e.storage().instance().get(&DataKey::State).unwrap()
+    pub fn get_state(e: Env, tournament_id: u32) -> TournamentState {
+        read_persistent(&e, &DataKey::State(tournament_id)).expect("Tournament not found")
     }
     
-    pub fn get_participants(e: Env) -> Vec<Address> {
-        e.storage().instance().get(&DataKey::Participants).unwrap()
+    pub fn get_participants(e: Env, tournament_id: u32) -> Vec<Address> {
+        read_persistent(&e, &DataKey::Participants(tournament_id)).unwrap()
     }
     
-    pub fn get_prize_pool(e: Env) -> i128 {
-        e.storage().instance().get(&DataKey::TotalPrize).unwrap_or(0)
+    /// Registrations left before the cap; `u32::MAX` when uncapped.
+    pub fn slots_remaining(e: Env, tournament_id: u32) -> u32 {
+        let config: TournamentConfig = read_persistent(&e, &DataKey::Config(tournament_id)).expect("Tournament not found");
+        if config.max_participants == 0 {
+            return u32::MAX;
+        }
+        let participants: Vec<Address> = read_persistent(&e, &DataKey::Participants(tournament_id)).unwrap();
+        config.max_participants.saturating_sub(participants.len())
+    }
+
+    /// Tokens this contract holds on behalf of the tournament.
+    pub fn get_escrow(e: Env, tournament_id: u32) -> i128 {
+        read_persistent(&e, &DataKey::Escrow(tournament_id)).unwrap_or(0)
+    }
+
+    pub fn get_prize_pool(e: Env, tournament_id: u32) -> i128 {
+        read_persistent(&e, &DataKey::TotalPrize(tournament_id)).unwrap_or(0)
+    }
+
+    pub fn get_match(e: Env, tournament_id: u32, match_id: u32) -> Option<Match> {
+        read_persistent(&e, &DataKey::Match(tournament_id, match_id))
+    }
+
+    /// First-round slots in the bracket (0 before it is generated).
+    pub fn get_bracket_size(e: Env, tournament_id: u32) -> u32 {
+        read_persistent(&e, &DataKey::BracketSize(tournament_id)).unwrap_or(0)
+    }
+
+    pub fn get_bracket_format(e: Env, tournament_id: u32) -> BracketFormat {
+        read_persistent(&e, &DataKey::Format(tournament_id)).unwrap_or(BracketFormat::Single)
+    }
+
+    pub fn get_losses(e: Env, tournament_id: u32, player: Address) -> u32 {
+        let losses: Map<Address, u32> = read_persistent(&e, &DataKey::Losses(tournament_id))
+            .unwrap_or(Map::new(&e));
+        losses.get(player).unwrap_or(0)
+    }
+
+    /// Participants who have not been eliminated yet.
+    pub fn get_active_players(e: Env, tournament_id: u32) -> Vec<Address> {
+        let max_losses = Self::max_losses(&e, tournament_id);
+        let mut active = Vec::new(&e);
+        for p in Self::get_participants(e.clone(), tournament_id).iter() {
+            if Self::get_losses(e.clone(), tournament_id, p.clone()) < max_losses {
+                active.push_back(p);
+            }
+        }
+        active
+    }
+
+    fn max_losses(e: &Env, tournament_id: u32) -> u32 {
+        match Self::get_bracket_format(e.clone(), tournament_id) {
+            BracketFormat::Single => 1,
+            BracketFormat::Double => 2,
+        }
+    }
+
+    /// Place the ranked participants into first-round matches, padding the
+    /// bracket to a power of two with byes that go to the top ranks. Double
+    /// elimination also lays out the losers' bracket and the grand final.
+    fn generate_bracket(e: &Env, tournament_id: u32, participants: Vec<Address>) {
+        let players = Self::rank_players(e, tournament_id, participants);
+
+        let mut size: u32 = 2;
+        while size < players.len() {
+            size *= 2;
+        }
+        write_persistent(e, &DataKey::BracketSize(tournament_id), &size);
+
+        // Standard seeding: 1 plays n, 2 plays n-1, and so on, with the pairs
+        // laid out so the top two ranks can only meet in the final.
+        let mut order: Vec<u32> = vec![e, 1];
+        while order.len() < size {
+            let m = order.len() * 2;
+            let mut next = Vec::new(e);
+            for rank in order.iter() {
+                next.push_back(rank);
+                next.push_back(m + 1 - rank);
+            }
+            order = next;
+        }
+
+        let first = size / 2;
+        for i in 0..first {
+            // The higher rank always exists, so byes never meet each other
+            let m = Match {
+                p1: players.get(order.get(2 * i).unwrap() - 1),
+                p2: players.get(order.get(2 * i + 1).unwrap() - 1),
+                winner: None,
+            };
+            write_persistent(e, &DataKey::Match(tournament_id, first + i), &m);
+        }
+        let last = match Self::get_bracket_format(e.clone(), tournament_id) {
+            BracketFormat::Single => first,
+            BracketFormat::Double => 2 * size,
+        };
+        for id in (1..first).chain(size..last) {
+            let m = Match { p1: None, p2: None, winner: None };
+            write_persistent(e, &DataKey::Match(tournament_id, id), &m);
+        }
+
+        for id in first..size {
+            let m: Match = read_persistent(e, &DataKey::Match(tournament_id, id)).unwrap();
+            if m.p2.is_none() {
+                Self::add_walkover(e, tournament_id, id);
+                Self::walk_over(e, tournament_id, id);
+            }
+        }
+    }
+
+    /// Seeded players by seed, then unseeded ones in registration order.
+    fn rank_players(e: &Env, tournament_id: u32, participants: Vec<Address>) -> Vec<Address> {
+        let seeds: Map<Address, u32> = read_persistent(e, &DataKey::Seeds(tournament_id))
+            .unwrap_or(Map::new(e));
+
+        // Insertion sort; brackets are small
+        let mut seeded: Vec<(u32, Address)> = Vec::new(e);
+        for p in participants.iter() {
+            if let Some(seed) = seeds.get(p.clone()) {
+                let mut at = seeded.len();
+                while at > 0 && seeded.get(at - 1).unwrap().0 > seed {
+                    at -= 1;
+                }
+                seeded.insert(at, (seed, p));
+            }
+        }
+
+        let mut players = Vec::new(e);
+        for (_, p) in seeded.iter() {
+            players.push_back(p);
+        }
+        for p in participants.iter() {
+            if !seeds.contains_key(p.clone()) {
+                players.push_back(p);
+            }
+        }
+        players
+    }
+
+    /// Put the winner and the loser of `match_id` into their next matches.
+    /// `None` stands for a player a bye left out, which gives the other
+    /// side of the next match a walkover.
+    fn send_on(e: &Env, tournament_id: u32, match_id: u32, winner: Option<Address>, loser: Option<Address>) {
+        let (to_winner, to_loser) = Self::routes(e, tournament_id, match_id);
+        if let Some((next_id, first_slot)) = to_winner {
+            Self::place(e, tournament_id, next_id, first_slot, winner);
+        }
+        if let Some((next_id, first_slot)) = to_loser {
+            Self::place(e, tournament_id, next_id, first_slot, loser);
+        }
+    }
+
+    /// Where the winner and the loser of `match_id` play next. A loser with
+    /// nowhere to go is out. See `Match` for how the ids are laid out.
+    fn routes(e: &Env, tournament_id: u32, match_id: u32) -> (Option<Slot>, Option<Slot>) {
+        let size = Self::get_bracket_size(e.clone(), tournament_id);
+        let heap_parent = Some((match_id / 2, match_id & 1 == 0));
+        if Self::get_bracket_format(e.clone(), tournament_id) == BracketFormat::Single {
+            return (heap_parent, None);
+        }
+
+        let grand_final = 2 * size - 2;
+        let last_round = 2 * (size.trailing_zeros() - 1);
+        if match_id < size {
+            // Winners' bracket; round 1 is the first round
+            let round = size.trailing_zeros() - (31 - match_id.leading_zeros());
+            let index = match_id - (size >> round);
+            let to_winner = if match_id == 1 { Some((grand_final, true)) } else { heap_parent };
+            let to_loser = if size == 2 {
+                Some((grand_final, false))
+            } else if round == 1 {
+                Some((Self::losers_match(size, 1, index / 2), index & 1 == 0))
+            } else {
+                // Drop into the opposite half so early rematches are avoided
+                let count = size >> round;
+                Some((Self::losers_match(size, 2 * (round - 1), count - 1 - index), false))
+            };
+            (to_winner, to_loser)
+        } else if match_id < grand_final {
+            let (round, index) = Self::losers_round(size, match_id);
+            let to_winner = if round & 1 == 1 {
+                Some((Self::losers_match(size, round + 1, index), true))
+            } else if round == last_round {
+                Some((grand_final, false))
+            } else {
+                Some((Self::losers_match(size, round + 1, index / 2), index & 1 == 0))
+            };
+            (to_winner, None)
+        } else {
+            // The grand final only gets here when the unbeaten finalist
+            // lost it; both meet again in the deciding rematch
+            (Some((grand_final + 1, false)), Some((grand_final + 1, true)))
+        }
+    }
+
+    /// Matches in losers' bracket `round`, counting from 1. Odd rounds pair
+    /// up the survivors, even rounds bring in the losers of the winners' bracket.
+    fn losers_round_len(size: u32, round: u32) -> u32 {
+        size >> (round.div_ceil(2) + 1)
+    }
+
+    fn losers_match(size: u32, round: u32, index: u32) -> u32 {
+        let mut id = size;
+        for r in 1..round {
+            id += Self::losers_round_len(size, r);
+        }
+        id + index
+    }
+
+    /// The losers' bracket round and index within it of `match_id`.
+    fn losers_round(size: u32, match_id: u32) -> (u32, u32) {
+        let mut start = size;
+        let mut round = 1;
+        while match_id >= start + Self::losers_round_len(size, round) {
+            start += Self::losers_round_len(size, round);
+            round += 1;
+        }
+        (round, match_id - start)
+    }
+
+    /// Seat `player` in a slot of `match_id`, or record that the slot stays
+    /// empty, then settle the match if only one side can ever show up.
+    fn place(e: &Env, tournament_id: u32, match_id: u32, first_slot: bool, player: Option<Address>) {
+        match player {
+            Some(p) => {
+                let mut m: Match = read_persistent(e, &DataKey::Match(tournament_id, match_id)).unwrap();
+                if first_slot {
+                    m.p1 = Some(p);
+                } else {
+                    m.p2 = Some(p);
+                }
+                write_persistent(e, &DataKey::Match(tournament_id, match_id), &m);
+            }
+            None => Self::add_walkover(e, tournament_id, match_id),
+        }
+        Self::walk_over(e, tournament_id, match_id);
+    }
+
+    fn add_walkover(e: &Env, tournament_id: u32, match_id: u32) {
+        let mut walkovers: Map<u32, u32> = read_persistent(e, &DataKey::Walkovers(tournament_id))
+            .unwrap_or(Map::new(e));
+        walkovers.set(match_id, walkovers.get(match_id).unwrap_or(0) + 1);
+        write_persistent(e, &DataKey::Walkovers(tournament_id), &walkovers);
+    }
+
+    /// Advance the only player of a match whose other slot will never be
+    /// filled. Nobody loses a walkover.
+    fn walk_over(e: &Env, tournament_id: u32, match_id: u32) {
+        let walkovers: Map<u32, u32> = read_persistent(e, &DataKey::Walkovers(tournament_id))
+            .unwrap_or(Map::new(e));
+        let empty = walkovers.get(match_id).unwrap_or(0);
+        let mut m: Match = read_persistent(e, &DataKey::Match(tournament_id, match_id)).unwrap();
+        let seated = m.p1.is_some() as u32 + m.p2.is_some() as u32;
+        if empty == 0 || seated + empty < 2 {
+            return;
+        }
+
+        m.winner = m.p1.clone().or(m.p2.clone());
+        write_persistent(e, &DataKey::Match(tournament_id, match_id), &m);
+        Self::send_on(e, tournament_id, match_id, m.winner, None);
+    }
+
+    /// Add `amount` to the prize pool of a tournament that still accepts prizes.
+    fn credit_prize(e: &Env, tournament_id: u32, amount: i128) {
+        let state: TournamentState = read_persistent(e, &DataKey::State(tournament_id)).expect("Tournament not found");
+        if state != TournamentState::Open && state != TournamentState::Started {
+            panic!("Tournament not accepting prizes");
+        }
+
+        let mut total_prize: i128 = read_persistent(e, &DataKey::TotalPrize(tournament_id)).unwrap();
+        total_prize += amount;
+        write_persistent(e, &DataKey::TotalPrize(tournament_id), &total_prize);
+    }
+
+    /// Pull `amount` of `token` from `from` and return what actually arrived.
+    fn receive(e: &Env, token: &Address, from: &Address, amount: i128) -> i128 {
+        let client = token::Client::new(e, token);
+        let this = e.current_contract_address();
+        let before = client.balance(&this);
+        client.transfer(from, &this, &amount);
+        client.balance(&this) - before
+    }
+
+    /// Move a tournament's escrow, and the contract-wide total for its token, by `amount`.
+    fn add_escrow(e: &Env, tournament_id: u32, token: &Address, amount: i128) {
+        let held = Self::get_escrow(e.clone(), tournament_id);
+        write_persistent(e, &DataKey::Escrow(tournament_id), &(held + amount));
+
+        let key = DataKey::TokenEscrow(token.clone());
+        let total: i128 = read_persistent(e, &key).unwrap_or(0);
+        write_persistent(e, &key, &(total + amount));
+    }
+
+    /// End the tournament and pay the prize pool to `winner`.
+    fn finish(e: &Env, tournament_id: u32, config: &TournamentConfig, winner: &Address) {
+        write_persistent(e, &DataKey::State(tournament_id), &TournamentState::Ended);
+
+        let total_prize: i128 = read_persistent(e, &DataKey::TotalPrize(tournament_id)).unwrap();
+        if total_prize > 0 {
+             let client = token::Client::new(e, &config.token);
+
+             // Never pay out more than this tournament holds, nor dip into
+             // what the contract holds for other tournaments
+             let held = Self::get_escrow(e.clone(), tournament_id);
+             let token_total: i128 = read_persistent(e, &DataKey::TokenEscrow(config.token.clone())).unwrap_or(0);
+             let others = token_total - held;
+             let balance = client.balance(&e.current_contract_address());
+             let payout = total_prize.min(held).min((balance - others).max(0));
+             // The tournament is over, so whatever it couldn't pay is written off
+             Self::add_escrow(e, tournament_id, &config.token, -held);
+             if payout < total_prize {
+                 e.events().publish(
+                     (symbol_short!("shortfall"), winner.clone()),
+                     (total_prize, payout),
+                 );
+             }
+
+             if payout > 0 {
+                 client.transfer(&e.current_contract_address(), winner, &payout);
+             }
+        }
+    }
+}
+
+// Every tournament keeps its own persistent entries; each write and read
+// extends their TTL so long-running tournaments don't expire.
+const LEDGER_THRESHOLD: u32 = 518_400; // ~30 days @ 5s/ledger
+const LEDGER_BUMP: u32 = 1_036_800; // ~60 days @ 5s/ledger
+
+fn write_persistent<V: IntoVal<Env, Val>>(e: &Env, key: &DataKey, value: &V) {
+    e.storage().persistent().set(key, value);
+    e.storage()
+        .persistent()
+        .extend_ttl(key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+fn read_persistent<V: TryFromVal<Env, Val>>(e: &Env, key: &DataKey) -> Option<V> {
+    let value = e.storage().persistent().get(key);
+    if value.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(key, LEDGER_THRESHOLD, LEDGER_BUMP);
     }
+    value
 }
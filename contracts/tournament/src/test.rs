@@ -1,7 +1,10 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, token, Address, Env};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token, Address, Env, Symbol, TryFromVal, Val,
+};
 
 fn create_token_contract<'a>(e: &Env, admin: &Address) -> (token::Client<'a>, token::StellarAssetClient<'a>) {
     let contract_address = e.register_stellar_asset_contract_v2(admin.clone())
@@ -36,26 +39,26 @@ fn test_tournament_flow() {
 
     // Initialize tournament
     let entry_fee = 100;
-    tournament_client.initialize(&admin, &token_client.address, &entry_fee);
+    let id = tournament_client.create_tournament(&admin, &token_client.address, &entry_fee);
 
     // Register users
-    tournament_client.register(&user1);
-    tournament_client.register(&user2);
+    tournament_client.register(&id, &user1);
+    tournament_client.register(&id, &user2);
 
     // Verify registrations
-    let participants = tournament_client.get_participants();
+    let participants = tournament_client.get_participants(&id);
     assert_eq!(participants.len(), 2);
-    assert_eq!(tournament_client.get_prize_pool(), 200);
+    assert_eq!(tournament_client.get_prize_pool(&id), 200);
 
     // Start tournament
-    tournament_client.start_tournament();
-    assert_eq!(tournament_client.get_state(), TournamentState::Started);
+    tournament_client.start_tournament(&id);
+    assert_eq!(tournament_client.get_state(&id), TournamentState::Started);
 
     // Record result (User1 wins)
-    tournament_client.record_result(&user1);
+    tournament_client.record_result(&id, &1, &user1);
     
     // Verify changes
-    assert_eq!(tournament_client.get_state(), TournamentState::Ended);
+    assert_eq!(tournament_client.get_state(&id), TournamentState::Ended);
     // User1 should have 900 (remaining) + 200 (prize) = 1100
     assert_eq!(token_client.balance(&user1), 1100);
     // User2 should have 900
@@ -76,18 +79,784 @@ fn test_cancel_and_refund() {
 
     token_admin_client.mint(&user1, &1000);
 
-    tournament_client.initialize(&admin, &token_client.address, &100);
-    tournament_client.register(&user1);
+    let id = tournament_client.create_tournament(&admin, &token_client.address, &100);
+    tournament_client.register(&id, &user1);
 
-    tournament_client.cancel_tournament();
-    assert_eq!(tournament_client.get_state(), TournamentState::Cancelled);
+    tournament_client.cancel_tournament(&id);
+    assert_eq!(tournament_client.get_state(&id), TournamentState::Cancelled);
 
-    tournament_client.withdraw_refund(&user1);
+    tournament_client.withdraw_refund(&id, &user1);
     
     // User1 should be back to 1000
     assert_eq!(token_client.balance(&user1), 1000);
     
-    // Participants list should be empty (or at least user1 removed)
-    let participants = tournament_client.get_participants();
-    assert!(!participants.contains(&user1));
+    // Participants list is kept for history; the refund is flagged instead
+    let participants = tournament_client.get_participants(&id);
+    assert!(participants.contains(&user1));
+    assert!(tournament_client.is_refunded(&id, &user1));
+
+    // A second refund is rejected
+    assert!(tournament_client.try_withdraw_refund(&id, &user1).is_err());
+    assert_eq!(token_client.balance(&user1), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Already refunded")]
+fn test_double_refund_rejected() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let tournament_client = create_tournament_contract(&e);
+
+    token_admin_client.mint(&user1, &1000);
+    token_admin_client.mint(&user2, &1000);
+
+    let id = tournament_client.create_tournament(&admin, &token_client.address, &100);
+    tournament_client.register(&id, &user1);
+    tournament_client.register(&id, &user2);
+    tournament_client.cancel_tournament(&id);
+
+    tournament_client.withdraw_refund(&id, &user1);
+    assert_eq!(tournament_client.get_participants(&id).len(), 2);
+    assert!(!tournament_client.is_refunded(&id, &user2));
+
+    // Would otherwise drain user2's entry fee
+    tournament_client.withdraw_refund(&id, &user1);
+}
+
+#[test]
+fn test_rollover_prize_to_next_tournament() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let sponsor = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let first = create_tournament_contract(&e);
+    let next = create_tournament_contract(&e);
+
+    token_admin_client.mint(&sponsor, &1000);
+    token_admin_client.mint(&user1, &1000);
+
+    let first_id = first.create_tournament(&admin, &token_client.address, &100);
+    let next_id = next.create_tournament(&admin, &token_client.address, &100);
+
+    // Sponsored pool plus one under-subscribed registration
+    first.add_prize(&first_id, &sponsor, &500);
+    first.register(&first_id, &user1);
+    assert_eq!(first.get_prize_pool(&first_id), 600);
+
+    first.cancel_tournament(&first_id);
+    first.withdraw_refund(&first_id, &user1);
+    assert_eq!(first.get_prize_pool(&first_id), 500);
+
+    first.rollover_prize(&admin, &first_id, &next.address, &next_id);
+
+    assert_eq!(first.get_prize_pool(&first_id), 0);
+    assert_eq!(next.get_prize_pool(&next_id), 500);
+    assert_eq!(token_client.balance(&first.address), 0);
+    assert_eq!(token_client.balance(&next.address), 500);
+    assert_eq!(token_client.balance(&user1), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Participants must be refunded first")]
+fn test_rollover_requires_refunds() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let first = create_tournament_contract(&e);
+    let next = create_tournament_contract(&e);
+
+    token_admin_client.mint(&user1, &1000);
+
+    let first_id = first.create_tournament(&admin, &token_client.address, &100);
+    let next_id = next.create_tournament(&admin, &token_client.address, &100);
+    first.register(&first_id, &user1);
+    first.cancel_tournament(&first_id);
+
+    first.rollover_prize(&admin, &first_id, &next.address, &next_id);
+}
+
+#[test]
+fn test_record_result_caps_payout_at_balance() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let drain = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let tournament_client = create_tournament_contract(&e);
+
+    token_admin_client.mint(&user1, &1000);
+    token_admin_client.mint(&user2, &1000);
+
+    let id = tournament_client.create_tournament(&admin, &token_client.address, &100);
+    tournament_client.register(&id, &user1);
+    tournament_client.register(&id, &user2);
+    tournament_client.start_tournament(&id);
+
+    // Reduce the escrowed balance below the recorded prize pool
+    token_client.transfer(&tournament_client.address, &drain, &50);
+    assert_eq!(tournament_client.get_prize_pool(&id), 200);
+
+    tournament_client.record_result(&id, &1, &user1);
+
+    assert_eq!(tournament_client.get_state(&id), TournamentState::Ended);
+    assert_eq!(token_client.balance(&user1), 900 + 150);
+    assert_eq!(token_client.balance(&tournament_client.address), 0);
+
+    let mut shortfall: Option<(i128, i128)> = None;
+    for (_, topics, data) in e.events().all().iter() {
+        let first: Val = topics.get(0).unwrap();
+        if Symbol::try_from_val(&e, &first) == Ok(Symbol::new(&e, "shortfall")) {
+            shortfall = Some(<(i128, i128)>::try_from_val(&e, &data).unwrap());
+        }
+    }
+    assert_eq!(shortfall, Some((200, 150)));
+}
+
+#[test]
+fn test_shortfall_stays_with_its_tournament() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let drain = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let client = create_tournament_contract(&e);
+
+    let short = client.create_tournament(&admin, &token_client.address, &100);
+    let full = client.create_tournament(&admin, &token_client.address, &100);
+    let mut players = Vec::new(&e);
+    for id in [short, short, full, full] {
+        let p = Address::generate(&e);
+        token_admin_client.mint(&p, &1000);
+        client.register(&id, &p);
+        players.push_back(p);
+    }
+    assert_eq!(client.get_escrow(&short), 200);
+    assert_eq!(client.get_escrow(&full), 200);
+
+    token_client.transfer(&client.address, &drain, &50);
+
+    // The short tournament's winner only gets what is left outside the
+    // other tournament's escrow
+    client.start_tournament(&short);
+    client.record_result(&short, &1, &players.get(0).unwrap());
+    assert_eq!(token_client.balance(&players.get(0).unwrap()), 900 + 150);
+    assert_eq!(client.get_escrow(&short), 0);
+    assert_eq!(client.get_escrow(&full), 200);
+    assert_eq!(token_client.balance(&client.address), 200);
+
+    // The other tournament still pays out in full
+    client.start_tournament(&full);
+    client.record_result(&full, &1, &players.get(2).unwrap());
+    assert_eq!(token_client.balance(&players.get(2).unwrap()), 900 + 200);
+    assert_eq!(client.get_escrow(&full), 0);
+}
+
+#[test]
+fn test_double_elimination_flow() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let tournament_client = create_tournament_contract(&e);
+
+    let id = tournament_client.create_tournament(&admin, &token_client.address, &100);
+    tournament_client.set_bracket_format(&id, &types::BracketFormat::Double);
+
+    let a = Address::generate(&e);
+    let b = Address::generate(&e);
+    let c = Address::generate(&e);
+    let d = Address::generate(&e);
+    for p in [&a, &b, &c, &d] {
+        token_admin_client.mint(p, &1000);
+        tournament_client.register(&id, p);
+    }
+    tournament_client.start_tournament(&id);
+
+    assert_eq!(tournament_client.get_bracket_size(&id), 4);
+
+    // Winners' bracket semi-finals send their losers to the losers' bracket
+    let semi1 = tournament_client.get_match(&id, &2).unwrap();
+    let semi2 = tournament_client.get_match(&id, &3).unwrap();
+    let (top, comeback) = (semi1.p1.unwrap(), semi1.p2.unwrap());
+    let (runner_up, out_first) = (semi2.p1.unwrap(), semi2.p2.unwrap());
+    tournament_client.record_result(&id, &2, &top);
+    tournament_client.record_result(&id, &3, &runner_up);
+    assert_eq!(tournament_client.get_losses(&id, &comeback), 1);
+    assert_eq!(tournament_client.get_active_players(&id).len(), 4);
+
+    // Losers' bracket round 1 is match 4 and knocks out a second-time loser
+    let losers_semi = tournament_client.get_match(&id, &4).unwrap();
+    assert_eq!(losers_semi.p1, Some(comeback.clone()));
+    assert_eq!(losers_semi.p2, Some(out_first.clone()));
+    tournament_client.record_result(&id, &4, &comeback);
+    assert_eq!(tournament_client.get_active_players(&id).len(), 3);
+
+    // The winners' final loser drops into the losers' final, match 5
+    tournament_client.record_result(&id, &1, &top);
+    let losers_final = tournament_client.get_match(&id, &5).unwrap();
+    assert_eq!(losers_final.p1, Some(comeback.clone()));
+    assert_eq!(losers_final.p2, Some(runner_up.clone()));
+    tournament_client.record_result(&id, &5, &comeback);
+    assert_eq!(tournament_client.get_active_players(&id).len(), 2);
+
+    // Eliminated players can't be recorded as winners anywhere
+    assert!(tournament_client.try_record_result(&id, &6, &runner_up).is_err());
+
+    // The comeback wins the grand final, which forces a deciding rematch
+    tournament_client.record_result(&id, &6, &comeback);
+    assert_eq!(tournament_client.get_state(&id), TournamentState::Started);
+    let rematch = tournament_client.get_match(&id, &7).unwrap();
+    assert_eq!(rematch.p1, Some(top.clone()));
+    assert_eq!(rematch.p2, Some(comeback.clone()));
+    tournament_client.record_result(&id, &7, &comeback);
+
+    assert_eq!(tournament_client.get_state(&id), TournamentState::Ended);
+    assert_eq!(tournament_client.get_losses(&id, &comeback), 1);
+    assert_eq!(tournament_client.get_losses(&id, &top), 2);
+    assert_eq!(token_client.balance(&comeback), 900 + 400);
+}
+
+#[test]
+fn test_double_elimination_unbeaten_finalist_skips_rematch() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let tournament_client = create_tournament_contract(&e);
+
+    let id = tournament_client.create_tournament(&admin, &token_client.address, &100);
+    tournament_client.set_bracket_format(&id, &types::BracketFormat::Double);
+
+    for _ in 0..3 {
+        let p = Address::generate(&e);
+        token_admin_client.mint(&p, &1000);
+        tournament_client.register(&id, &p);
+    }
+    tournament_client.start_tournament(&id);
+
+    // The bye leaves one side of the losers' first round empty, so the
+    // other semi-final's loser walks over into the losers' final
+    let semi1 = tournament_client.get_match(&id, &2).unwrap();
+    let semi2 = tournament_client.get_match(&id, &3).unwrap();
+    let top = semi1.p1.unwrap();
+    let (second, third) = (semi2.p1.unwrap(), semi2.p2.unwrap());
+    tournament_client.record_result(&id, &3, &second);
+    let walkover = tournament_client.get_match(&id, &4).unwrap();
+    assert_eq!(walkover.winner, Some(third.clone()));
+    assert_eq!(tournament_client.get_match(&id, &5).unwrap().p1, Some(third.clone()));
+
+    tournament_client.record_result(&id, &1, &top);
+    tournament_client.record_result(&id, &5, &second);
+    assert_eq!(tournament_client.get_active_players(&id).len(), 2);
+
+    tournament_client.record_result(&id, &6, &top);
+    assert_eq!(tournament_client.get_state(&id), TournamentState::Ended);
+    assert_eq!(tournament_client.get_match(&id, &7).unwrap().p1, None);
+    assert_eq!(token_client.balance(&top), 900 + 300);
+}
+
+#[test]
+fn test_single_elimination_knocks_out_on_first_loss() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let tournament_client = create_tournament_contract(&e);
+
+    let id = tournament_client.create_tournament(&admin, &token_client.address, &100);
+
+    let a = Address::generate(&e);
+    let b = Address::generate(&e);
+    let c = Address::generate(&e);
+    for p in [&a, &b, &c] {
+        token_admin_client.mint(p, &1000);
+        tournament_client.register(&id, p);
+    }
+    tournament_client.start_tournament(&id);
+
+    // One player has the bye; the other two meet in the second semi-final
+    let semi = tournament_client.get_match(&id, &3).unwrap();
+    let winner = semi.p1.unwrap();
+    let loser = semi.p2.unwrap();
+    tournament_client.record_result(&id, &3, &winner);
+    assert_eq!(tournament_client.get_losses(&id, &loser), 1);
+    let active = tournament_client.get_active_players(&id);
+    assert_eq!(active.len(), 2);
+    assert!(!active.contains(&loser));
+
+    // The loser is out and can't win the final
+    assert!(tournament_client.try_record_result(&id, &1, &loser).is_err());
+    tournament_client.record_result(&id, &1, &winner);
+    assert_eq!(tournament_client.get_state(&id), TournamentState::Ended);
+}
+
+#[test]
+fn test_four_player_bracket() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let tournament_client = create_tournament_contract(&e);
+    let id = tournament_client.create_tournament(&admin, &token_client.address, &100);
+
+    let mut players = Vec::new(&e);
+    for _ in 0..4 {
+        let p = Address::generate(&e);
+        token_admin_client.mint(&p, &1000);
+        tournament_client.register(&id, &p);
+        players.push_back(p);
+    }
+
+    tournament_client.start_tournament(&id);
+    assert_eq!(tournament_client.get_bracket_size(&id), 4);
+
+    // Semi-finals are matches 2 and 3 and cover every player once
+    let semi1 = tournament_client.get_match(&id, &2).unwrap();
+    let semi2 = tournament_client.get_match(&id, &3).unwrap();
+    let mut seeded = Vec::new(&e);
+    for m in [&semi1, &semi2] {
+        seeded.push_back(m.p1.clone().unwrap());
+        seeded.push_back(m.p2.clone().unwrap());
+        assert_eq!(m.winner, None);
+    }
+    for p in players.iter() {
+        assert!(seeded.contains(&p));
+    }
+
+    // The final can't be played before the semis
+    let final_match = tournament_client.get_match(&id, &1).unwrap();
+    assert_eq!(final_match.p1, None);
+    assert!(tournament_client
+        .try_record_result(&id, &1, &semi1.p1.clone().unwrap())
+        .is_err());
+
+    let finalist1 = semi1.p1.clone().unwrap();
+    let finalist2 = semi2.p2.clone().unwrap();
+    tournament_client.record_result(&id, &2, &finalist1);
+    assert_eq!(tournament_client.get_state(&id), TournamentState::Started);
+    assert_eq!(tournament_client.get_losses(&id, &semi1.p2.clone().unwrap()), 1);
+
+    // A decided match can't be recorded again
+    assert!(tournament_client.try_record_result(&id, &2, &finalist1).is_err());
+
+    tournament_client.record_result(&id, &3, &finalist2);
+    assert_eq!(tournament_client.get_active_players(&id).len(), 2);
+
+    let final_match = tournament_client.get_match(&id, &1).unwrap();
+    assert_eq!(final_match.p1, Some(finalist1.clone()));
+    assert_eq!(final_match.p2, Some(finalist2.clone()));
+
+    tournament_client.record_result(&id, &1, &finalist2);
+    assert_eq!(tournament_client.get_state(&id), TournamentState::Ended);
+    assert_eq!(token_client.balance(&finalist2), 900 + 400);
+    assert_eq!(token_client.balance(&finalist1), 900);
+}
+
+#[test]
+fn test_bracket_gives_byes_to_odd_fields() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let tournament_client = create_tournament_contract(&e);
+    let id = tournament_client.create_tournament(&admin, &token_client.address, &100);
+
+    for _ in 0..3 {
+        let p = Address::generate(&e);
+        token_admin_client.mint(&p, &1000);
+        tournament_client.register(&id, &p);
+    }
+
+    tournament_client.start_tournament(&id);
+    assert_eq!(tournament_client.get_bracket_size(&id), 4);
+
+    // The top rank gets the bye and already sits in the final
+    let semi1 = tournament_client.get_match(&id, &2).unwrap();
+    let semi2 = tournament_client.get_match(&id, &3).unwrap();
+    assert_eq!(semi1.p2, None);
+    assert_eq!(semi1.winner, semi1.p1);
+    assert!(semi2.p2.is_some());
+    assert_eq!(tournament_client.get_match(&id, &1).unwrap().p1, semi1.p1);
+
+    tournament_client.record_result(&id, &3, &semi2.p2.clone().unwrap());
+    tournament_client.record_result(&id, &1, &semi1.p1.clone().unwrap());
+    assert_eq!(tournament_client.get_state(&id), TournamentState::Ended);
+    assert_eq!(token_client.balance(&semi1.p1.unwrap()), 900 + 300);
+}
+
+#[test]
+fn test_registration_cap() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let tournament_client = create_tournament_contract(&e);
+    let id = tournament_client.create_tournament(&admin, &token_client.address, &100);
+
+    assert_eq!(tournament_client.slots_remaining(&id), u32::MAX);
+    tournament_client.set_registration_limits(&id, &2, &0);
+    assert_eq!(tournament_client.slots_remaining(&id), 2);
+
+    let players = [Address::generate(&e), Address::generate(&e), Address::generate(&e)];
+    for p in players.iter() {
+        token_admin_client.mint(p, &1000);
+    }
+
+    tournament_client.register(&id, &players[0]);
+    assert_eq!(tournament_client.slots_remaining(&id), 1);
+    tournament_client.register(&id, &players[1]);
+    assert_eq!(tournament_client.slots_remaining(&id), 0);
+
+    assert!(tournament_client.try_register(&id, &players[2]).is_err());
+    assert_eq!(tournament_client.get_participants(&id).len(), 2);
+    assert_eq!(token_client.balance(&players[2]), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Tournament full")]
+fn test_register_over_cap() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let tournament_client = create_tournament_contract(&e);
+    let id = tournament_client.create_tournament(&admin, &token_client.address, &100);
+    tournament_client.set_registration_limits(&id, &1, &0);
+
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    token_admin_client.mint(&user1, &1000);
+    token_admin_client.mint(&user2, &1000);
+
+    tournament_client.register(&id, &user1);
+    tournament_client.register(&id, &user2);
+}
+
+#[test]
+#[should_panic(expected = "Registration closed")]
+fn test_register_after_deadline() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let tournament_client = create_tournament_contract(&e);
+    let id = tournament_client.create_tournament(&admin, &token_client.address, &100);
+
+    e.ledger().set_timestamp(1_000);
+    tournament_client.set_registration_limits(&id, &0, &2_000);
+
+    let early = Address::generate(&e);
+    let late = Address::generate(&e);
+    token_admin_client.mint(&early, &1000);
+    token_admin_client.mint(&late, &1000);
+
+    // Still open at the deadline itself
+    e.ledger().set_timestamp(2_000);
+    tournament_client.register(&id, &early);
+
+    e.ledger().set_timestamp(2_001);
+    tournament_client.register(&id, &late);
+}
+
+/// The most recent event from `contract` whose first topic is `name`.
+fn last_event(e: &Env, contract: &Address, name: &str) -> Option<(Vec<Val>, Val)> {
+    let mut found = None;
+    for (address, topics, data) in e.events().all().iter() {
+        if &address != contract {
+            continue;
+        }
+        let first: Val = topics.get(0).unwrap();
+        if Symbol::try_from_val(e, &first) == Ok(Symbol::new(e, name)) {
+            found = Some((topics, data));
+        }
+    }
+    found
+}
+
+#[test]
+fn test_lifecycle_events() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    token_admin_client.mint(&user1, &1000);
+    token_admin_client.mint(&user2, &1000);
+
+    let played = create_tournament_contract(&e);
+    let played_id = played.create_tournament(&admin, &token_client.address, &100);
+
+    played.register(&played_id, &user1);
+    let (topics, data) = last_event(&e, &played.address, "register").unwrap();
+    assert_eq!(Address::try_from_val(&e, &topics.get(1).unwrap()).unwrap(), user1);
+    assert_eq!(i128::try_from_val(&e, &data).unwrap(), 100);
+    played.register(&played_id, &user2);
+
+    played.start_tournament(&played_id);
+    let (topics, data) = last_event(&e, &played.address, "start").unwrap();
+    assert_eq!(topics.len(), 1);
+    assert_eq!(u32::try_from_val(&e, &data).unwrap(), 2);
+
+    played.record_result(&played_id, &1, &user2);
+    let (topics, data) = last_event(&e, &played.address, "result").unwrap();
+    assert_eq!(Address::try_from_val(&e, &topics.get(1).unwrap()).unwrap(), user2);
+    assert_eq!(<(u32, Address)>::try_from_val(&e, &data).unwrap(), (1, user1.clone()));
+
+    let cancelled = create_tournament_contract(&e);
+    let cancelled_id = cancelled.create_tournament(&admin, &token_client.address, &100);
+    cancelled.register(&cancelled_id, &user1);
+
+    cancelled.cancel_tournament(&cancelled_id);
+    let (topics, data) = last_event(&e, &cancelled.address, "cancel").unwrap();
+    assert_eq!(topics.len(), 1);
+    assert_eq!(i128::try_from_val(&e, &data).unwrap(), 100);
+
+    cancelled.withdraw_refund(&cancelled_id, &user1);
+    let (topics, data) = last_event(&e, &cancelled.address, "refund").unwrap();
+    assert_eq!(Address::try_from_val(&e, &topics.get(1).unwrap()).unwrap(), user1);
+    assert_eq!(i128::try_from_val(&e, &data).unwrap(), 100);
+}
+
+#[test]
+fn test_concurrent_tournaments() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let user3 = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let client = create_tournament_contract(&e);
+    for user in [&user1, &user2, &user3] {
+        token_admin_client.mint(user, &1000);
+    }
+
+    let cup = client.create_tournament(&admin, &token_client.address, &100);
+    let league = client.create_tournament(&admin, &token_client.address, &50);
+    assert_ne!(cup, league);
+
+    client.register(&cup, &user1);
+    client.register(&cup, &user2);
+    client.register(&league, &user2);
+    client.register(&league, &user3);
+
+    assert_eq!(client.get_prize_pool(&cup), 200);
+    assert_eq!(client.get_prize_pool(&league), 100);
+
+    // Starting and finishing one leaves the other untouched
+    client.start_tournament(&cup);
+    assert_eq!(client.get_state(&league), TournamentState::Open);
+
+    client.record_result(&cup, &1, &user1);
+    assert_eq!(client.get_state(&cup), TournamentState::Ended);
+    assert_eq!(client.get_state(&league), TournamentState::Open);
+    assert_eq!(client.get_losses(&league, &user2), 0);
+    assert_eq!(token_client.balance(&user1), 900 + 200);
+
+    // The cup's payout didn't touch the league's escrow
+    assert_eq!(token_client.balance(&client.address), 100);
+
+    client.cancel_tournament(&league);
+    client.withdraw_refund(&league, &user2);
+    client.withdraw_refund(&league, &user3);
+    assert!(!client.is_refunded(&cup, &user2));
+    assert_eq!(token_client.balance(&user2), 900);
+    assert_eq!(token_client.balance(&user3), 1000);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_rollover_between_tournaments_in_one_contract() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let sponsor = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let client = create_tournament_contract(&e);
+    token_admin_client.mint(&sponsor, &1000);
+
+    let first_id = client.create_tournament(&admin, &token_client.address, &100);
+    let next_id = client.create_tournament(&admin, &token_client.address, &100);
+
+    client.add_prize(&first_id, &sponsor, &300);
+    client.rollover_prize(&admin, &first_id, &client.address, &next_id);
+
+    assert_eq!(client.get_prize_pool(&first_id), 0);
+    assert_eq!(client.get_prize_pool(&next_id), 300);
+    assert_eq!(token_client.balance(&client.address), 300);
+}
+
+#[test]
+#[should_panic(expected = "Tournament not found")]
+fn test_unknown_tournament() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let client = create_tournament_contract(&e);
+    client.register(&7, &Address::generate(&e));
+}
+
+#[test]
+fn test_top_seeds_only_meet_in_final() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let client = create_tournament_contract(&e);
+    let id = client.create_tournament(&admin, &token_client.address, &10);
+
+    let mut players = Vec::new(&e);
+    for _ in 0..8 {
+        let p = Address::generate(&e);
+        token_admin_client.mint(&p, &1000);
+        client.register(&id, &p);
+        players.push_back(p);
+    }
+
+    // The two strongest registered back to back
+    let top = players.get(0).unwrap();
+    let second = players.get(1).unwrap();
+    client.set_seed(&admin, &id, &top, &1);
+    client.set_seed(&admin, &id, &second, &2);
+    assert_eq!(client.get_seed(&id, &top), Some(1));
+    assert_eq!(client.get_seed(&id, &players.get(2).unwrap()), None);
+
+    client.start_tournament(&id);
+    assert_eq!(client.get_bracket_size(&id), 8);
+
+    // 1 and 2 sit in opposite halves: matches 4-5 feed 2, matches 6-7 feed 3
+    let first_round = |ids: [u32; 2]| {
+        let mut found = Vec::new(&e);
+        for match_id in ids {
+            let m = client.get_match(&id, &match_id).unwrap();
+            found.push_back(m.p1.unwrap());
+            found.push_back(m.p2.unwrap());
+        }
+        found
+    };
+    let upper = first_round([4, 5]);
+    let lower = first_round([6, 7]);
+    assert!(upper.contains(&top) && !upper.contains(&second));
+    assert!(lower.contains(&second) && !lower.contains(&top));
+
+    // Seed 1 opens against the lowest rank, the last unseeded registrant
+    let opener = client.get_match(&id, &4).unwrap();
+    assert_eq!(opener.p1, Some(top.clone()));
+    assert_eq!(opener.p2, Some(players.get(7).unwrap()));
+
+    // Let the top seeds win every match; they meet in the final
+    for match_id in (2..8u32).rev() {
+        let m = client.get_match(&id, &match_id).unwrap();
+        let p1 = m.p1.unwrap();
+        let p2 = m.p2.unwrap();
+        let winner = if p2 == top || p2 == second { p2 } else { p1 };
+        client.record_result(&id, &match_id, &winner);
+    }
+    let final_match = client.get_match(&id, &1).unwrap();
+    assert_eq!(final_match.p1, Some(top));
+    assert_eq!(final_match.p2, Some(second));
+}
+
+#[test]
+#[should_panic(expected = "Seed already taken")]
+fn test_duplicate_seed_rejected() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let client = create_tournament_contract(&e);
+    let id = client.create_tournament(&admin, &token_client.address, &10);
+
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    token_admin_client.mint(&user1, &1000);
+    token_admin_client.mint(&user2, &1000);
+    client.register(&id, &user1);
+    client.register(&id, &user2);
+
+    client.set_seed(&admin, &id, &user1, &1);
+    client.set_seed(&admin, &id, &user2, &1);
+}
+
+#[test]
+fn test_unseeded_bracket_follows_registration_order() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token_admin = Address::generate(&e);
+    let (token_client, token_admin_client) = create_token_contract(&e, &token_admin);
+    let client = create_tournament_contract(&e);
+    let id = client.create_tournament(&admin, &token_client.address, &10);
+
+    let mut players = Vec::new(&e);
+    for _ in 0..4 {
+        let p = Address::generate(&e);
+        token_admin_client.mint(&p, &1000);
+        client.register(&id, &p);
+        players.push_back(p);
+    }
+    client.start_tournament(&id);
+
+    // First registrant plays the last, second plays third
+    let semi1 = client.get_match(&id, &2).unwrap();
+    let semi2 = client.get_match(&id, &3).unwrap();
+    assert_eq!(semi1.p1, players.get(0));
+    assert_eq!(semi1.p2, players.get(3));
+    assert_eq!(semi2.p1, players.get(1));
+    assert_eq!(semi2.p2, players.get(2));
 }
@@ -9,30 +9,59 @@ pub enum TournamentState {
     Cancelled,
 }
 
+/// Single elimination knocks players out on their first loss; double
+/// elimination sends them to the losers' bracket and knocks them out on the second.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BracketFormat {
+    Single,
+    Double,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TournamentConfig {
     pub admin: Address,
     pub token: Address,
     pub entry_fee: i128,
+    pub max_participants: u32,      // 0 = no cap
+    pub registration_deadline: u64, // 0 = no deadline
 }
 
+/// Everything but the counter and the per-token escrow totals is scoped to
+/// one tournament id.
 #[contracttype]
 #[allow(dead_code)]
 pub enum DataKey {
-    Config,
-    State,
-    Participants, // Vector<Address>
-    Match(u32),   // Map match_id to Match
-    Results,      // Map match_id to Winner Address
-    TotalPrize,
+    TournamentCounter,     // u32 - last id handed out
+    Config(u32),
+    State(u32),
+    Participants(u32),     // Vector<Address>
+    Match(u32, u32),       // (tournament_id, match_id) -> Match
+    Results(u32),          // Map match_id to Winner Address
+    TotalPrize(u32),
+    Format(u32),           // BracketFormat
+    Losses(u32),           // Map participant to number of losses
+    BracketSize(u32),      // u32 - first-round slots, a power of two
+    Refunded(u32, Address), // bool - entry fee returned after cancellation
+    Seeds(u32),            // Map participant to seed, 1 = strongest
+    Walkovers(u32),        // Map match_id to slots a bye leaves empty
+    Escrow(u32),           // i128 - tokens held for the tournament
+    TokenEscrow(Address),  // i128 - tokens held across every tournament using the token
 }
 
+/// A bracket match. The winners' bracket is numbered like a binary heap:
+/// its final is match 1 and match `id` feeds its winner into match `id / 2`,
+/// so first-round matches are `bracket_size / 2 .. bracket_size`.
+/// A player without an opponent in the first round gets a bye and advances.
+///
+/// Double elimination adds the losers' bracket as matches
+/// `bracket_size .. 2 * bracket_size - 2`, round by round, then the grand
+/// final `2 * bracket_size - 2` and its deciding rematch right after it.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[allow(dead_code)]
 pub struct Match {
-    pub p1: Address,
-    pub p2: Address,
+    pub p1: Option<Address>,
+    pub p2: Option<Address>,
     pub winner: Option<Address>,
 }